@@ -0,0 +1,16 @@
+#pragma once
+#include <exception>
+#include <string>
+
+/**
+ * Returns the message of an exception caught by a Rust binding, so that it outlives the exception.
+ *
+ * The message is kept in a thread local buffer until the next error raised on the same thread, which gives the
+ * Rust caller time to copy it.
+ */
+inline const char* rust_bind_error(std::exception const& e)
+{
+    thread_local std::string message;
+    message = e.what();
+    return message.c_str();
+}
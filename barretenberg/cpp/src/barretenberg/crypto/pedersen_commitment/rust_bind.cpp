@@ -2,6 +2,7 @@
 #include "../pedersen_hash/pedersen.hpp"
 #include "barretenberg/common/serialize.hpp"
 #include "pedersen.hpp"
+#include "barretenberg/common/rust_bind.hpp"
 
 extern "C" {
 
@@ -17,7 +18,7 @@ WASM_EXPORT const char* rust_pedersen_commit(fr::vec_in_buf inputs_buffer, fr::o
         serialize::write(output, pedersen_commitment);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 }
\ No newline at end of file
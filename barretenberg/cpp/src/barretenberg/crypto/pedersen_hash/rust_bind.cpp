@@ -2,6 +2,7 @@
 #include "barretenberg/common/mem.hpp"
 #include "barretenberg/common/serialize.hpp"
 #include "pedersen.hpp"
+#include "barretenberg/common/rust_bind.hpp"
 
 extern "C" {
 
@@ -18,7 +19,7 @@ WASM_EXPORT const char* rust_pedersen_hash(uint8_t const* inputs_buffer, uint32_
     barretenberg::fr::serialize_to_buffer(r, output);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -33,7 +34,7 @@ WASM_EXPORT const char* rust_pedersen_hash_buffer(uint8_t const* input_buffer, u
     barretenberg::fr::serialize_to_buffer(r, output);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 }
\ No newline at end of file
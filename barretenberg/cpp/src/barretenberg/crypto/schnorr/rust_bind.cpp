@@ -1,6 +1,7 @@
 #include "rust_bind.hpp"
 #include "multisig.hpp"
 #include "schnorr.hpp"
+#include "barretenberg/common/rust_bind.hpp"
 
 extern "C" {
 
@@ -17,7 +18,7 @@ WASM_EXPORT const char* rust_schnorr_compute_public_key(uint8_t const* private_k
         serialize::write(public_key_buf, pub_key);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -30,7 +31,7 @@ WASM_EXPORT const char* rust_schnorr_negate_public_key(uint8_t const* public_key
         serialize::write(output, -account_public_key);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -49,7 +50,7 @@ WASM_EXPORT const char* rust_schnorr_construct_signature(uint8_t const* message_
         write(e, sig.e);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -68,7 +69,7 @@ WASM_EXPORT const char* rust_schnorr_verify_signature(
             message, pubk, sig);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -87,7 +88,7 @@ WASM_EXPORT const char* rust_schnorr_multisig_create_multisig_public_key(uint8_t
         serialize::write(multisig_pubkey_buf, agg_pubkey);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -110,7 +111,7 @@ WASM_EXPORT const char* rust_schnorr_multisig_validate_and_combine_signer_pubkey
         }
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -125,7 +126,7 @@ WASM_EXPORT const char* rust_schnorr_multisig_construct_signature_round_1(uint8_
         serialize::write(round_one_private_output_buf, private_output);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -159,7 +160,7 @@ WASM_EXPORT const char* rust_schnorr_multisig_construct_signature_round_2(uint8_
         }
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -190,7 +191,7 @@ WASM_EXPORT const char* rust_schnorr_multisig_combine_signatures(uint8_t const*
         }
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 }
\ No newline at end of file
@@ -31,6 +31,20 @@ void AcirComposer::create_circuit(acir_format::acir_format& constraint_system)
     vinfo("gates: ", builder_.get_total_circuit_size());
 }
 
+/**
+ * @brief Drops the circuit and the keys computed for it, so that another circuit can be proven with this composer.
+ *        The size hint is kept, circuits of the same subgroup size reserve the same amount of memory.
+ */
+void AcirComposer::reset()
+{
+    builder_ = acir_format::Builder(size_hint_);
+    exact_circuit_size_ = 0;
+    total_circuit_size_ = 0;
+    circuit_subgroup_size_ = 0;
+    proving_key_ = nullptr;
+    verification_key_ = nullptr;
+}
+
 std::shared_ptr<proof_system::plonk::proving_key> AcirComposer::init_proving_key(
     acir_format::acir_format& constraint_system)
 {
@@ -87,6 +101,13 @@ std::shared_ptr<proof_system::plonk::verification_key> AcirComposer::init_verifi
     return verification_key_;
 }
 
+void AcirComposer::load_proving_key(proof_system::plonk::proving_key_data&& data)
+{
+    // The prover commits to polynomials of the circuit size, which takes one more point than that.
+    auto crs = srs::get_crs_factory()->get_prover_crs(data.circuit_size + 1);
+    proving_key_ = std::make_shared<proof_system::plonk::proving_key>(std::move(data), crs);
+}
+
 void AcirComposer::load_verification_key(proof_system::plonk::verification_key_data&& data)
 {
     verification_key_ = std::make_shared<proof_system::plonk::verification_key>(
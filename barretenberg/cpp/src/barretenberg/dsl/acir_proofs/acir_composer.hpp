@@ -14,12 +14,16 @@ class AcirComposer {
 
     void create_circuit(acir_format::acir_format& constraint_system);
 
+    void reset();
+
     std::shared_ptr<proof_system::plonk::proving_key> init_proving_key(acir_format::acir_format& constraint_system);
 
     std::vector<uint8_t> create_proof(acir_format::acir_format& constraint_system,
                                       acir_format::WitnessVector& witness,
                                       bool is_recursive);
 
+    void load_proving_key(proof_system::plonk::proving_key_data&& data);
+
     void load_verification_key(proof_system::plonk::verification_key_data&& data);
 
     std::shared_ptr<proof_system::plonk::verification_key> init_verification_key();
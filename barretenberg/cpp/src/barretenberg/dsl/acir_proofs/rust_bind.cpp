@@ -2,8 +2,49 @@
 #include "../acir_format/acir_to_constraint_buf.hpp"
 #include "acir_composer.hpp"
 #include "barretenberg/dsl/acir_format/acir_format.hpp"
+#include "barretenberg/plonk/proof_system/proving_key/serialize.hpp"
 #include "barretenberg/plonk/proof_system/verification_key/verification_key.hpp"
 #include "barretenberg/srs/global_crs.hpp"
+#include "barretenberg/ultra_honk/ultra_composer.hpp"
+#include "barretenberg/common/rust_bind.hpp"
+
+namespace {
+
+using HonkVerificationKey = proof_system::honk::flavor::Ultra::VerificationKey;
+
+/**
+ * The UltraHonk verification key has no serialization of its own, so we write the circuit size and the number of
+ * public inputs followed by every precomputed commitment, in the order of the flavor entities.
+ */
+std::vector<uint8_t> honk_verification_key_to_buffer(HonkVerificationKey const& key)
+{
+    std::vector<uint8_t> buf;
+    write(buf, static_cast<uint32_t>(key.circuit_size));
+    write(buf, static_cast<uint32_t>(key.num_public_inputs));
+    for (auto& commitment : key.get_all()) {
+        write(buf, commitment);
+    }
+    return buf;
+}
+
+std::shared_ptr<HonkVerificationKey> honk_verification_key_from_buffer(std::vector<uint8_t> const& buf)
+{
+    uint8_t const* it = buf.data();
+    uint32_t circuit_size = 0;
+    uint32_t num_public_inputs = 0;
+    read(it, circuit_size);
+    read(it, num_public_inputs);
+    auto key = std::make_shared<HonkVerificationKey>(circuit_size, num_public_inputs);
+    for (auto& commitment : key->get_all()) {
+        read(it, commitment);
+    }
+    if (it != buf.data() + buf.size()) {
+        throw_or_abort("UltraHonk verification key has an unexpected length.");
+    }
+    return key;
+}
+
+} // namespace
 
 extern "C" {
 
@@ -17,7 +58,7 @@ const char* rust_acir_get_circuit_sizes(uint8_t const* acir_vec, uint32_t* exact
         *subgroup = (uint32_t)composer.get_circuit_subgroup_size(composer.get_total_circuit_size());
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -27,7 +68,7 @@ const char* rust_acir_new_acir_composer(uint32_t const* size_hint, out_ptr out)
         *out = new acir_proofs::AcirComposer(*size_hint);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -37,7 +78,17 @@ const char* rust_acir_delete_acir_composer(in_ptr acir_composer_ptr)
         delete reinterpret_cast<acir_proofs::AcirComposer*>(*acir_composer_ptr);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_acir_reset_acir_composer(in_ptr acir_composer_ptr)
+{
+    try {
+        reinterpret_cast<acir_proofs::AcirComposer*>(*acir_composer_ptr)->reset();
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
     }
 }
 
@@ -50,7 +101,7 @@ const char* rust_acir_init_proving_key(in_ptr acir_composer_ptr, uint8_t const*
         acir_composer->init_proving_key(constraint_system);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -69,7 +120,32 @@ const char* rust_acir_create_proof(in_ptr acir_composer_ptr,
         *out = to_heap_buffer(proof_data);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_acir_get_proving_key(in_ptr acir_composer_ptr, uint8_t const* acir_vec, uint8_t** out)
+{
+    try {
+        auto acir_composer = reinterpret_cast<acir_proofs::AcirComposer*>(*acir_composer_ptr);
+        auto constraint_system = acir_format::circuit_buf_to_acir_format(from_buffer<std::vector<uint8_t>>(acir_vec));
+        auto pk = acir_composer->init_proving_key(constraint_system);
+        *out = to_heap_buffer(*pk);
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_acir_load_proving_key(in_ptr acir_composer_ptr, uint8_t const* pk_buf)
+{
+    try {
+        auto acir_composer = reinterpret_cast<acir_proofs::AcirComposer*>(*acir_composer_ptr);
+        auto pk_data = from_buffer<plonk::proving_key_data>(pk_buf);
+        acir_composer->load_proving_key(std::move(pk_data));
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
     }
 }
 
@@ -81,7 +157,7 @@ const char* rust_acir_load_verification_key(in_ptr acir_composer_ptr, uint8_t co
         acir_composer->load_verification_key(std::move(vk_data));
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -92,7 +168,7 @@ const char* rust_acir_init_verification_key(in_ptr acir_composer_ptr)
         acir_composer->init_verification_key();
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -105,7 +181,7 @@ const char* rust_acir_get_verification_key(in_ptr acir_composer_ptr, uint8_t** o
         *out = to_heap_buffer(*vk);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -120,7 +196,7 @@ const char* rust_acir_verify_proof(in_ptr acir_composer_ptr,
         *result = acir_composer->verify_proof(proof, *is_recursive);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -132,7 +208,7 @@ const char* rust_acir_get_solidity_verifier(in_ptr acir_composer_ptr, out_str_bu
         *out = to_heap_buffer(str);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -149,7 +225,7 @@ const char* rust_acir_serialize_proof_into_fields(in_ptr acir_composer_ptr,
         *out = to_heap_buffer(proof_as_fields);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 
@@ -168,7 +244,54 @@ const char* rust_acir_serialize_verification_key_into_fields(in_ptr acir_compose
         write(out_key_hash, vk_hash);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_acir_prove_ultra_honk(uint8_t const* acir_vec, uint8_t const* witness_vec, uint8_t** out)
+{
+    try {
+        auto constraint_system = acir_format::circuit_buf_to_acir_format(from_buffer<std::vector<uint8_t>>(acir_vec));
+        auto witness = acir_format::witness_buf_to_witness_data(from_buffer<std::vector<uint8_t>>(witness_vec));
+        auto builder = acir_format::create_circuit_with_witness(constraint_system, witness);
+
+        proof_system::honk::UltraComposer composer;
+        auto instance = composer.create_instance(builder);
+        auto prover = composer.create_prover(instance);
+        auto proof = prover.construct_proof();
+        *out = to_heap_buffer(proof.proof_data);
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_acir_get_ultra_honk_verification_key(uint8_t const* acir_vec, uint8_t** out)
+{
+    try {
+        auto constraint_system = acir_format::circuit_buf_to_acir_format(from_buffer<std::vector<uint8_t>>(acir_vec));
+        auto builder = acir_format::create_circuit(constraint_system);
+
+        proof_system::honk::UltraComposer composer;
+        auto instance = composer.create_instance(builder);
+        *out = to_heap_buffer(honk_verification_key_to_buffer(*instance->verification_key));
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_acir_verify_ultra_honk(uint8_t const* proof_buf, uint8_t const* vk_buf, bool* result)
+{
+    try {
+        auto proof = from_buffer<std::vector<uint8_t>>(proof_buf);
+        auto verification_key = honk_verification_key_from_buffer(from_buffer<std::vector<uint8_t>>(vk_buf));
+
+        proof_system::honk::UltraVerifier verifier(verification_key);
+        *result = verifier.verify_proof({ proof });
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
     }
 }
 }
\ No newline at end of file
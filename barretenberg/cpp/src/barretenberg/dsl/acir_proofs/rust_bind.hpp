@@ -17,6 +17,8 @@ const char* rust_acir_new_acir_composer(uint32_t const* size_hint, out_ptr out);
 
 const char* rust_acir_delete_acir_composer(in_ptr acir_composer_ptr);
 
+const char* rust_acir_reset_acir_composer(in_ptr acir_composer_ptr);
+
 const char* rust_acir_create_circuit(in_ptr acir_composer_ptr,
                                      uint8_t const* constraint_system_buf,
                                      uint32_t const* size_hint);
@@ -34,6 +36,8 @@ const char* rust_acir_create_proof(in_ptr acir_composer_ptr,
                                    bool const* is_recursive,
                                    uint8_t** out);
 
+const char* rust_acir_load_proving_key(in_ptr acir_composer_ptr, uint8_t const* pk_buf);
+
 const char* rust_acir_load_verification_key(in_ptr acir_composer_ptr, uint8_t const* vk_buf);
 
 const char* rust_acir_init_verification_key(in_ptr acir_composer_ptr);
@@ -57,4 +61,10 @@ const char* rust_acir_serialize_proof_into_fields(in_ptr acir_composer_ptr,
 const char* rust_acir_serialize_verification_key_into_fields(in_ptr acir_composer_ptr,
                                                              fr::vec_out_buf out_vkey,
                                                              fr::out_buf out_key_hash);
+
+const char* rust_acir_prove_ultra_honk(uint8_t const* constraint_system_buf, uint8_t const* witness_buf, uint8_t** out);
+
+const char* rust_acir_get_ultra_honk_verification_key(uint8_t const* constraint_system_buf, uint8_t** out);
+
+const char* rust_acir_verify_ultra_honk(uint8_t const* proof_buf, uint8_t const* vk_buf, bool* result);
 }
\ No newline at end of file
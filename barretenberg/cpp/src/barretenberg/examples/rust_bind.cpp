@@ -1,6 +1,7 @@
 #include "barretenberg/srs/global_crs.hpp"
 #include "rust_bind.hpp"
 #include "simple/simple.hpp"
+#include "barretenberg/common/rust_bind.hpp"
 
 using namespace proof_system::plonk::stdlib::types;
 
@@ -14,7 +15,7 @@ const char* rust_examples_simple_create_and_verify_proof(bool* valid)
         examples::simple::delete_builder_and_composer(ptrs);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
     }
 }
 }
\ No newline at end of file
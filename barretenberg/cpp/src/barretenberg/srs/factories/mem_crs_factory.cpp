@@ -1,4 +1,5 @@
 #include "mem_crs_factory.hpp"
+#include "barretenberg/common/throw_or_abort.hpp"
 #include "barretenberg/ecc/curves/bn254/bn254.hpp"
 #include "barretenberg/ecc/curves/bn254/g1.hpp"
 #include "barretenberg/ecc/curves/bn254/pairing.hpp"
@@ -10,26 +11,39 @@ namespace {
 using namespace barretenberg;
 using namespace barretenberg::srs::factories;
 
-class MemProverCrs : public ProverCrs<curve::BN254> {
+template <typename Curve> class MemProverCrs : public ProverCrs<Curve> {
+    using AffineElement = typename Curve::AffineElement;
+
   public:
-    MemProverCrs(std::vector<g1::affine_element> const& points)
+    MemProverCrs(std::vector<AffineElement> const& points)
         : num_points(points.size())
     {
-        monomials_ = scalar_multiplication::point_table_alloc<g1::affine_element>(num_points);
+        monomials_ = scalar_multiplication::point_table_alloc<AffineElement>(num_points);
         std::copy(points.begin(), points.end(), monomials_.get());
-        scalar_multiplication::generate_pippenger_point_table<curve::BN254>(
-            monomials_.get(), monomials_.get(), num_points);
+        scalar_multiplication::generate_pippenger_point_table<Curve>(monomials_.get(), monomials_.get(), num_points);
     }
 
-    g1::affine_element* get_monomial_points() override { return monomials_.get(); }
+    AffineElement* get_monomial_points() override { return monomials_.get(); }
 
     size_t get_monomial_size() const override { return num_points; }
 
   private:
     size_t num_points;
-    std::shared_ptr<g1::affine_element[]> monomials_;
+    std::shared_ptr<AffineElement[]> monomials_;
 };
 
+/**
+ * Fails with a recoverable error when a circuit needs more points than the CRS holds, instead of letting the
+ * multi-scalar multiplications read past the end of the point table.
+ */
+void check_crs_size(size_t degree, size_t num_points)
+{
+    if (degree > num_points) {
+        throw_or_abort("SRS too small for circuit: " + std::to_string(degree) + " points required, " +
+                       std::to_string(num_points) + " available");
+    }
+}
+
 class MemVerifierCrs : public VerifierCrs<curve::BN254> {
   public:
     MemVerifierCrs(g2::affine_element const& g2_point)
@@ -55,17 +69,56 @@ class MemVerifierCrs : public VerifierCrs<curve::BN254> {
     pairing::miller_lines* precomputed_g2_lines;
 };
 
+/**
+ * Returns the first point of a CRS, failing with a recoverable error on an empty one rather than reading past the
+ * end of the vector.
+ */
+template <typename AffineElement> AffineElement first_point(std::vector<AffineElement> const& points)
+{
+    if (points.empty()) {
+        throw_or_abort("SRS holds no points");
+    }
+    return points.front();
+}
+
+class MemGrumpkinVerifierCrs : public VerifierCrs<curve::Grumpkin> {
+    using AffineElement = curve::Grumpkin::AffineElement;
+
+  public:
+    MemGrumpkinVerifierCrs(std::vector<AffineElement> const& points)
+        : num_points(points.size())
+        , first_g1(first_point(points))
+    {
+        monomials_ = scalar_multiplication::point_table_alloc<AffineElement>(num_points);
+        std::copy(points.begin(), points.end(), monomials_.get());
+        scalar_multiplication::generate_pippenger_point_table<curve::Grumpkin>(
+            monomials_.get(), monomials_.get(), num_points);
+    }
+
+    AffineElement* get_monomial_points() const override { return monomials_.get(); }
+
+    size_t get_monomial_size() const override { return num_points; }
+
+    AffineElement get_first_g1() const override { return first_g1; };
+
+  private:
+    size_t num_points;
+    AffineElement first_g1;
+    std::shared_ptr<AffineElement[]> monomials_;
+};
+
 } // namespace
 
 namespace barretenberg::srs::factories {
 
 MemCrsFactory::MemCrsFactory(std::vector<g1::affine_element> const& points, g2::affine_element const g2_point)
-    : prover_crs_(std::make_shared<MemProverCrs>(points))
+    : prover_crs_(std::make_shared<MemProverCrs<curve::BN254>>(points))
     , verifier_crs_(std::make_shared<MemVerifierCrs>(g2_point))
 {}
 
-std::shared_ptr<barretenberg::srs::factories::ProverCrs<curve::BN254>> MemCrsFactory::get_prover_crs(size_t)
+std::shared_ptr<barretenberg::srs::factories::ProverCrs<curve::BN254>> MemCrsFactory::get_prover_crs(size_t degree)
 {
+    check_crs_size(degree, prover_crs_->get_monomial_size());
     return prover_crs_;
 }
 
@@ -74,4 +127,22 @@ std::shared_ptr<barretenberg::srs::factories::VerifierCrs<curve::BN254>> MemCrsF
     return verifier_crs_;
 }
 
+MemGrumpkinCrsFactory::MemGrumpkinCrsFactory(std::vector<curve::Grumpkin::AffineElement> const& points)
+    : prover_crs_(std::make_shared<MemProverCrs<curve::Grumpkin>>(points))
+    , verifier_crs_(std::make_shared<MemGrumpkinVerifierCrs>(points))
+{}
+
+std::shared_ptr<barretenberg::srs::factories::ProverCrs<curve::Grumpkin>> MemGrumpkinCrsFactory::get_prover_crs(
+    size_t degree)
+{
+    check_crs_size(degree, prover_crs_->get_monomial_size());
+    return prover_crs_;
+}
+
+std::shared_ptr<barretenberg::srs::factories::VerifierCrs<curve::Grumpkin>> MemGrumpkinCrsFactory::get_verifier_crs(
+    size_t)
+{
+    return verifier_crs_;
+}
+
 } // namespace barretenberg::srs::factories
\ No newline at end of file
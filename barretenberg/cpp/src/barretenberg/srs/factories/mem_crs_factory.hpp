@@ -2,6 +2,7 @@
 #include "barretenberg/ecc/curves/bn254/bn254.hpp"
 #include "barretenberg/ecc/curves/bn254/g1.hpp"
 #include "barretenberg/ecc/curves/bn254/g2.hpp"
+#include "barretenberg/ecc/curves/grumpkin/grumpkin.hpp"
 #include "crs_factory.hpp"
 #include <cstddef>
 #include <utility>
@@ -28,4 +29,24 @@ class MemCrsFactory : public CrsFactory<curve::BN254> {
     std::shared_ptr<barretenberg::srs::factories::VerifierCrs<curve::BN254>> verifier_crs_;
 };
 
+/**
+ * Create Grumpkin reference strings given the monomial points in memory.
+ *
+ * The Grumpkin CRS has no G2 element, the verifier uses the same monomial points as the prover.
+ */
+class MemGrumpkinCrsFactory : public CrsFactory<curve::Grumpkin> {
+  public:
+    MemGrumpkinCrsFactory(std::vector<curve::Grumpkin::AffineElement> const& points);
+    MemGrumpkinCrsFactory(MemGrumpkinCrsFactory&& other) = default;
+
+    std::shared_ptr<barretenberg::srs::factories::ProverCrs<curve::Grumpkin>> get_prover_crs(size_t degree) override;
+
+    std::shared_ptr<barretenberg::srs::factories::VerifierCrs<curve::Grumpkin>> get_verifier_crs(
+        size_t degree = 0) override;
+
+  private:
+    std::shared_ptr<barretenberg::srs::factories::ProverCrs<curve::Grumpkin>> prover_crs_;
+    std::shared_ptr<barretenberg::srs::factories::VerifierCrs<curve::Grumpkin>> verifier_crs_;
+};
+
 } // namespace barretenberg::srs::factories
@@ -23,6 +23,12 @@ void init_crs_factory(std::string crs_path)
     crs_factory = std::make_shared<factories::FileCrsFactory<curve::BN254>>(crs_path);
 }
 
+// Initializes the grumpkin crs using the memory buffer
+void init_grumpkin_crs_factory(std::vector<curve::Grumpkin::AffineElement> const& points)
+{
+    grumpkin_crs_factory = std::make_shared<factories::MemGrumpkinCrsFactory>(points);
+}
+
 void init_grumpkin_crs_factory(std::string crs_path)
 {
     grumpkin_crs_factory = std::make_shared<factories::FileCrsFactory<curve::Grumpkin>>(crs_path);
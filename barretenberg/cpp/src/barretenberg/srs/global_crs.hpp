@@ -7,6 +7,7 @@ void init_crs_factory(std::vector<barretenberg::g1::affine_element> const& point
                       barretenberg::g2::affine_element const g2_point);
 
 void init_crs_factory(std::string crs_path);
+void init_grumpkin_crs_factory(std::vector<curve::Grumpkin::AffineElement> const& points);
 void init_grumpkin_crs_factory(std::string crs_path);
 
 std::shared_ptr<barretenberg::srs::factories::CrsFactory<curve::BN254>> get_crs_factory();
@@ -5,6 +5,7 @@
 #include <barretenberg/common/streams.hpp>
 #include <barretenberg/ecc/curves/bn254/g1.hpp>
 #include <barretenberg/ecc/curves/bn254/g2.hpp>
+#include "barretenberg/common/rust_bind.hpp"
 
 using namespace barretenberg;
 
@@ -26,7 +27,21 @@ const char* rust_srs_init_srs(uint8_t const* points_buf, uint32_t const* num_poi
         barretenberg::srs::init_crs_factory(points, g2_point);
         return nullptr;
     } catch (const std::exception& e) {
-        return e.what(); // return the exception message
+        return rust_bind_error(e);
+    }
+}
+
+const char* rust_srs_init_grumpkin_srs(uint8_t const* points_buf, uint32_t const* num_points)
+{
+    try {
+        auto points = std::vector<curve::Grumpkin::AffineElement>(*num_points);
+        srs::IO<curve::Grumpkin>::read_affine_elements_from_buffer(
+            points.data(), (char*)points_buf, points.size() * 64);
+
+        barretenberg::srs::init_grumpkin_crs_factory(points);
+        return nullptr;
+    } catch (const std::exception& e) {
+        return rust_bind_error(e);
     }
 }
 }
\ No newline at end of file
@@ -1,15 +1,16 @@
 use noir_rs_barretenberg::{
     acir::{
-        create_proof, delete, get_solidity_verifier, get_verification_key, init_proving_key,
-        init_verification_key, load_verification_key, new_acir_composer,
-        serialize_proof_into_fields, serialize_verification_key_into_fields, verify_proof,
-        AcirComposerPtr,
+        create_proof, delete, get_proving_key, get_solidity_verifier, get_verification_key,
+        init_proving_key, init_verification_key, load_proving_key, load_verification_key,
+        new_acir_composer, reset, serialize_proof_into_fields,
+        serialize_verification_key_into_fields, verify_proof, AcirComposerPtr,
     },
     BackendError,
 };
 
 pub struct AcirComposer {
     composer_ptr: AcirComposerPtr,
+    size_hint: u32,
 }
 
 impl AcirComposer {
@@ -19,7 +20,26 @@ impl AcirComposer {
     /// # Returns
     /// * `Result<AcirComposer, AcirComposerError>` - Returns an AcirComposer instance or an AcirComposerError.
     pub fn new(size_hint: &u32) -> Result<Self, AcirComposerError> {
-        Ok(new_acir_composer(size_hint).map(|ptr| Self { composer_ptr: ptr })?)
+        Ok(new_acir_composer(size_hint)
+            .map(|ptr| Self { composer_ptr: ptr, size_hint: *size_hint })?)
+    }
+
+    /// Returns the size hint the composer was created with, the subgroup size of the circuits
+    /// it is meant to prove.
+    pub fn subgroup_size(&self) -> u32 {
+        self.size_hint
+    }
+
+    /// Drops the circuit, proving key and verification key held by the composer, so that another
+    /// circuit can be proven with it.
+    ///
+    /// The composer keeps the proving key of the first circuit it proves and uses it for every
+    /// later proof, so it must be reset before proving a different circuit. Circuits of the same
+    /// subgroup size reuse the memory reserved for the size hint.
+    /// # Returns
+    /// * `Result<(), AcirComposerError>` - Returns an empty result or an AcirComposerError.
+    pub fn reset(&mut self) -> Result<(), AcirComposerError> {
+        Ok(reset(&self.composer_ptr)?)
     }
 
     /// Initializes the proving key for the given composer.
@@ -31,6 +51,28 @@ impl AcirComposer {
         Ok(init_proving_key(&self.composer_ptr, constraint_system_buf)?)
     }
 
+    /// Computes the proving key of a circuit, keeping it in the composer, and returns it serialized.
+    /// # Arguments
+    /// * `constraint_system_buf` - Buffer representing the constraint system.
+    /// # Returns
+    /// * `Result<Vec<u8>, AcirComposerError>` - Returns the serialized proving key or an AcirComposerError.
+    pub fn get_proving_key(
+        &self,
+        constraint_system_buf: &[u8],
+    ) -> Result<Vec<u8>, AcirComposerError> {
+        Ok(get_proving_key(&self.composer_ptr, constraint_system_buf)?)
+    }
+
+    /// Loads a proving key returned by [`AcirComposer::get_proving_key`], so that creating a proof
+    /// skips computing it.
+    /// # Arguments
+    /// * `proving_key` - Buffer representing the proving key.
+    /// # Returns
+    /// * `Result<(), AcirComposerError>` - Returns an empty result or an AcirComposerError.
+    pub fn load_proving_key(&self, proving_key: &[u8]) -> Result<(), AcirComposerError> {
+        Ok(load_proving_key(&self.composer_ptr, proving_key)?)
+    }
+
     /// Creates a proof using the provided constraint system buffer and witness.
     /// # Arguments
     /// * `constraint_system_buf` - Buffer representing the constraint system.
@@ -129,6 +171,6 @@ impl Drop for AcirComposer {
 
 #[derive(thiserror::Error, Debug)]
 pub enum AcirComposerError {
-    #[error("BackendError")]
+    #[error(transparent)]
     BackendError(#[from] BackendError),
 }
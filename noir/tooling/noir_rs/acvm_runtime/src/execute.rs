@@ -1,14 +1,16 @@
-use acvm::brillig_vm::brillig::ForeignCallResult;
-use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM};
 use acvm::BlackBoxFunctionSolver;
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 
-use crate::errors::{ACVMError, ExecutionError};
+use crate::errors::ACVMError;
+use crate::executor::CircuitExecutor;
 
 /// Executes a given ACIR circuit with an initial witness, using a black box function solver.
 ///
 /// This function will continuously attempt to solve the circuit until a solution is found or an error occurs.
-/// If a foreign call is required, the function currently resolves it with an empty result.
+/// Brillig opcodes, emitted for unconstrained functions, are run by the ACVM's Brillig VM and
+/// assign their outputs like any other opcode. If a foreign call is required, e.g. by `println`,
+/// the function currently resolves it with an empty result, so the call returns no value.
+/// Use a [`CircuitExecutor`] to inspect the witness values while the circuit is being solved.
 ///
 /// # Parameters
 /// - `blackbox_solver`: A reference to the black box function solver that assists in solving the circuit.
@@ -23,46 +25,5 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver>(
     circuit: Circuit,
     initial_witness: WitnessMap,
 ) -> Result<WitnessMap, ACVMError> {
-    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
-
-    loop {
-        let solver_status = acvm.solve();
-
-        match solver_status {
-            ACVMStatus::Solved => break,
-            ACVMStatus::InProgress => {
-                unreachable!("Execution should not stop while in `InProgress` state.")
-            }
-            ACVMStatus::Failure(error) => {
-                let call_stack = match &error {
-                    OpcodeResolutionError::UnsatisfiedConstrain {
-                        opcode_location: ErrorLocation::Resolved(opcode_location),
-                    } => Some(vec![*opcode_location]),
-                    OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
-                        Some(call_stack.clone())
-                    }
-                    _ => None,
-                };
-
-                return Err(ACVMError::ExecutionError(match call_stack {
-                    Some(call_stack) => {
-                        if let Some(assert_message) = circuit.get_assert_message(
-                            *call_stack.last().expect("Call stacks should not be empty"),
-                        ) {
-                            ExecutionError::AssertionFailed(assert_message.to_owned(), call_stack)
-                        } else {
-                            ExecutionError::SolvingError(error)
-                        }
-                    }
-                    None => ExecutionError::SolvingError(error),
-                }));
-            }
-            ACVMStatus::RequiresForeignCall(_foreign_call) => {
-                acvm.resolve_pending_foreign_call(ForeignCallResult { values: vec![] });
-            }
-        }
-    }
-
-    let solved_witness = acvm.finalize();
-    Ok(solved_witness)
+    CircuitExecutor::new(blackbox_solver, &circuit, initial_witness).run_to_completion()
 }
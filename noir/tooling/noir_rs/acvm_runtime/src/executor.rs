@@ -0,0 +1,141 @@
+use acvm::acir::circuit::Circuit;
+use acvm::acir::native_types::{Witness, WitnessMap};
+use acvm::brillig_vm::brillig::ForeignCallResult;
+use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM};
+use acvm::{BlackBoxFunctionSolver, FieldElement};
+
+use crate::errors::{ACVMError, ExecutionError};
+
+/// The outcome of solving a single opcode with [`CircuitExecutor::step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepResult {
+    /// The opcode at `opcode_index` was solved.
+    Solved {
+        /// Index of the solved opcode in the circuit.
+        opcode_index: usize,
+        /// Witnesses the opcode assigned, in increasing witness order.
+        assigned: Vec<(Witness, FieldElement)>,
+    },
+    /// Every opcode of the circuit has been solved.
+    Finished,
+}
+
+/// Executes an ACIR circuit opcode by opcode, exposing the witness values as they are assigned.
+///
+/// Foreign calls made by Brillig opcodes are resolved with an empty result, like
+/// [`execute_circuit`](crate::execute::execute_circuit) does.
+pub struct CircuitExecutor<'a, B: BlackBoxFunctionSolver> {
+    circuit: &'a Circuit,
+    acvm: ACVM<'a, B>,
+}
+
+impl<'a, B: BlackBoxFunctionSolver> CircuitExecutor<'a, B> {
+    /// Creates a new CircuitExecutor positioned before the first opcode.
+    ///
+    /// # Parameters
+    /// - `blackbox_solver`: A reference to the black box function solver that assists in solving the circuit.
+    /// - `circuit`: The ACIR circuit that needs to be executed.
+    /// - `initial_witness`: The initial witness values for the circuit.
+    pub fn new(blackbox_solver: &'a B, circuit: &'a Circuit, initial_witness: WitnessMap) -> Self {
+        CircuitExecutor {
+            circuit,
+            acvm: ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness),
+        }
+    }
+
+    /// Solves the next opcode.
+    ///
+    /// Finding the assigned witnesses compares the witness map before and after the opcode, so
+    /// use [`CircuitExecutor::run_to_completion`] for the opcodes that need not be inspected.
+    ///
+    /// # Returns
+    /// - `Ok(StepResult)`: The solved opcode and the witnesses it assigned, or `StepResult::Finished`.
+    /// - `Err(ACVMError)`: The error the opcode failed with. The executor must not be stepped again.
+    pub fn step(&mut self) -> Result<StepResult, ACVMError> {
+        if *self.acvm.get_status() == ACVMStatus::Solved {
+            return Ok(StepResult::Finished);
+        }
+
+        let opcode_index = self.acvm.instruction_pointer();
+        let before = self.acvm.witness_map().clone();
+        loop {
+            match self.acvm.solve_opcode() {
+                ACVMStatus::Solved | ACVMStatus::InProgress => break,
+                ACVMStatus::Failure(error) => return Err(self.resolve_error(error)),
+                ACVMStatus::RequiresForeignCall(_foreign_call) => {
+                    self.acvm.resolve_pending_foreign_call(ForeignCallResult { values: vec![] });
+                }
+            }
+        }
+
+        let assigned = self
+            .acvm
+            .witness_map()
+            .clone()
+            .into_iter()
+            .filter(|(witness, _)| !before.contains_key(witness))
+            .collect();
+        Ok(StepResult::Solved { opcode_index, assigned })
+    }
+
+    /// Returns the value assigned to a witness so far, if any.
+    pub fn witness(&self, witness: Witness) -> Option<FieldElement> {
+        self.acvm.witness_map().get(&witness).copied()
+    }
+
+    /// Returns the witness values assigned so far.
+    pub fn witness_map(&self) -> &WitnessMap {
+        self.acvm.witness_map()
+    }
+
+    /// Solves the remaining opcodes.
+    ///
+    /// # Returns
+    /// - `Ok(WitnessMap)`: The solution to the circuit, represented as a `WitnessMap`.
+    /// - `Err(ACVMError)`: An error encountered during the execution of the circuit.
+    pub fn run_to_completion(mut self) -> Result<WitnessMap, ACVMError> {
+        loop {
+            let solver_status = self.acvm.solve();
+
+            match solver_status {
+                ACVMStatus::Solved => break,
+                ACVMStatus::InProgress => {
+                    unreachable!("Execution should not stop while in `InProgress` state.")
+                }
+                ACVMStatus::Failure(error) => return Err(self.resolve_error(error)),
+                ACVMStatus::RequiresForeignCall(_foreign_call) => {
+                    self.acvm.resolve_pending_foreign_call(ForeignCallResult { values: vec![] });
+                }
+            }
+        }
+
+        Ok(self.acvm.finalize())
+    }
+
+    /// Converts a solving error, attaching the assertion message of the failing opcode if the
+    /// circuit has one.
+    fn resolve_error(&self, error: OpcodeResolutionError) -> ACVMError {
+        let call_stack = match &error {
+            OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Resolved(opcode_location),
+            } => Some(vec![*opcode_location]),
+            OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+                Some(call_stack.clone())
+            }
+            _ => None,
+        };
+
+        ACVMError::ExecutionError(match call_stack {
+            Some(call_stack) => {
+                if let Some(assert_message) = self.circuit.get_assert_message(
+                    *call_stack.last().expect("Call stacks should not be empty"),
+                ) {
+                    ExecutionError::AssertionFailed(assert_message.to_owned(), call_stack)
+                } else {
+                    ExecutionError::SolvingError(error)
+                }
+            }
+            None => ExecutionError::SolvingError(error),
+        })
+    }
+}
@@ -3,3 +3,4 @@
 
 pub mod errors;
 pub mod execute;
+pub mod executor;
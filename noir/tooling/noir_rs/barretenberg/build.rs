@@ -1,13 +1,49 @@
+use std::process::Command;
+
 use cmake::Config;
 
+const BARRETENBERG_DIR: &str = "../../../../barretenberg/cpp";
+
+/// Identifies the barretenberg sources being built: the last commit touching them, or the CMake
+/// project version when git is unavailable, e.g. in a source archive.
+fn barretenberg_commit() -> String {
+    let commit = Command::new("git")
+        .args(["log", "-1", "--format=%H", "--", BARRETENBERG_DIR])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty());
+    if let Some(commit) = commit {
+        return commit;
+    }
+
+    let cmake_lists =
+        std::fs::read_to_string(format!("{}/CMakeLists.txt", BARRETENBERG_DIR)).unwrap_or_default();
+    cmake_lists
+        .lines()
+        .find(|line| line.contains("x-release-please-version"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .map(|version| format!("v{}", version))
+        .unwrap_or_else(|| String::from("unknown"))
+}
+
 fn main() {
     // Notify Cargo to rerun this build script if `build.rs` changes.
     println!("cargo:rerun-if-changed=build.rs");
 
+    // Record which barretenberg sources the byte formats of proofs and keys come from.
+    println!("cargo:rustc-env=BARRETENBERG_COMMIT={}", barretenberg_commit());
+
+    // The backend is single threaded unless the `multithreading` feature is enabled.
+    let multithreading =
+        if std::env::var_os("CARGO_FEATURE_MULTITHREADING").is_some() { "ON" } else { "OFF" };
+
     // Build the C++ code using CMake and get the build directory path.
-    let dst = Config::new("../../../../barretenberg/cpp")
+    let dst = Config::new(BARRETENBERG_DIR)
         .configure_arg("-DCMAKE_BUILD_TYPE=RelWithAssert")
         .define("TARGET_ARCH", "skylake")
+        .define("MULTITHREADING", multithreading)
         .build();
 
     // Add the library search path for Rust to find during linking.
@@ -44,9 +80,12 @@ fn main() {
         .allowlist_function("rust_acir_get_circuit_sizes")
         .allowlist_function("rust_acir_new_acir_composer")
         .allowlist_function("rust_acir_delete_acir_composer")
+        .allowlist_function("rust_acir_reset_acir_composer")
         .allowlist_function("rust_acir_create_circuit")
         .allowlist_function("rust_acir_init_proving_key")
         .allowlist_function("rust_acir_create_proof")
+        .allowlist_function("rust_acir_get_proving_key")
+        .allowlist_function("rust_acir_load_proving_key")
         .allowlist_function("rust_acir_load_verification_key")
         .allowlist_function("rust_acir_init_verification_key")
         .allowlist_function("rust_acir_get_verification_key")
@@ -54,12 +93,16 @@ fn main() {
         .allowlist_function("rust_acir_get_solidity_verifier")
         .allowlist_function("rust_acir_serialize_proof_into_fields")
         .allowlist_function("rust_acir_serialize_verification_key_into_fields")
+        .allowlist_function("rust_acir_prove_ultra_honk")
+        .allowlist_function("rust_acir_get_ultra_honk_verification_key")
+        .allowlist_function("rust_acir_verify_ultra_honk")
         .allowlist_function("rust_pedersen_hash")
         .allowlist_function("rust_pedersen_commit")
         .allowlist_function("rust_schnorr_compute_public_key")
         .allowlist_function("rust_schnorr_construct_signature")
         .allowlist_function("rust_schnorr_verify_signature")
         .allowlist_function("rust_srs_init_srs")
+        .allowlist_function("rust_srs_init_grumpkin_srs")
         .allowlist_function("rust_examples_simple_create_and_verify_proof")
         // Generate the bindings.
         .generate()
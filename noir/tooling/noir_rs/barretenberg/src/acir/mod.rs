@@ -1,11 +1,14 @@
 use std::{ffi::c_void, ptr};
 
 use crate::{
-    parse_c_str, rust_acir_create_proof, rust_acir_delete_acir_composer,
-    rust_acir_get_solidity_verifier, rust_acir_get_verification_key, rust_acir_init_proving_key,
-    rust_acir_init_verification_key, rust_acir_load_verification_key, rust_acir_new_acir_composer,
-    rust_acir_serialize_proof_into_fields, rust_acir_serialize_verification_key_into_fields,
-    rust_acir_verify_proof,
+    binding_error, parse_c_str, rust_acir_create_proof, rust_acir_delete_acir_composer,
+    rust_acir_get_proving_key, rust_acir_get_solidity_verifier,
+    rust_acir_get_ultra_honk_verification_key, rust_acir_get_verification_key,
+    rust_acir_init_proving_key, rust_acir_init_verification_key, rust_acir_load_proving_key,
+    rust_acir_load_verification_key, rust_acir_new_acir_composer, rust_acir_prove_ultra_honk,
+    rust_acir_reset_acir_composer, rust_acir_serialize_proof_into_fields,
+    rust_acir_serialize_verification_key_into_fields, rust_acir_verify_proof,
+    rust_acir_verify_ultra_honk,
 };
 
 use super::{serialize_slice, BackendError, Buffer};
@@ -23,10 +26,7 @@ pub fn new_acir_composer(size_hint: &u32) -> Result<AcirComposerPtr, BackendErro
     let mut out_ptr = ptr::null_mut();
     let error_msg_ptr = unsafe { rust_acir_new_acir_composer(size_hint, &mut out_ptr) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     if out_ptr.is_null() {
         return Err(BackendError::BindingCallPointerError(
@@ -36,6 +36,19 @@ pub fn new_acir_composer(size_hint: &u32) -> Result<AcirComposerPtr, BackendErro
     Ok(out_ptr)
 }
 
+/// Drops the circuit and the keys held by the composer, keeping its size hint.
+/// # Arguments
+/// * `acir_composer` - Pointer to the ACIR composer.
+/// # Returns
+/// * `Result<(), BackendError>` - Returns an empty result or an error message.
+pub fn reset(acir_composer: &AcirComposerPtr) -> Result<(), BackendError> {
+    let error_msg_ptr = unsafe { rust_acir_reset_acir_composer(acir_composer) };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    Ok(())
+}
+
 /// Initializes the proving key for the given composer.
 /// # Arguments
 /// * `acir_composer` - Pointer to the ACIR composer.
@@ -53,10 +66,51 @@ pub fn init_proving_key(
         )
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    Ok(())
+}
+
+/// Computes the proving key of a circuit and returns it serialized.
+/// # Arguments
+/// * `acir_composer` - Pointer to the ACIR composer.
+/// * `constraint_system_buf` - Buffer representing the constraint system.
+/// # Returns
+/// * `Result<Vec<u8>, BackendError>` - Returns the serialized proving key or an error message.
+pub fn get_proving_key(
+    acir_composer: &AcirComposerPtr,
+    constraint_system_buf: &[u8],
+) -> Result<Vec<u8>, BackendError> {
+    let mut out_ptr = ptr::null_mut();
+    let error_msg_ptr = unsafe {
+        rust_acir_get_proving_key(
+            acir_composer,
+            serialize_slice(constraint_system_buf).as_slice().as_ptr(),
+            &mut out_ptr,
+        )
+    };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    let result = unsafe { Buffer::from_ptr(out_ptr)?.to_vec() };
+    Ok(result)
+}
+
+/// Loads a proving key returned by [`get_proving_key`] into the given composer.
+///
+/// The SRS must hold at least one more point than the circuit size of the key.
+/// # Arguments
+/// * `acir_composer` - Pointer to the ACIR composer.
+/// * `proving_key` - Buffer representing the proving key.
+/// # Returns
+/// * `Result<(), BackendError>` - Returns an empty result or an error message.
+pub fn load_proving_key(
+    acir_composer: &AcirComposerPtr,
+    proving_key: &[u8],
+) -> Result<(), BackendError> {
+    let error_msg_ptr = unsafe { rust_acir_load_proving_key(acir_composer, proving_key.as_ptr()) };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(())
 }
@@ -86,10 +140,7 @@ pub fn create_proof(
         )
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     let result = unsafe {
         Buffer::from_ptr(Buffer::from_ptr(out_ptr)?.to_vec().as_slice().as_ptr())?.to_vec()
@@ -110,10 +161,7 @@ pub fn load_verification_key(
     let error_msg_ptr =
         unsafe { rust_acir_load_verification_key(acir_composer, verification_key.as_ptr()) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(())
 }
@@ -126,10 +174,7 @@ pub fn load_verification_key(
 pub fn init_verification_key(acir_composer: &AcirComposerPtr) -> Result<(), BackendError> {
     let error_msg_ptr = unsafe { rust_acir_init_verification_key(acir_composer) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(())
 }
@@ -143,10 +188,7 @@ pub fn get_verification_key(acir_composer: &AcirComposerPtr) -> Result<Vec<u8>,
     let mut out_ptr = ptr::null_mut();
     let error_msg_ptr = unsafe { rust_acir_get_verification_key(acir_composer, &mut out_ptr) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     let result = unsafe { Buffer::from_ptr(out_ptr)?.to_vec() };
     Ok(result)
@@ -174,10 +216,7 @@ pub fn verify_proof(
         )
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(result)
 }
@@ -191,14 +230,12 @@ pub fn get_solidity_verifier(acir_composer: &AcirComposerPtr) -> Result<String,
     let mut out_ptr: *mut u8 = ptr::null_mut();
     let error_msg_ptr = unsafe { rust_acir_get_solidity_verifier(acir_composer, &mut out_ptr) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
-    let verifier_string =
-        unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string());
-    Ok(verifier_string)
+    let verifier_buffer = unsafe { Buffer::from_ptr(out_ptr)?.to_vec() };
+    String::from_utf8(verifier_buffer).map_err(|error| {
+        BackendError::BindingCallPointerError(format!("Solidity verifier is not UTF-8: {}", error))
+    })
 }
 
 /// Serializes the provided proof into fields.
@@ -207,7 +244,8 @@ pub fn get_solidity_verifier(acir_composer: &AcirComposerPtr) -> Result<String,
 /// * `proof` - Buffer representing the proof.
 /// * `num_inner_public_inputs` - Number of inner public inputs.
 /// # Returns
-/// * `Result<Vec<u8>, String>` - Returns the serialized proof or an error message.
+/// * `Result<Vec<u8>, String>` - Returns the serialized proof as consecutive 32-byte big-endian
+///   field elements or an error message.
 pub fn serialize_proof_into_fields(
     acir_composer: &AcirComposerPtr,
     proof: &[u8],
@@ -223,12 +261,9 @@ pub fn serialize_proof_into_fields(
         )
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
-    let result = unsafe { Buffer::from_ptr(out_ptr)?.to_vec() };
+    let result = unsafe { Buffer::from_fields_ptr(out_ptr)?.to_vec() };
     Ok(result)
 }
 
@@ -236,28 +271,102 @@ pub fn serialize_proof_into_fields(
 /// # Arguments
 /// * `acir_composer` - Pointer to the ACIR composer.
 /// # Returns
-/// * `Result<(Vec<u8>, Vec<u8>), String>` - Returns serialized verification key and its hash, or an error message.
+/// * `Result<(Vec<u8>, Vec<u8>), String>` - Returns serialized verification key as consecutive
+///   32-byte big-endian field elements and its 32-byte hash, or an error message.
 pub fn serialize_verification_key_into_fields(
     acir_composer: &AcirComposerPtr,
 ) -> Result<(Vec<u8>, Vec<u8>), BackendError> {
     let mut out_vkey_ptr: *mut u8 = ptr::null_mut();
-    let out_key_hash_ptr: *mut u8 = ptr::null_mut();
+    let mut key_hash = [0u8; 32];
     let error_msg_ptr = unsafe {
         rust_acir_serialize_verification_key_into_fields(
             acir_composer,
             &mut out_vkey_ptr,
-            out_key_hash_ptr,
+            key_hash.as_mut_ptr(),
         )
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
-    let vkey = unsafe { Buffer::from_ptr(out_vkey_ptr)?.to_vec() };
-    let key_hash = unsafe { Buffer::from_ptr(out_key_hash_ptr)?.to_vec() };
-    Ok((vkey, key_hash))
+    let vkey = unsafe { Buffer::from_fields_ptr(out_vkey_ptr)?.to_vec() };
+    Ok((vkey, key_hash.to_vec()))
+}
+
+/// Creates an UltraHonk proof for the given constraint system and witness.
+///
+/// UltraHonk does not go through the ACIR composer, the circuit is built from scratch.
+/// # Arguments
+/// * `constraint_system_buf` - Buffer representing the constraint system.
+/// * `witness` - Buffer representing the witness.
+/// # Returns
+/// * `Result<Vec<u8>, String>` - Returns the created proof or an error message.
+pub fn prove_ultra_honk(
+    constraint_system_buf: &[u8],
+    witness: &[u8],
+) -> Result<Vec<u8>, BackendError> {
+    let mut out_ptr = ptr::null_mut();
+    let error_msg_ptr = unsafe {
+        rust_acir_prove_ultra_honk(
+            serialize_slice(constraint_system_buf).as_slice().as_ptr(),
+            serialize_slice(witness).as_slice().as_ptr(),
+            &mut out_ptr,
+        )
+    };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    let result = unsafe {
+        Buffer::from_ptr(Buffer::from_ptr(out_ptr)?.to_vec().as_slice().as_ptr())?.to_vec()
+    };
+    Ok(result)
+}
+
+/// Computes the UltraHonk verification key of the given constraint system.
+///
+/// The key holds the big-endian `u32` circuit size and number of public inputs followed by every
+/// precomputed commitment as 64-byte affine points.
+/// # Arguments
+/// * `constraint_system_buf` - Buffer representing the constraint system.
+/// # Returns
+/// * `Result<Vec<u8>, String>` - Returns the verification key or an error message.
+pub fn get_ultra_honk_verification_key(
+    constraint_system_buf: &[u8],
+) -> Result<Vec<u8>, BackendError> {
+    let mut out_ptr = ptr::null_mut();
+    let error_msg_ptr = unsafe {
+        rust_acir_get_ultra_honk_verification_key(
+            serialize_slice(constraint_system_buf).as_slice().as_ptr(),
+            &mut out_ptr,
+        )
+    };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    let result = unsafe {
+        Buffer::from_ptr(Buffer::from_ptr(out_ptr)?.to_vec().as_slice().as_ptr())?.to_vec()
+    };
+    Ok(result)
+}
+
+/// Verifies an UltraHonk proof against its verification key.
+/// # Arguments
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the UltraHonk verification key.
+/// # Returns
+/// * `Result<bool, String>` - Returns `true` if the verification succeeds, `false` otherwise, or an error message.
+pub fn verify_ultra_honk(proof: &[u8], verification_key: &[u8]) -> Result<bool, BackendError> {
+    let mut result = false;
+    let error_msg_ptr = unsafe {
+        rust_acir_verify_ultra_honk(
+            serialize_slice(proof).as_slice().as_ptr(),
+            serialize_slice(verification_key).as_slice().as_ptr(),
+            &mut result,
+        )
+    };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    Ok(result)
 }
 
 /// Frees the internal memory used by the ACIR composer.
@@ -1,12 +1,12 @@
 use crate::{
-    rust_acir_get_circuit_sizes, {parse_c_str, serialize_slice, BackendError},
+    rust_acir_get_circuit_sizes, {binding_error, serialize_slice, BackendError},
 };
 
 /// Represents the sizes of various components within a circuit.
 ///
 /// This struct captures sizes that are relevant to the construction or evaluation of a circuit,
 /// such as the exact size of the circuit, its total size, and the size of its subgroup.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CircuitSizes {
     pub exact: u32,
     pub total: u32,
@@ -44,10 +44,7 @@ pub fn get_circuit_sizes(constraint_system_buf: &[u8]) -> Result<CircuitSizes, B
         )
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(ret)
 }
@@ -1,4 +1,4 @@
-use crate::{parse_c_str, rust_examples_simple_create_and_verify_proof};
+use crate::{binding_error, rust_examples_simple_create_and_verify_proof};
 
 use super::BackendError;
 
@@ -16,10 +16,7 @@ pub fn simple_create_and_verify_proof() -> Result<bool, BackendError> {
     let mut result = false;
     let error_msg_ptr = unsafe { rust_examples_simple_create_and_verify_proof(&mut result) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(result)
 }
@@ -16,6 +16,13 @@ pub mod srs;
 // This matches bindgen::Builder output
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+/// The barretenberg sources this crate was built from: the hash of the last commit touching them,
+/// or the barretenberg version when built outside of a git checkout.
+pub const BACKEND_COMMIT: &str = env!("BARRETENBERG_COMMIT");
+
+/// Environment variable barretenberg reads the size of its thread pool from, once per process.
+pub const HARDWARE_CONCURRENCY_VAR: &str = "HARDWARE_CONCURRENCY";
+
 // TODO create proper logging manager
 #[no_mangle]
 pub unsafe extern "C" fn logstr(ptr: *const ::std::os::raw::c_char) {
@@ -25,10 +32,18 @@ pub unsafe extern "C" fn logstr(ptr: *const ::std::os::raw::c_char) {
     }
 }
 
+/// Prefix of the message barretenberg raises when the initialized SRS holds fewer points than a
+/// circuit needs.
+const SRS_TOO_SMALL_PREFIX: &str = "SRS too small for circuit";
+
 #[derive(Debug, thiserror::Error)]
 pub enum BackendError {
-    #[error("Binding call error")]
+    /// A native call failed, with the message barretenberg raised.
+    #[error("Binding call error: {0}")]
     BindingCallError(String),
+    /// The SRS handed to the backend holds fewer points than the circuit needs.
+    #[error("{0}")]
+    SrsTooSmall(String),
     #[error("Binding call output pointer error")]
     BindingCallPointerError(String),
 }
@@ -54,6 +69,23 @@ impl Buffer {
         Ok(Self { data: data.to_vec() })
     }
 
+    /// Constructs a Buffer from a raw pointer to a serialized field vector, reading a u32 element
+    /// count followed by that many 32-byte field elements.
+    ///
+    /// # Safety
+    /// This method is unsafe because it trusts the caller to ensure that `ptr` is a valid pointer
+    /// pointing to at least `u32` bytes plus 32 bytes for each element indicated by the u32 value.
+    pub unsafe fn from_fields_ptr(ptr: *const u8) -> Result<Self, BackendError> {
+        if ptr.is_null() {
+            return Err(BackendError::BindingCallPointerError("Pointer is null.".to_string()));
+        }
+        let len_slice = slice::from_raw_parts(ptr, 4);
+        let len = u32::from_be_bytes([len_slice[0], len_slice[1], len_slice[2], len_slice[3]]);
+        let data_ptr = ptr.add(4);
+        let data = slice::from_raw_parts(data_ptr, len as usize * 32);
+        Ok(Self { data: data.to_vec() })
+    }
+
     /// Returns a reference to the buffer's data as a slice.
     pub fn as_slice(&self) -> &[u8] {
         &self.data
@@ -77,6 +109,21 @@ pub unsafe fn parse_c_str(ptr: *const ::std::os::raw::c_char) -> Option<String>
     CStr::from_ptr(ptr).to_str().map_or(None, |s| Some(s.to_string()))
 }
 
+/// Builds the error for a failed binding call from the message it returned.
+///
+/// The message is kept verbatim, and well-known messages are mapped to their own variant.
+///
+/// # Safety
+/// `error_msg_ptr` must be a valid null-terminated C string.
+unsafe fn binding_error(error_msg_ptr: *const ::std::os::raw::c_char) -> BackendError {
+    let message = parse_c_str(error_msg_ptr).unwrap_or_else(|| "Parsing c_str failed".to_string());
+    if message.starts_with(SRS_TOO_SMALL_PREFIX) {
+        BackendError::SrsTooSmall(message)
+    } else {
+        BackendError::BindingCallError(message)
+    }
+}
+
 /// Serializes a slice into a vector of bytes.
 ///
 /// This function takes a byte slice and returns a `Vec<u8>` containing the length of the slice
@@ -106,4 +153,4 @@ pub fn serialize_slice(data: &[u8]) -> Vec<u8> {
     buffer.extend_from_slice(&(data.len() as u32).to_be_bytes());
     buffer.extend_from_slice(data);
     buffer
-}
\ No newline at end of file
+}
@@ -1,4 +1,4 @@
-use super::{parse_c_str, BackendError};
+use super::{binding_error, BackendError};
 use crate::{rust_pedersen_commit, rust_pedersen_hash};
 
 /// Computes a Pedersen commitment using the provided input buffer and hash index.
@@ -12,10 +12,7 @@ pub fn pedersen_commit(input_buf: &[u8], _hash_index: u32) -> Result<[u8; 64], B
     let error_msg_ptr =
         unsafe { rust_pedersen_commit(input_buf.as_ptr(), result.as_mut_slice().as_mut_ptr()) };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(result)
 }
@@ -32,10 +29,7 @@ pub fn pedersen_hash(input_buf: &[u8], hash_index: u32) -> Result<[u8; 64], Back
         rust_pedersen_hash(input_buf.as_ptr(), &hash_index, result.as_mut_slice().as_mut_ptr())
     };
     if !error_msg_ptr.is_null() {
-        return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
-        )));
+        return Err(unsafe { binding_error(error_msg_ptr) });
     }
     Ok(result)
 }
@@ -0,0 +1,332 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use super::{
+    checksum::SrsChecksum,
+    netsrs::{NetSrs, Validator},
+    Srs, SrsError,
+};
+
+const G1_FILE: &str = "g1.dat";
+const G2_FILE: &str = "g2.dat";
+const VALIDATOR_FILE: &str = "validator";
+const G1_PART_FILE: &str = "g1.dat.part";
+const VALIDATOR_PART_FILE: &str = "validator.part";
+/// Number of G1 points downloaded and appended to the partial cache file at a time.
+const SEGMENT_POINTS: u32 = 1 << 16;
+
+/// Controls when a [`CachedSrs`] contacts the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Trusts the cache whenever it holds enough points and only downloads missing data.
+    Always,
+    /// Asks the server whether the cached transcript is still current before using it, and
+    /// downloads it again if it changed.
+    #[default]
+    Revalidate,
+    /// Never contacts the server and fails if the cache does not hold enough points.
+    Offline,
+}
+
+/// Transcript data read from the cache directory.
+struct CacheEntry {
+    g1_data: Vec<u8>,
+    g2_data: Vec<u8>,
+    validator: Option<Validator>,
+}
+
+/// An SRS that keeps downloaded transcript data in a directory on disk.
+///
+/// The ETag (or Last-Modified date) of the transcript is stored next to the data, so that with
+/// [`CachePolicy::Revalidate`] a changed transcript is detected with a single conditional request
+/// instead of silently producing unverifiable proofs. Use one directory per transcript URL.
+///
+/// G1 data is downloaded in segments appended to a partial file in the cache directory, so an
+/// interrupted download resumes from the last stored segment on the next load, as long as the
+/// transcript did not change in between.
+#[derive(Debug)]
+pub struct CachedSrs {
+    pub data: Vec<u8>,
+    pub g2_data: Vec<u8>,
+    pub num_points: u32,
+    cache_dir: PathBuf,
+    policy: CachePolicy,
+    net_srs: NetSrs,
+    checksum: Option<SrsChecksum>,
+}
+
+impl CachedSrs {
+    /// Creates a new CachedSrs instance backed by the default ignition transcript.
+    ///
+    /// # Arguments
+    /// * `cache_dir` - Directory the transcript data is stored in. It is created if missing.
+    /// * `policy` - When to contact the server.
+    pub fn new(cache_dir: impl AsRef<Path>, policy: CachePolicy) -> Self {
+        Self::with_net_srs(cache_dir, policy, NetSrs::default())
+    }
+
+    /// Creates a new CachedSrs instance that downloads missing or stale data using `net_srs`.
+    ///
+    /// # Arguments
+    /// * `cache_dir` - Directory the transcript data is stored in. It is created if missing.
+    /// * `policy` - When to contact the server.
+    /// * `net_srs` - The source used to download and revalidate the transcript.
+    pub fn with_net_srs(cache_dir: impl AsRef<Path>, policy: CachePolicy, net_srs: NetSrs) -> Self {
+        CachedSrs {
+            data: Vec::new(),
+            g2_data: Vec::new(),
+            num_points: 0,
+            cache_dir: cache_dir.as_ref().to_path_buf(),
+            policy,
+            net_srs,
+            checksum: None,
+        }
+    }
+
+    /// Verifies the cached and downloaded data against a pinned checksum.
+    ///
+    /// A cache failing the check is downloaded again, except with [`CachePolicy::Offline`] which
+    /// fails with [`SrsError::ChecksumMismatch`]. Downloaded data failing the check is not cached.
+    /// Loads read at least the points covered by the checksum, see [`SrsChecksum`].
+    ///
+    /// # Arguments
+    /// * `checksum` - The expected digest of the transcript.
+    pub fn with_checksum(mut self, checksum: SrsChecksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Reads the cached G1 data, G2 data and validator.
+    ///
+    /// Missing or malformed files are reported as an empty cache.
+    fn read_cache(&self) -> Result<CacheEntry, SrsError> {
+        let g1_data = read_optional(&self.cache_dir.join(G1_FILE))?.unwrap_or_default();
+        let g2_data = read_optional(&self.cache_dir.join(G2_FILE))?.unwrap_or_default();
+        if g1_data.len() % 64 != 0 || g2_data.len() != 128 {
+            return Ok(CacheEntry { g1_data: Vec::new(), g2_data: Vec::new(), validator: None });
+        }
+        let validator = read_optional(&self.cache_dir.join(VALIDATOR_FILE))?
+            .and_then(|contents| parse_validator(&String::from_utf8_lossy(&contents)));
+        Ok(CacheEntry { g1_data, g2_data, validator })
+    }
+
+    /// Downloads `num_points` G1 points into the partial cache file, resuming a previous download.
+    ///
+    /// The partial file is kept only if the validator of the transcript it was downloaded from is
+    /// known, and with [`CachePolicy::Revalidate`] only if that transcript is still current.
+    ///
+    /// # Returns
+    /// * `Result<(Vec<u8>, Option<Validator>), SrsError>` - The G1 data and the validator of the
+    ///   transcript it was read from.
+    fn download_g1_data(&self, num_points: u32) -> Result<(Vec<u8>, Option<Validator>), SrsError> {
+        fs::create_dir_all(&self.cache_dir)?;
+        let part_path = self.cache_dir.join(G1_PART_FILE);
+        let validator_path = self.cache_dir.join(VALIDATOR_PART_FILE);
+        let mut validator = read_optional(&validator_path)?
+            .and_then(|contents| parse_validator(&String::from_utf8_lossy(&contents)));
+        let mut part = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&part_path)?;
+
+        let mut written = part.metadata()?.len() / 64 * 64;
+        let resumable = written > 0
+            && match &validator {
+                Some(validator) if self.policy == CachePolicy::Revalidate => {
+                    self.net_srs.is_current(validator)?
+                }
+                Some(_) => true,
+                None => false,
+            };
+        if !resumable {
+            written = 0;
+            validator = None;
+            remove_optional(&validator_path)?;
+        }
+        part.set_len(written)?;
+        part.seek(SeekFrom::Start(written))?;
+
+        let mut downloaded = (written / 64) as u32;
+        while downloaded < num_points {
+            let count = (num_points - downloaded).min(SEGMENT_POINTS);
+            let (segment, segment_validator) =
+                self.net_srs.download_g1_points(downloaded, count)?;
+            match (&validator, segment_validator) {
+                (Some(validator), Some(segment_validator)) if *validator != segment_validator => {
+                    remove_optional(&validator_path)?;
+                    part.set_len(0)?;
+                    return Err(SrsError::InvalidTranscript(
+                        "the transcript changed while it was being downloaded".to_string(),
+                    ));
+                }
+                (None, Some(segment_validator)) => {
+                    fs::write(&validator_path, format_validator(&segment_validator))?;
+                    validator = Some(segment_validator);
+                }
+                _ => {}
+            }
+            if segment.len() != count as usize * 64 {
+                return Err(SrsError::InvalidTranscript(format!(
+                    "expected {} bytes of G1 data from point {}, found {}",
+                    count as usize * 64,
+                    downloaded,
+                    segment.len()
+                )));
+            }
+            part.write_all(&segment)?;
+            part.flush()?;
+            downloaded += count;
+        }
+
+        let mut g1_data = Vec::with_capacity(num_points as usize * 64);
+        part.seek(SeekFrom::Start(0))?;
+        part.read_to_end(&mut g1_data)?;
+        if g1_data.len() != num_points as usize * 64 {
+            return Err(SrsError::InvalidTranscript(format!(
+                "expected {} bytes of G1 data in {}, found {}",
+                num_points as usize * 64,
+                part_path.display(),
+                g1_data.len()
+            )));
+        }
+        Ok((g1_data, validator))
+    }
+
+    /// Replaces the cached data. The validator is written last, so an interrupted write never
+    /// leaves a validator describing data that was not fully stored.
+    fn write_cache(
+        &self,
+        g1_data: &[u8],
+        g2_data: &[u8],
+        validator: Option<&Validator>,
+    ) -> Result<(), SrsError> {
+        fs::create_dir_all(&self.cache_dir)?;
+        remove_optional(&self.cache_dir.join(VALIDATOR_FILE))?;
+        fs::write(self.cache_dir.join(G1_FILE), g1_data)?;
+        fs::write(self.cache_dir.join(G2_FILE), g2_data)?;
+        if let Some(validator) = validator {
+            fs::write(self.cache_dir.join(VALIDATOR_FILE), format_validator(validator))?;
+        }
+        remove_optional(&self.cache_dir.join(G1_PART_FILE))?;
+        remove_optional(&self.cache_dir.join(VALIDATOR_PART_FILE))?;
+        Ok(())
+    }
+}
+
+impl Srs for CachedSrs {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        let num_points = num_points.max(self.checksum.map_or(0, |checksum| checksum.num_points));
+        let cache = self.read_cache()?;
+        let cached_points = (cache.g1_data.len() / 64) as u32;
+
+        let mut use_cache = match self.policy {
+            CachePolicy::Offline => {
+                if cached_points < num_points {
+                    return Err(SrsError::InsufficientPoints {
+                        available: cached_points,
+                        requested: num_points,
+                        path: None,
+                    });
+                }
+                true
+            }
+            CachePolicy::Always => cached_points >= num_points,
+            CachePolicy::Revalidate => match &cache.validator {
+                Some(validator) if cached_points >= num_points => {
+                    self.net_srs.is_current(validator)?
+                }
+                _ => false,
+            },
+        };
+        if let (true, Some(checksum)) = (use_cache, &self.checksum) {
+            if let Err(error) = checksum.verify(&cache.g1_data, &cache.g2_data) {
+                if self.policy == CachePolicy::Offline {
+                    return Err(error);
+                }
+                use_cache = false;
+            }
+        }
+
+        if use_cache {
+            self.data = cache.g1_data;
+            self.g2_data = cache.g2_data;
+        } else {
+            let (g1_data, validator) = self.download_g1_data(num_points.max(1))?;
+            let g2_data = self.net_srs.download_g2_data()?;
+            if g2_data.len() != 128 {
+                return Err(SrsError::InvalidTranscript(format!(
+                    "expected 128 bytes of G2 data, found {}",
+                    g2_data.len()
+                )));
+            }
+            if let Some(checksum) = &self.checksum {
+                if let Err(error) = checksum.verify(&g1_data, &g2_data) {
+                    remove_optional(&self.cache_dir.join(G1_PART_FILE))?;
+                    remove_optional(&self.cache_dir.join(VALIDATOR_PART_FILE))?;
+                    return Err(error);
+                }
+            }
+            self.write_cache(&g1_data, &g2_data, validator.as_ref())?;
+            (self.data, self.g2_data) = (g1_data, g2_data);
+        }
+        self.num_points = (self.data.len() / 64) as u32;
+        Ok(())
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        &self.g2_data
+    }
+
+    fn num_points(&self) -> u32 {
+        self.num_points
+    }
+
+    fn clear(&mut self) {
+        self.data = Vec::new();
+        self.g2_data = Vec::new();
+    }
+
+    fn max_points(&self) -> Option<u32> {
+        self.net_srs.max_points()
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>, SrsError> {
+    match fs::read(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+fn remove_optional(path: &Path) -> Result<(), SrsError> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+fn format_validator(validator: &Validator) -> String {
+    match validator {
+        Validator::ETag(etag) => format!("etag {}", etag),
+        Validator::LastModified(date) => format!("last-modified {}", date),
+    }
+}
+
+fn parse_validator(contents: &str) -> Option<Validator> {
+    let (kind, value) = contents.split_once(' ')?;
+    match kind {
+        "etag" => Some(Validator::ETag(value.to_string())),
+        "last-modified" => Some(Validator::LastModified(value.to_string())),
+        _ => None,
+    }
+}
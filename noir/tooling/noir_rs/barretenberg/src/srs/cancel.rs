@@ -0,0 +1,65 @@
+use std::{
+    io::{self, Read},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Lets another thread, or a deadline, abort a running SRS download or proof.
+///
+/// Clones share the cancellation flag, so a request handler can keep one clone and hand another
+/// to the prover. The deadline belongs to each clone.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is only cancelled by [`CancellationToken::cancel`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also cancels the token once `deadline` passes.
+    ///
+    /// # Arguments
+    /// * `deadline` - The instant after which the token counts as cancelled.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Cancels the token and every clone of it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the token was cancelled or its deadline passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self.deadline.map_or(false, |deadline| Instant::now() >= deadline)
+    }
+
+    /// Returns the time left until the deadline, if there is one.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// A reader failing as soon as its token is cancelled, so that long reads stop between chunks.
+pub(crate) struct CancellableRead<'a, R> {
+    pub(crate) inner: R,
+    pub(crate) token: Option<&'a CancellationToken>,
+}
+
+impl<R: Read> Read for CancellableRead<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.token.map_or(false, CancellationToken::is_cancelled) {
+            return Err(io::Error::new(io::ErrorKind::Other, "SRS download cancelled"));
+        }
+        self.inner.read(buf)
+    }
+}
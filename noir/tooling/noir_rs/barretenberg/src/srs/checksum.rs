@@ -0,0 +1,68 @@
+use sha2::{Digest, Sha256};
+
+use super::SrsError;
+
+/// A pinned SHA-256 digest of the first G1 points of a transcript and its G2 point.
+///
+/// Transcripts are only read from their start, so pinning a prefix covers every circuit needing at
+/// most `num_points` points. Sources configured with a checksum load at least `num_points` points
+/// to verify it, and the points past them are not covered, so pin as many points as the largest
+/// circuit needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SrsChecksum {
+    /// Number of G1 points covered by the digest.
+    pub num_points: u32,
+    /// SHA-256 of the first `num_points` G1 points followed by the G2 data.
+    pub sha256: [u8; 32],
+}
+
+impl SrsChecksum {
+    /// Creates a checksum from a digest computed ahead of time, e.g. with [`SrsChecksum::compute`]
+    /// over a trusted copy of the transcript.
+    ///
+    /// # Arguments
+    /// * `num_points` - Number of G1 points covered by the digest.
+    /// * `sha256` - SHA-256 of the first `num_points` G1 points followed by the G2 data.
+    pub fn new(num_points: u32, sha256: [u8; 32]) -> Self {
+        SrsChecksum { num_points, sha256 }
+    }
+
+    /// Computes the checksum of loaded SRS data.
+    ///
+    /// # Arguments
+    /// * `g1_data` - The G1 points, of which the first `num_points` are hashed.
+    /// * `g2_data` - The G2 data, empty for Grumpkin.
+    /// * `num_points` - Number of G1 points to cover.
+    ///
+    /// # Returns
+    /// * `Result<SrsChecksum, SrsError>` - The checksum, or `SrsError::InsufficientPoints` if
+    ///   `g1_data` holds fewer than `num_points` points.
+    pub fn compute(g1_data: &[u8], g2_data: &[u8], num_points: u32) -> Result<Self, SrsError> {
+        let g1_prefix =
+            g1_data.get(..num_points as usize * 64).ok_or(SrsError::InsufficientPoints {
+                available: (g1_data.len() / 64) as u32,
+                requested: num_points,
+                path: None,
+            })?;
+        let mut hasher = Sha256::new();
+        hasher.update(g1_prefix);
+        hasher.update(g2_data);
+        Ok(SrsChecksum { num_points, sha256: hasher.finalize().into() })
+    }
+
+    /// Checks loaded SRS data against the checksum.
+    ///
+    /// # Returns
+    /// * `Result<(), SrsError>` - `SrsError::ChecksumMismatch` if the data does not match.
+    pub fn verify(&self, g1_data: &[u8], g2_data: &[u8]) -> Result<(), SrsError> {
+        let actual = Self::compute(g1_data, g2_data, self.num_points)?;
+        if actual.sha256 != self.sha256 {
+            return Err(SrsError::ChecksumMismatch {
+                num_points: self.num_points,
+                expected: self.sha256,
+                actual: actual.sha256,
+            });
+        }
+        Ok(())
+    }
+}
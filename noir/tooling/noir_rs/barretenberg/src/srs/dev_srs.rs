@@ -0,0 +1,119 @@
+use ark_bn254::{Fq, Fr, G1Projective, G2Projective};
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+
+use super::{Srs, SrsError};
+
+/// Toxic waste used to generate the development SRS. It is public, so proofs made with a
+/// `DevSrs` provide no soundness guarantees.
+pub const DEV_SRS_TAU: u64 = 0x006e_6f69_725f_7273;
+
+/// An insecure SRS generated locally from the powers of a known `tau` over bn254.
+///
+/// Intended for tests and local development only: it avoids downloading the ignition transcript
+/// while still producing proofs that verify against a verification key built from the same SRS.
+#[derive(Debug)]
+pub struct DevSrs {
+    pub data: Vec<u8>,
+    pub g2_data: Vec<u8>,
+    pub num_points: u32,
+    pub max_points: u32,
+}
+
+impl DevSrs {
+    /// Creates a new DevSrs able to generate up to `max_points` G1 points.
+    ///
+    /// # Arguments
+    /// * `max_points` - Maximum number of G1 points that may be requested.
+    pub fn new(max_points: u32) -> Self {
+        DevSrs { data: Vec::new(), g2_data: Vec::new(), num_points: 0, max_points }
+    }
+
+    /// Generates `[tau^i]G1` for `i` in `0..num_points`, encoded in the transcript layout.
+    ///
+    /// # Arguments
+    /// * `num_points` - Number of G1 points to generate.
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - A byte vector containing the G1 data.
+    fn generate_g1_data(num_points: u32) -> Vec<u8> {
+        let tau = Fr::from(DEV_SRS_TAU);
+        let mut point = G1Projective::generator();
+        let mut points = Vec::with_capacity(num_points as usize);
+        for _ in 0..num_points {
+            points.push(point);
+            point *= tau;
+        }
+
+        let mut data = Vec::with_capacity(num_points as usize * 64);
+        for point in G1Projective::normalize_batch(&points) {
+            write_fq(&mut data, &point.x);
+            write_fq(&mut data, &point.y);
+        }
+        data
+    }
+
+    /// Generates `[tau]G2`, encoded in the transcript layout.
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - A byte vector containing the G2 data.
+    fn generate_g2_data() -> Vec<u8> {
+        let point = (G2Projective::generator() * Fr::from(DEV_SRS_TAU)).into_affine();
+
+        let mut data = Vec::with_capacity(128);
+        write_fq(&mut data, &point.x.c0);
+        write_fq(&mut data, &point.x.c1);
+        write_fq(&mut data, &point.y.c0);
+        write_fq(&mut data, &point.y.c1);
+        data
+    }
+}
+
+/// Writes a base field element as four big-endian 64-bit limbs, least significant limb first,
+/// which is how barretenberg reads transcript points.
+fn write_fq(buffer: &mut Vec<u8>, element: &Fq) {
+    for limb in element.into_bigint().0 {
+        buffer.extend_from_slice(&limb.to_be_bytes());
+    }
+}
+
+impl Srs for DevSrs {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        if num_points > self.max_points {
+            return Err(SrsError::InsufficientPoints {
+                available: self.max_points,
+                requested: num_points,
+                path: None,
+            });
+        }
+        if num_points > self.num_points || self.data.is_empty() {
+            self.data = Self::generate_g1_data(num_points);
+            self.num_points = num_points;
+        }
+        if self.g2_data.is_empty() {
+            self.g2_data = Self::generate_g2_data();
+        }
+        Ok(())
+    }
+
+    fn max_points(&self) -> Option<u32> {
+        Some(self.max_points)
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        &self.g2_data
+    }
+
+    fn num_points(&self) -> u32 {
+        self.num_points
+    }
+
+    fn clear(&mut self) {
+        self.data = Vec::new();
+        self.g2_data = Vec::new();
+    }
+}
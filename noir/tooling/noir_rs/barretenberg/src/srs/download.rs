@@ -0,0 +1,286 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use fd_lock::RwLock;
+use reqwest::blocking::Client;
+use reqwest::header::RANGE;
+use reqwest::StatusCode;
+
+use super::{
+    http::{check_response, default_client, redact_url},
+    netsrs::DEFAULT_SRS_URL,
+    SrsError,
+};
+
+const MANIFEST_SIZE: u64 = 28;
+
+/// Downloads an ignition transcript to disk, to be read with [`LocalSrs`](super::local_srs::LocalSrs).
+///
+/// The data is written to `<dest>.part` and renamed to `dest` once complete, so `dest` never holds
+/// a truncated transcript. A `<dest>.part` file left by an interrupted call is resumed rather than
+/// downloaded again.
+///
+/// With `num_points`, only the manifest, the first `num_points` G1 points and the G2 point are
+/// downloaded, and the manifest is rewritten to describe that many points. This produces a valid
+/// transcript of a few megabytes instead of the 322 MB of the full `transcript00.dat`.
+///
+/// # Arguments
+/// * `url` - URL of the transcript, [`DEFAULT_SRS_URL`] when `None`.
+/// * `dest` - Path the transcript is written to.
+/// * `num_points` - Number of G1 points to keep, or `None` to download the whole file.
+///
+/// # Returns
+/// * `Result<u64, SrsError>` - The number of bytes downloaded by this call, which excludes the
+///   bytes resumed from a partial file.
+pub fn download_srs(
+    url: Option<&str>,
+    dest: &Path,
+    num_points: Option<u32>,
+) -> Result<u64, SrsError> {
+    download_transcript(&default_client(), url.unwrap_or(DEFAULT_SRS_URL), dest, num_points)
+}
+
+/// Downloads a transcript like [`download_srs`], with the given client.
+fn download_transcript(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    num_points: Option<u32>,
+) -> Result<u64, SrsError> {
+    let part_path = part_path(dest);
+    let mut part =
+        OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&part_path)?;
+    let resumed = part.metadata()?.len();
+
+    let written = match num_points {
+        None => fetch_range(client, url, &mut part, resumed, None)?,
+        Some(num_points) => {
+            let g1_end = MANIFEST_SIZE + num_points as u64 * 64;
+            // A longer partial file was interrupted after its manifest was rewritten, or belongs to
+            // another number of points, so it is downloaded again.
+            let start = if resumed > g1_end { 0 } else { resumed };
+            let written = fetch_range(client, url, &mut part, start, Some(g1_end - start))?;
+            written + append_g2(client, url, &mut part, num_points)?
+        }
+    };
+
+    part.sync_all()?;
+    drop(part);
+    fs::rename(&part_path, dest)?;
+    Ok(written)
+}
+
+/// Makes sure `dest` holds an ignition transcript of at least `num_points` G1 points, downloading
+/// one with [`download_srs`] only when the file is missing or holds fewer points.
+///
+/// A smaller transcript, such as one left by an earlier call for a smaller circuit, is extended:
+/// its points are kept and only the missing ones are downloaded. Concurrent calls, from threads or
+/// processes, wait on an exclusive lock of `<dest>.lock` so that the transcript is downloaded
+/// once. The lock file is left in place.
+///
+/// # Arguments
+/// * `url` - URL of the transcript, [`DEFAULT_SRS_URL`] when `None`.
+/// * `dest` - Path of the transcript.
+/// * `num_points` - Number of G1 points the transcript must hold.
+///
+/// # Returns
+/// * `Result<u64, SrsError>` - The number of bytes downloaded by this call, zero if `dest` already
+///   held enough points.
+pub fn ensure_transcript(url: Option<&str>, dest: &Path, num_points: u32) -> Result<u64, SrsError> {
+    if transcript_points(dest)? >= num_points {
+        return Ok(0);
+    }
+
+    let mut lock = RwLock::new(
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(suffixed(dest, ".lock"))?,
+    );
+    let _guard = lock.write()?;
+    // Another caller may have downloaded the transcript while this one waited for the lock.
+    let available = transcript_points(dest)?;
+    if available >= num_points {
+        return Ok(0);
+    }
+
+    // The manifest and the points are fetched over the same connections.
+    let client = default_client();
+    let url = url.unwrap_or(DEFAULT_SRS_URL);
+    let mut written = 0;
+    let part_path = part_path(dest);
+    if available > 0 && !part_path.exists() {
+        written += seed_part(&client, url, dest, &part_path, available)?;
+    }
+    Ok(written + download_transcript(&client, url, dest, Some(num_points))?)
+}
+
+/// Returns the number of G1 points of the ignition transcript at `path`, or zero if the file is
+/// missing or does not hold a whole transcript.
+fn transcript_points(path: &Path) -> Result<u32, SrsError> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error.into()),
+    };
+    let file_len = file.metadata()?.len();
+    let mut manifest = [0u8; MANIFEST_SIZE as usize];
+    if file_len < MANIFEST_SIZE {
+        return Ok(0);
+    }
+    file.read_exact(&mut manifest)?;
+    let num_g1_points =
+        u32::from_be_bytes([manifest[16], manifest[17], manifest[18], manifest[19]]);
+    let num_g2_points =
+        u32::from_be_bytes([manifest[20], manifest[21], manifest[22], manifest[23]]);
+    if num_g2_points == 0 || file_len < MANIFEST_SIZE + num_g1_points as u64 * 64 + 128 {
+        return Ok(0);
+    }
+    Ok(num_g1_points)
+}
+
+/// Starts the partial file of a download to `dest` with the `num_points` G1 points `dest` already
+/// holds, so that only the missing points are downloaded.
+///
+/// The manifest of `dest` may have been rewritten to describe fewer points than the transcript
+/// holds, so the one of the transcript is downloaded instead.
+///
+/// # Returns
+/// * `Result<u64, SrsError>` - The number of bytes downloaded.
+fn seed_part(
+    client: &Client,
+    url: &str,
+    dest: &Path,
+    part_path: &Path,
+    num_points: u32,
+) -> Result<u64, SrsError> {
+    let mut manifest = Vec::with_capacity(MANIFEST_SIZE as usize);
+    let written = download(client, url, 0, Some(MANIFEST_SIZE), &mut manifest)?;
+
+    let mut points = File::open(dest)?.take(MANIFEST_SIZE + num_points as u64 * 64);
+    io::copy(&mut (&mut points).take(MANIFEST_SIZE), &mut io::sink())?;
+    let mut part = File::create(part_path)?;
+    part.write_all(&manifest)?;
+    io::copy(&mut points, &mut part)?;
+    Ok(written)
+}
+
+/// Returns the path of the file a download to `dest` is written to until it completes.
+fn part_path(dest: &Path) -> PathBuf {
+    suffixed(dest, ".part")
+}
+
+/// Returns `path` with `suffix` appended to its file name.
+fn suffixed(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Checks that the transcript holds `num_points` points, appends its first G2 point and rewrites
+/// the manifest to describe the G1 points kept.
+///
+/// # Returns
+/// * `Result<u64, SrsError>` - The number of bytes downloaded.
+fn append_g2(
+    client: &Client,
+    url: &str,
+    part: &mut File,
+    num_points: u32,
+) -> Result<u64, SrsError> {
+    let mut manifest = [0u8; MANIFEST_SIZE as usize];
+    part.seek(SeekFrom::Start(0))?;
+    part.read_exact(&mut manifest)?;
+    let num_g1_points =
+        u32::from_be_bytes([manifest[16], manifest[17], manifest[18], manifest[19]]);
+    let num_g2_points =
+        u32::from_be_bytes([manifest[20], manifest[21], manifest[22], manifest[23]]);
+    if num_points > num_g1_points {
+        return Err(SrsError::InsufficientPoints {
+            available: num_g1_points,
+            requested: num_points,
+            path: None,
+        });
+    }
+    if num_g2_points == 0 {
+        return Err(SrsError::InvalidTranscript(format!("{} holds no G2 point", redact_url(url))));
+    }
+
+    let g1_end = MANIFEST_SIZE + num_points as u64 * 64;
+    let g2_start = MANIFEST_SIZE + num_g1_points as u64 * 64;
+    let mut g2_data = Vec::with_capacity(128);
+    download(client, url, g2_start, Some(128), &mut g2_data)?;
+
+    part.set_len(g1_end)?;
+    part.seek(SeekFrom::Start(g1_end))?;
+    part.write_all(&g2_data)?;
+    part.seek(SeekFrom::Start(16))?;
+    part.write_all(&num_points.to_be_bytes())?;
+    Ok(g2_data.len() as u64)
+}
+
+/// Downloads the bytes from `start`, `len` of them or up to the end of the file, and writes them at
+/// the same offset of `part`.
+///
+/// # Returns
+/// * `Result<u64, SrsError>` - The number of bytes downloaded.
+fn fetch_range(
+    client: &Client,
+    url: &str,
+    part: &mut File,
+    start: u64,
+    len: Option<u64>,
+) -> Result<u64, SrsError> {
+    if len == Some(0) {
+        return Ok(0);
+    }
+    part.set_len(start)?;
+    part.seek(SeekFrom::Start(start))?;
+    download(client, url, start, len, part)
+}
+
+/// Streams the bytes from `start`, `len` of them or up to the end of the file, into `writer`.
+///
+/// Servers that ignore the range header answer with the whole file, of which the bytes before
+/// `start` are skipped.
+fn download(
+    client: &Client,
+    url: &str,
+    start: u64,
+    len: Option<u64>,
+    writer: &mut impl Write,
+) -> Result<u64, SrsError> {
+    let range = match len {
+        Some(len) => format!("bytes={}-{}", start, start + len - 1),
+        None => format!("bytes={}-", start),
+    };
+    let response = client.get(url).header(RANGE, range).send()?;
+    if len.is_none() && start > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // The partial file already holds the whole transcript.
+        return Ok(0);
+    }
+    let mut response = check_response(response, &[StatusCode::OK, StatusCode::PARTIAL_CONTENT])?;
+    if response.status() != StatusCode::PARTIAL_CONTENT {
+        io::copy(&mut (&mut response).take(start), &mut io::sink())?;
+    }
+
+    let len = match len {
+        Some(len) => len,
+        None => return Ok(io::copy(&mut response, writer)?),
+    };
+    let written = io::copy(&mut response.take(len), writer)?;
+    if written < len {
+        return Err(SrsError::InvalidTranscript(format!(
+            "{} ended after {} bytes, expected at least {}",
+            redact_url(url),
+            start + written,
+            start + len
+        )));
+    }
+    Ok(written)
+}
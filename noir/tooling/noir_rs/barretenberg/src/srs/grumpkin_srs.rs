@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+use super::{
+    local_srs::{G2Location, LocalSrs},
+    netsrs::NetSrs,
+    Curve, Srs, SrsError,
+};
+
+/// Where a [`GrumpkinSrs`] reads the transcript from.
+#[derive(Debug)]
+enum Loader {
+    Local(PathBuf),
+    Net(Box<NetSrs>),
+}
+
+/// Loads a Grumpkin SRS, as written by barretenberg's `grumpkin_srs_gen`.
+///
+/// The transcript uses the ignition layout, a 28-byte manifest followed by 64-byte G1 points, but
+/// holds no G2 point, so [`Srs::g2_data`] is always empty. Initialize the backend with
+/// [`srs_init_grumpkin`](super::srs_init_grumpkin) rather than [`srs_init`](super::srs_init).
+#[derive(Debug)]
+pub struct GrumpkinSrs {
+    pub data: Vec<u8>,
+    pub num_points: u32,
+    g2_data: Vec<u8>,
+    loader: Loader,
+}
+
+impl GrumpkinSrs {
+    /// Creates a new GrumpkinSrs instance reading a transcript from disk.
+    ///
+    /// # Arguments
+    /// * `path` - Path to a transcript file, or to the output directory of `grumpkin_srs_gen`
+    ///   holding `monomial/transcript00.dat`.
+    pub fn local(path: impl AsRef<Path>) -> Self {
+        Self::with_loader(Loader::Local(path.as_ref().to_path_buf()))
+    }
+
+    /// Creates a new GrumpkinSrs instance downloading the transcript from the given URL.
+    ///
+    /// # Arguments
+    /// * `url` - URL of the transcript file.
+    pub fn net(url: &str) -> Self {
+        Self::with_net_srs(NetSrs::with_url(url))
+    }
+
+    /// Creates a new GrumpkinSrs instance downloading the transcript with the given NetSrs, which
+    /// carries the URL, client and concurrency.
+    ///
+    /// # Arguments
+    /// * `net_srs` - The source used to download the transcript.
+    pub fn with_net_srs(net_srs: NetSrs) -> Self {
+        Self::with_loader(Loader::Net(Box::new(net_srs.with_curve(Curve::Grumpkin))))
+    }
+
+    fn with_loader(loader: Loader) -> Self {
+        GrumpkinSrs { data: Vec::new(), num_points: 0, g2_data: Vec::new(), loader }
+    }
+}
+
+impl Srs for GrumpkinSrs {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        self.data = match &mut self.loader {
+            Loader::Local(path) => {
+                let transcript = if path.is_dir() {
+                    path.join("monomial").join("transcript00.dat")
+                } else {
+                    path.clone()
+                };
+                LocalSrs::load_ignition(
+                    &transcript,
+                    num_points,
+                    Curve::Grumpkin,
+                    G2Location::Transcript,
+                )?
+                .0
+            }
+            Loader::Net(net_srs) => {
+                net_srs.load_data(num_points)?;
+                std::mem::take(&mut net_srs.data)
+            }
+        };
+        self.num_points = (self.data.len() / Curve::Grumpkin.g1_point_size()) as u32;
+        Ok(())
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        &self.g2_data
+    }
+
+    fn num_points(&self) -> u32 {
+        self.num_points
+    }
+
+    fn clear(&mut self) {
+        self.data = Vec::new();
+        self.g2_data = Vec::new();
+    }
+}
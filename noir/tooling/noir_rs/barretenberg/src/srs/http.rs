@@ -0,0 +1,87 @@
+use std::io::Read;
+
+use reqwest::{
+    blocking::{Client, Response},
+    header::CONTENT_TYPE,
+    redirect::Policy,
+    StatusCode,
+};
+
+use super::SrsError;
+
+/// Largest number of redirects followed by the clients created by [`NetSrs`](super::netsrs::NetSrs)
+/// and [`download_srs`](super::download::download_srs).
+pub const MAX_REDIRECTS: usize = 5;
+
+/// Number of bytes of an unexpected response body included in the error.
+const BODY_EXCERPT_LEN: u64 = 256;
+
+/// Creates the client used when none is supplied, following at most [`MAX_REDIRECTS`] redirects
+/// and logging each of them.
+pub(crate) fn default_client() -> Client {
+    let policy = Policy::custom(|attempt| {
+        let from = attempt.previous().last().map_or(String::new(), |url| redact_url(url.as_str()));
+        if attempt.previous().len() > MAX_REDIRECTS {
+            log::warn!("SRS download from {} stopped after {} redirects", from, MAX_REDIRECTS);
+            attempt.error(format!("more than {} redirects", MAX_REDIRECTS))
+        } else {
+            log::debug!(
+                "SRS download redirected from {} to {}",
+                from,
+                redact_url(attempt.url().as_str())
+            );
+            attempt.follow()
+        }
+    });
+    // Only fails when the TLS backend cannot be initialized, as `Client::new` does.
+    Client::builder().redirect(policy).build().expect("cannot build the SRS download client")
+}
+
+/// Removes the query string and fragment of a URL before it is logged or reported in an error.
+///
+/// Presigned URLs carry their credentials in the query string, such as `X-Goog-Signature` or
+/// `X-Amz-Signature`, which must not end up in logs.
+pub(crate) fn redact_url(url: &str) -> String {
+    url.split(['?', '#']).next().unwrap_or_default().to_string()
+}
+
+/// Checks that `response` has one of the `expected` statuses and carries binary data.
+///
+/// Servers and object stores answer errors, such as a rate limit, with an XML, JSON or HTML body
+/// that would otherwise be read as points, sometimes with a `200 OK` status.
+///
+/// # Returns
+/// * `Result<Response, SrsError>` - The response, or `SrsError::UnexpectedResponse` holding its
+///   status, content type, final URL without its query string and the start of its body.
+pub(crate) fn check_response(
+    response: Response,
+    expected: &[StatusCode],
+) -> Result<Response, SrsError> {
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let is_text = content_type.as_deref().map_or(false, |content_type| {
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        mime.starts_with("text/")
+            || mime.ends_with("/xml")
+            || mime.ends_with("+xml")
+            || mime.ends_with("/json")
+    });
+    if expected.contains(&response.status()) && !is_text {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let url = redact_url(response.url().as_str());
+    let mut body = Vec::new();
+    // The excerpt only helps the user tell what went wrong, a failure to read it is not reported.
+    let _ = response.take(BODY_EXCERPT_LEN).read_to_end(&mut body);
+    Err(SrsError::UnexpectedResponse {
+        status,
+        url,
+        content_type,
+        body: String::from_utf8_lossy(&body).trim().to_string(),
+    })
+}
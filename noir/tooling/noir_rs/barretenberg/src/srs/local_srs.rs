@@ -0,0 +1,392 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use super::{starts_with_generator, Curve, Srs, SrsError};
+
+/// Name of the file holding the G1 points in a flat CRS directory such as `~/.bb-crs`.
+pub const FLAT_G1_FILE: &str = "bn254_g1.dat";
+/// Name of the file holding the G2 point in a flat CRS directory such as `~/.bb-crs`.
+pub const FLAT_G2_FILE: &str = "bn254_g2.dat";
+
+const MANIFEST_SIZE: u64 = 28;
+
+/// Layout of SRS files on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    /// An ignition transcript such as `transcript00.dat`: a 28-byte manifest followed by the G1
+    /// points and then the G2 points.
+    Ignition,
+    /// The layout distributed by recent barretenberg tooling: `bn254_g1.dat` holds the G1 points
+    /// from offset 0 and `bn254_g2.dat` next to it holds the G2 point.
+    Flat,
+}
+
+/// Reads the SRS from files on disk.
+#[derive(Debug)]
+pub struct LocalSrs {
+    pub data: Vec<u8>,
+    pub g2_data: Vec<u8>,
+    pub num_points: u32,
+    path: PathBuf,
+    format: Option<TranscriptFormat>,
+    g2_path: Option<PathBuf>,
+}
+
+impl LocalSrs {
+    /// Creates a new LocalSrs instance, detecting the format of the files when loading.
+    ///
+    /// A directory is read in the flat format. A file starting with the bn254 generator is read as
+    /// the `bn254_g1.dat` file of the flat format, any other file as an ignition transcript.
+    ///
+    /// # Arguments
+    /// * `path` - Path to an ignition transcript, a `bn254_g1.dat` file or a flat CRS directory.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        LocalSrs {
+            data: Vec::new(),
+            g2_data: Vec::new(),
+            num_points: 0,
+            path: path.as_ref().to_path_buf(),
+            format: None,
+            g2_path: None,
+        }
+    }
+
+    /// Creates a new LocalSrs instance reading the files in the given format.
+    ///
+    /// # Arguments
+    /// * `path` - Path to an ignition transcript, a `bn254_g1.dat` file or a flat CRS directory.
+    /// * `format` - The layout of the files.
+    pub fn with_format(path: impl AsRef<Path>, format: TranscriptFormat) -> Self {
+        LocalSrs { format: Some(format), ..Self::new(path) }
+    }
+
+    /// Reads the G2 point from its own file instead of the transcript, for setups whose G2 point
+    /// is not stored where the ignition or flat layout places it, such as the result of a custom
+    /// ceremony.
+    ///
+    /// Only the G1 points are read from the transcript, whose G2 point, if any, is ignored.
+    ///
+    /// # Arguments
+    /// * `g2_path` - Path to a file holding exactly the 128 bytes of the G2 point.
+    pub fn with_g2_path(mut self, g2_path: impl AsRef<Path>) -> Self {
+        self.g2_path = Some(g2_path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Returns the path of the file holding the G2 point of the flat format.
+    fn flat_g2_path(&self, g1_path: &Path) -> PathBuf {
+        self.g2_path.clone().unwrap_or_else(|| g1_path.with_file_name(FLAT_G2_FILE))
+    }
+
+    /// Resolves the format and the path of the file holding the G1 points.
+    fn resolve(&self) -> Result<(TranscriptFormat, PathBuf), SrsError> {
+        if self.path.is_dir() {
+            if self.format == Some(TranscriptFormat::Ignition) {
+                return Err(SrsError::InvalidTranscript(format!(
+                    "{} is a directory, not an ignition transcript",
+                    self.path.display()
+                )));
+            }
+            return Ok((TranscriptFormat::Flat, self.path.join(FLAT_G1_FILE)));
+        }
+        let format = match self.format {
+            Some(format) => format,
+            None => {
+                let mut file = open(&self.path)?;
+                // Files too short to hold a point are left for the ignition reader to reject.
+                if file_len(&file, &self.path)? >= 64
+                    && starts_with_generator(&read_range(&mut file, &self.path, 0, 64)?)
+                {
+                    TranscriptFormat::Flat
+                } else {
+                    TranscriptFormat::Ignition
+                }
+            }
+        };
+        Ok((format, self.path.clone()))
+    }
+
+    /// Returns the number of G1 points the files hold, the largest number of points
+    /// [`Srs::load_data`] accepts, from the manifest and the sizes of the files.
+    ///
+    /// This tells which circuits a trimmed transcript can prove before any point is read. A file
+    /// of `len` bytes holding the G2 point right after the G1 points, as written by
+    /// [`trim_transcript`], holds `(len - 28 - 128) / 64` points.
+    ///
+    /// # Returns
+    /// * `Result<u32, SrsError>` - Returns the number of points, or an SrsError if the files are
+    ///   missing or would be rejected by [`Srs::load_data`].
+    pub fn max_points(&self) -> Result<u32, SrsError> {
+        let (format, g1_path) = self.resolve()?;
+        match (format, &self.g2_path) {
+            (TranscriptFormat::Ignition, None) => {
+                let layout = IgnitionLayout::read(&g1_path, Curve::Bn254, G2Location::Transcript)?;
+                layout.check_g2(&g1_path, Curve::Bn254, G2Location::Transcript)?;
+                Ok(layout.available)
+            }
+            (TranscriptFormat::Ignition, Some(g2_path)) => {
+                let layout = IgnitionLayout::read(&g1_path, Curve::Bn254, G2Location::Separate)?;
+                open_g2_file(g2_path)?;
+                Ok(layout.available)
+            }
+            (TranscriptFormat::Flat, _) => {
+                let (_, available) = open_flat_g1(&g1_path)?;
+                open_g2_file(&self.flat_g2_path(&g1_path))?;
+                Ok(available)
+            }
+        }
+    }
+
+    /// Reads `num_points` G1 points and, for bn254, the first G2 point from an ignition
+    /// transcript, unless the G2 point is supplied separately, in which case no G2 data is
+    /// returned.
+    pub(super) fn load_ignition(
+        path: &Path,
+        num_points: u32,
+        curve: Curve,
+        g2: G2Location,
+    ) -> Result<(Vec<u8>, Vec<u8>), SrsError> {
+        let mut layout = IgnitionLayout::read(path, curve, g2)?;
+        if num_points > layout.available {
+            return Err(SrsError::InsufficientPoints {
+                available: layout.available,
+                requested: num_points,
+                path: Some(path.to_path_buf()),
+            });
+        }
+        layout.check_g2(path, curve, g2)?;
+
+        let g1_data = read_range(&mut layout.file, path, MANIFEST_SIZE, num_points as u64 * 64)?;
+        let g2_data = match g2 {
+            G2Location::Transcript => {
+                read_range(&mut layout.file, path, layout.g2_start, curve.g2_size() as u64)?
+            }
+            G2Location::Separate => Vec::new(),
+        };
+        Ok((g1_data, g2_data))
+    }
+
+    /// Reads `num_points` G1 points and the G2 point from the flat format files.
+    fn load_flat(
+        g1_path: &Path,
+        g2_path: &Path,
+        num_points: u32,
+    ) -> Result<(Vec<u8>, Vec<u8>), SrsError> {
+        let (mut g1_file, available) = open_flat_g1(g1_path)?;
+        if num_points > available {
+            return Err(SrsError::InsufficientPoints {
+                available,
+                requested: num_points,
+                path: Some(g1_path.to_path_buf()),
+            });
+        }
+
+        let g1_data = read_range(&mut g1_file, g1_path, 0, num_points as u64 * 64)?;
+        Ok((g1_data, read_g2_file(g2_path)?))
+    }
+}
+
+/// Where the G2 point of an ignition transcript is read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum G2Location {
+    /// After the G1 points of the transcript, as its manifest describes.
+    Transcript,
+    /// From another file, see [`LocalSrs::with_g2_path`], so the transcript need not hold it.
+    Separate,
+}
+
+/// The points held by an ignition transcript, read from its manifest and its size.
+struct IgnitionLayout {
+    file: File,
+    file_len: u64,
+    /// The number of G1 points the file holds.
+    available: u32,
+    /// The offset of the first G2 point.
+    g2_start: u64,
+}
+
+impl IgnitionLayout {
+    fn read(path: &Path, curve: Curve, g2: G2Location) -> Result<Self, SrsError> {
+        let mut file = open(path)?;
+        let file_len = file_len(&file, path)?;
+        if file_len < MANIFEST_SIZE {
+            return Err(SrsError::InvalidTranscript(format!(
+                "{} is {} bytes, shorter than the {}-byte manifest",
+                path.display(),
+                file_len,
+                MANIFEST_SIZE
+            )));
+        }
+        let manifest = read_range(&mut file, path, 0, MANIFEST_SIZE)?;
+        let num_g1_points =
+            u32::from_be_bytes([manifest[16], manifest[17], manifest[18], manifest[19]]);
+        let num_g2_points =
+            u32::from_be_bytes([manifest[20], manifest[21], manifest[22], manifest[23]]);
+        if g2 == G2Location::Transcript && curve.g2_size() > 0 && num_g2_points == 0 {
+            return Err(SrsError::InvalidTranscript(format!(
+                "the manifest of {} describes no G2 point",
+                path.display()
+            )));
+        }
+
+        // A partially downloaded transcript still holds the points written before it was cut.
+        let available = num_g1_points.min(((file_len - MANIFEST_SIZE) / 64) as u32);
+        let g2_start = MANIFEST_SIZE + num_g1_points as u64 * 64;
+        Ok(IgnitionLayout { file, file_len, available, g2_start })
+    }
+
+    /// Checks that the file holds the G2 point the curve requires, unless it is read from another
+    /// file.
+    fn check_g2(&self, path: &Path, curve: Curve, g2: G2Location) -> Result<(), SrsError> {
+        if g2 == G2Location::Separate {
+            return Ok(());
+        }
+        if curve.g2_size() > 0 && self.file_len < self.g2_start + curve.g2_size() as u64 {
+            return Err(SrsError::FileTooSmallForG2 {
+                path: path.to_path_buf(),
+                len: self.file_len,
+                g2_offset: self.g2_start,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Opens the `bn254_g1.dat` file of the flat format, returning it with the number of points it
+/// holds.
+fn open_flat_g1(g1_path: &Path) -> Result<(File, u32), SrsError> {
+    let g1_file = open(g1_path)?;
+    let g1_len = file_len(&g1_file, g1_path)?;
+    if g1_len % 64 != 0 {
+        return Err(SrsError::InvalidTranscript(format!(
+            "{} is {} bytes, which is not a whole number of 64-byte G1 points",
+            g1_path.display(),
+            g1_len
+        )));
+    }
+    Ok((g1_file, (g1_len / 64) as u32))
+}
+
+/// Opens a file holding only the G2 point, such as the `bn254_g2.dat` file of the flat format,
+/// checking it holds exactly its 128 bytes.
+fn open_g2_file(g2_path: &Path) -> Result<File, SrsError> {
+    let g2_file = open(g2_path)?;
+    let g2_len = file_len(&g2_file, g2_path)?;
+    if g2_len < 128 {
+        return Err(SrsError::FileTooSmallForG2 {
+            path: g2_path.to_path_buf(),
+            len: g2_len,
+            g2_offset: 0,
+        });
+    }
+    if g2_len != 128 {
+        return Err(SrsError::InvalidTranscript(format!(
+            "{} is {} bytes instead of a single 128-byte G2 point",
+            g2_path.display(),
+            g2_len
+        )));
+    }
+    Ok(g2_file)
+}
+
+/// Reads the G2 point from a file holding only it, see [`open_g2_file`].
+fn read_g2_file(g2_path: &Path) -> Result<Vec<u8>, SrsError> {
+    read_range(&mut open_g2_file(g2_path)?, g2_path, 0, 128)
+}
+
+impl Srs for LocalSrs {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        let (format, g1_path) = self.resolve()?;
+        (self.data, self.g2_data) = match (format, &self.g2_path) {
+            (TranscriptFormat::Ignition, None) => {
+                Self::load_ignition(&g1_path, num_points, Curve::Bn254, G2Location::Transcript)?
+            }
+            (TranscriptFormat::Ignition, Some(g2_path)) => {
+                let (g1_data, _) =
+                    Self::load_ignition(&g1_path, num_points, Curve::Bn254, G2Location::Separate)?;
+                (g1_data, read_g2_file(g2_path)?)
+            }
+            (TranscriptFormat::Flat, _) => {
+                Self::load_flat(&g1_path, &self.flat_g2_path(&g1_path), num_points)?
+            }
+        };
+        self.num_points = num_points;
+        Ok(())
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        &self.g2_data
+    }
+
+    fn num_points(&self) -> u32 {
+        self.num_points
+    }
+
+    fn clear(&mut self) {
+        self.data = Vec::new();
+        self.g2_data = Vec::new();
+    }
+
+    /// Returns the points held by the files, see [`LocalSrs::max_points`], or `None` if they
+    /// cannot be read, leaving [`Srs::load_data`] to report why.
+    fn max_points(&self) -> Option<u32> {
+        LocalSrs::max_points(self).ok()
+    }
+}
+
+/// Writes an ignition transcript holding the first `num_points` G1 points and the G2 point of the
+/// ignition transcript at `src`, with its manifest rewritten to describe them.
+///
+/// The result is read by [`LocalSrs`] like the full transcript, for circuits needing up to
+/// `num_points` points, which lets a small transcript be shipped along with a circuit.
+///
+/// # Arguments
+/// * `src` - Path of the ignition transcript to trim, such as `transcript00.dat`.
+/// * `dst` - Path the trimmed transcript is written to.
+/// * `num_points` - Number of G1 points to keep.
+///
+/// # Returns
+/// * `Result<(), SrsError>` - Returns an SrsError if `src` holds fewer points or `dst` cannot be
+///   written.
+pub fn trim_transcript(src: &Path, dst: &Path, num_points: u32) -> Result<(), SrsError> {
+    let (g1_data, g2_data) =
+        LocalSrs::load_ignition(src, num_points, Curve::Bn254, G2Location::Transcript)?;
+    let mut manifest = read_range(&mut open(src)?, src, 0, MANIFEST_SIZE)?;
+    manifest[16..20].copy_from_slice(&num_points.to_be_bytes());
+    manifest[20..24].copy_from_slice(&1u32.to_be_bytes());
+
+    let mut transcript = manifest;
+    transcript.extend_from_slice(&g1_data);
+    transcript.extend_from_slice(&g2_data);
+    fs::write(dst, transcript).map_err(|source| file_error(dst, source))
+}
+
+/// Opens an SRS file, naming it in the error.
+fn open(path: &Path) -> Result<File, SrsError> {
+    File::open(path).map_err(|source| file_error(path, source))
+}
+
+/// Returns the length of an SRS file, naming it in the error.
+fn file_len(file: &File, path: &Path) -> Result<u64, SrsError> {
+    Ok(file.metadata().map_err(|source| file_error(path, source))?.len())
+}
+
+/// Reads `len` bytes starting at `offset` of the file at `path`.
+fn read_range(file: &mut File, path: &Path, offset: u64, len: u64) -> Result<Vec<u8>, SrsError> {
+    let mut buffer = vec![0u8; len as usize];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut buffer))
+        .map_err(|source| file_error(path, source))?;
+    Ok(buffer)
+}
+
+fn file_error(path: &Path, source: io::Error) -> SrsError {
+    SrsError::File { path: path.to_path_buf(), source }
+}
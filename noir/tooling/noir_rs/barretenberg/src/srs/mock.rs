@@ -0,0 +1,434 @@
+use std::{
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use super::netsrs::{G1_START, G2_START};
+
+/// Size of the buffers the served bytes are written from.
+const WRITE_CHUNK_SIZE: usize = 1 << 16;
+
+/// The file served by a [`MockSrsServer`].
+///
+/// Bytes not covered by any segment are served as zeros, so that a transcript with the layout of
+/// the 322 MB ignition `transcript00.dat` only holds the points actually read.
+#[derive(Debug, Clone)]
+pub struct MockTranscript {
+    len: u64,
+    segments: Vec<(u64, Vec<u8>)>,
+}
+
+impl MockTranscript {
+    /// Lays out the points with the offsets of the ignition transcript, where the G2 point
+    /// follows 5040001 G1 points, as [`NetSrs`](super::netsrs::NetSrs) expects.
+    ///
+    /// # Arguments
+    /// * `g1_data` - The G1 points served from the start of the transcript.
+    /// * `g2_data` - The G2 point.
+    pub fn ignition(g1_data: &[u8], g2_data: &[u8]) -> Self {
+        let num_g1_points = ((G2_START - G1_START) / 64) as u32;
+        let mut manifest = Vec::with_capacity(G1_START as usize);
+        for word in [0, 1, num_g1_points, 1, num_g1_points, 1, 0] {
+            manifest.extend_from_slice(&u32::to_be_bytes(word));
+        }
+        MockTranscript {
+            len: G2_START + g2_data.len() as u64,
+            segments: vec![
+                (0, manifest),
+                (G1_START, g1_data.to_vec()),
+                (G2_START, g2_data.to_vec()),
+            ],
+        }
+    }
+
+    /// Returns the length of the transcript in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether the transcript holds no byte.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes the bytes from `start` to `end`, excluded.
+    fn write_range(&self, writer: &mut impl Write, start: u64, end: u64) -> io::Result<()> {
+        let mut buf = vec![0u8; WRITE_CHUNK_SIZE];
+        let mut offset = start;
+        while offset < end {
+            let chunk_end = end.min(offset + WRITE_CHUNK_SIZE as u64);
+            let chunk = &mut buf[..(chunk_end - offset) as usize];
+            chunk.fill(0);
+            for (segment_start, data) in &self.segments {
+                let segment_end = segment_start + data.len() as u64;
+                let (from, to) = (offset.max(*segment_start), chunk_end.min(segment_end));
+                if from < to {
+                    chunk[(from - offset) as usize..(to - offset) as usize].copy_from_slice(
+                        &data[(from - segment_start) as usize..(to - segment_start) as usize],
+                    );
+                }
+            }
+            writer.write_all(chunk)?;
+            offset = chunk_end;
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<u8>> for MockTranscript {
+    fn from(bytes: Vec<u8>) -> Self {
+        MockTranscript { len: bytes.len() as u64, segments: vec![(0, bytes)] }
+    }
+}
+
+impl Default for MockTranscript {
+    fn default() -> Self {
+        MockTranscript::from(Vec::new())
+    }
+}
+
+/// A request received by a [`MockSrsServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockRequest {
+    /// The request method, such as `GET`.
+    pub method: String,
+    /// The requested path.
+    pub path: String,
+    /// The first and last byte of the `Range` header, `None` as the end of an open-ended range.
+    pub range: Option<(u64, Option<u64>)>,
+    /// The request headers, with their names in lowercase.
+    pub headers: Vec<(String, String)>,
+}
+
+impl MockRequest {
+    /// Returns the value of a header, matching its name case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Configures the failures a [`MockSrsServer`] injects.
+#[derive(Debug, Clone, Default)]
+pub struct MockSrsServerBuilder {
+    transcript: MockTranscript,
+    failures: usize,
+    truncate_at: Option<u64>,
+    delay: Option<Duration>,
+    ignore_range: bool,
+    short_ranges: Option<u64>,
+    signed_ranges: bool,
+    etags: Vec<String>,
+}
+
+impl MockSrsServerBuilder {
+    /// Answers the first `failures` requests with `503 Service Unavailable`.
+    pub fn failures(mut self, failures: usize) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    /// Closes the connection once `len` bytes of a response body have been sent, while the
+    /// headers announce the whole body.
+    pub fn truncate_at(mut self, len: u64) -> Self {
+        self.truncate_at = Some(len);
+        self
+    }
+
+    /// Waits for `delay` before answering each request.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Answers range requests with the whole transcript and `200 OK`, as servers without range
+    /// support do.
+    pub fn ignore_range(mut self) -> Self {
+        self.ignore_range = true;
+        self
+    }
+
+    /// Answers range requests with at most the first `len` bytes of the range, announced as such
+    /// in the `Content-Range` and `Content-Length` headers, as a proxy caching a truncated range
+    /// does.
+    pub fn short_ranges(mut self, len: u64) -> Self {
+        self.short_ranges = Some(len);
+        self
+    }
+
+    /// Only answers requests whose `X-Goog-Signature` query parameter is `<first>-<last>`, the
+    /// first and last byte of their `Range` header, as a server accepting presigned URLs signed
+    /// for a single range does. Other requests are answered with `403 Forbidden`.
+    pub fn signed_ranges(mut self) -> Self {
+        self.signed_ranges = true;
+        self
+    }
+
+    /// Sends the `ETag` of index `n` of `etags` with the response to the request of index `n`, and
+    /// the last one with the following responses, as a server whose transcript is replaced does.
+    pub fn etags<I, S>(mut self, etags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.etags = etags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Starts the server on a free local port.
+    ///
+    /// # Returns
+    /// * `(String, MockSrsServer)` - The URL of the transcript, and the server, which stops when
+    ///   dropped.
+    pub fn serve(self) -> (String, MockSrsServer) {
+        let listener =
+            TcpListener::bind("127.0.0.1:0").expect("failed to bind the mock SRS server");
+        let address = listener.local_addr().expect("failed to read the mock SRS server address");
+        let url = format!("http://{}/transcript00.dat", address);
+        let state = Arc::new(ServerState {
+            config: self,
+            requests: Mutex::new(Vec::new()),
+            served: AtomicUsize::new(0),
+            connections: AtomicUsize::new(0),
+            stopped: AtomicBool::new(false),
+        });
+
+        let server_state = state.clone();
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server_state.stopped.load(Ordering::SeqCst) {
+                    return;
+                }
+                let state = server_state.clone();
+                if let Ok(stream) = stream {
+                    state.connections.fetch_add(1, Ordering::SeqCst);
+                    thread::spawn(move || state.handle_connection(stream));
+                }
+            }
+        });
+        (url, MockSrsServer { address, state, handle: Some(handle) })
+    }
+}
+
+/// A local HTTP server standing in for the ignition transcript host, to test SRS downloads
+/// offline.
+///
+/// It answers `GET` and `HEAD` requests for any path with the transcript, honoring `Range`
+/// headers, and records the requests it receives. Failures, truncated bodies and slow responses
+/// can be injected with [`MockSrsServer::builder`].
+///
+/// ```no_run
+/// # use noir_rs_barretenberg::srs::{dev_srs::DevSrs, mock::{MockSrsServer, MockTranscript}, netsrs::NetSrs, Srs};
+/// let mut dev_srs = DevSrs::new(1024);
+/// dev_srs.load_data(1024)?;
+/// let (url, server) =
+///     MockSrsServer::serve(MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data()));
+/// NetSrs::with_url(&url).load_data(1024)?;
+/// assert!(!server.requests().is_empty());
+/// # Ok::<(), noir_rs_barretenberg::srs::SrsError>(())
+/// ```
+pub struct MockSrsServer {
+    address: SocketAddr,
+    state: Arc<ServerState>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockSrsServer {
+    /// Serves the transcript without injecting any failure.
+    ///
+    /// # Arguments
+    /// * `transcript` - The file to serve, such as the bytes of a transcript.
+    ///
+    /// # Returns
+    /// * `(String, MockSrsServer)` - The URL of the transcript, and the server, which stops when
+    ///   dropped.
+    pub fn serve(transcript: impl Into<MockTranscript>) -> (String, MockSrsServer) {
+        Self::builder(transcript).serve()
+    }
+
+    /// Returns a builder to configure the failures the server injects.
+    ///
+    /// # Arguments
+    /// * `transcript` - The file to serve, such as the bytes of a transcript.
+    pub fn builder(transcript: impl Into<MockTranscript>) -> MockSrsServerBuilder {
+        MockSrsServerBuilder { transcript: transcript.into(), ..MockSrsServerBuilder::default() }
+    }
+
+    /// Returns the requests received so far, in the order they arrived.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    /// Returns the number of connections accepted so far, which is lower than the number of
+    /// requests when clients reuse their connections.
+    pub fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+
+    /// Returns the requests received since the last call, and forgets them.
+    pub fn take_requests(&self) -> Vec<MockRequest> {
+        std::mem::take(&mut *self.state.requests.lock().unwrap())
+    }
+}
+
+impl Drop for MockSrsServer {
+    fn drop(&mut self) {
+        self.state.stopped.store(true, Ordering::SeqCst);
+        // Wakes the listener up so that it sees the flag.
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// State shared by the listener and the connection threads of a [`MockSrsServer`].
+struct ServerState {
+    config: MockSrsServerBuilder,
+    requests: Mutex<Vec<MockRequest>>,
+    served: AtomicUsize,
+    connections: AtomicUsize,
+    stopped: AtomicBool,
+}
+
+impl ServerState {
+    /// Answers the requests of a connection until it is closed.
+    fn handle_connection(&self, mut stream: TcpStream) {
+        while let Some(request) = read_request(&mut stream) {
+            if self.stopped.load(Ordering::SeqCst) {
+                return;
+            }
+            self.requests.lock().unwrap().push(request.clone());
+            if let Some(delay) = self.config.delay {
+                thread::sleep(delay);
+            }
+            match self.respond(&mut stream, &request) {
+                Ok(true) => {}
+                Ok(false) | Err(_) => return,
+            }
+        }
+    }
+
+    /// Writes the response to a request.
+    ///
+    /// # Returns
+    /// * `io::Result<bool>` - Whether the connection can be reused.
+    fn respond(&self, stream: &mut TcpStream, request: &MockRequest) -> io::Result<bool> {
+        let index = self.served.fetch_add(1, Ordering::SeqCst);
+        if index < self.config.failures {
+            stream.write_all(
+                b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            )?;
+            return Ok(false);
+        }
+
+        if self.config.signed_ranges && !has_range_signature(request) {
+            let body = "<?xml version='1.0' encoding='UTF-8'?><Error><Code>SignatureDoesNotMatch</Code></Error>";
+            let head = format!(
+                "HTTP/1.1 403 Forbidden\r\nContent-Type: application/xml\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(head.as_bytes())?;
+            stream.write_all(body.as_bytes())?;
+            return Ok(true);
+        }
+
+        let transcript = &self.config.transcript;
+        let (status, start, end) = match request.range {
+            Some((start, _)) if !self.config.ignore_range && start >= transcript.len() => {
+                let head = format!(
+                    "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\n\r\n",
+                    transcript.len()
+                );
+                stream.write_all(head.as_bytes())?;
+                return Ok(true);
+            }
+            Some((start, end)) if !self.config.ignore_range => {
+                let end = end.map_or(transcript.len(), |end| (end + 1).min(transcript.len()));
+                let end = self.config.short_ranges.map_or(end, |len| end.min(start + len));
+                ("206 Partial Content", start, end)
+            }
+            _ => ("200 OK", 0, transcript.len()),
+        };
+
+        let mut head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\n",
+            status,
+            end - start
+        );
+        if let Some(etag) = self.config.etags.get(index).or_else(|| self.config.etags.last()) {
+            head.push_str(&format!("ETag: \"{}\"\r\n", etag));
+        }
+        if status.starts_with("206") {
+            head.push_str(&format!(
+                "Content-Range: bytes {}-{}/{}\r\n",
+                start,
+                end - 1,
+                transcript.len()
+            ));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        if request.method == "HEAD" {
+            return Ok(true);
+        }
+
+        match self.config.truncate_at {
+            Some(len) if len < end - start => {
+                transcript.write_range(stream, start, start + len)?;
+                Ok(false)
+            }
+            _ => {
+                transcript.write_range(stream, start, end)?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// Returns whether the `X-Goog-Signature` query parameter of a request matches its range.
+fn has_range_signature(request: &MockRequest) -> bool {
+    let signature = request.path.split_once('?').and_then(|(_, query)| {
+        query.split('&').find_map(|parameter| parameter.strip_prefix("X-Goog-Signature="))
+    });
+    match (signature, request.range) {
+        (Some(signature), Some((start, Some(end)))) => signature == format!("{}-{}", start, end),
+        _ => false,
+    }
+}
+
+/// Reads one request head from the stream, returning `None` once the connection is closed.
+fn read_request(stream: &mut TcpStream) -> Option<MockRequest> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut buf).ok()? == 0 {
+            return None;
+        }
+        head.push(buf[0]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let (method, path) = (request_line.next()?.to_string(), request_line.next()?.to_string());
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let range = headers
+        .iter()
+        .find(|(name, _)| name == "range")
+        .and_then(|(_, value)| value.strip_prefix("bytes="))
+        .and_then(|range| range.split_once('-'))
+        .and_then(|(start, end)| Some((start.parse().ok()?, end.parse().ok())));
+    Some(MockRequest { method, path, range, headers })
+}
@@ -1,8 +1,284 @@
-use crate::rust_srs_init_srs;
+use std::path::PathBuf;
 
-use super::{parse_c_str, BackendError};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, PrimeField};
 
+use crate::{rust_srs_init_grumpkin_srs, rust_srs_init_srs};
+
+use super::{binding_error, BackendError};
+
+pub mod cached_srs;
+pub mod cancel;
+pub mod checksum;
+pub mod dev_srs;
+pub mod download;
+pub mod grumpkin_srs;
+pub mod http;
+pub mod local_srs;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
 pub mod netsrs;
+pub mod source;
+
+#[cfg(test)]
+pub mod test;
+
+/// The curve an SRS is defined over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Curve {
+    /// The bn254 SRS of the ignition ceremony, used by the Plonk and Honk provers.
+    #[default]
+    Bn254,
+    /// The Grumpkin SRS used by the ECCVM of the Goblin proving flows. It holds no G2 point.
+    Grumpkin,
+}
+
+impl Curve {
+    /// Returns the size in bytes of an encoded G1 point.
+    pub const fn g1_point_size(self) -> usize {
+        64
+    }
+
+    /// Returns the size in bytes of the encoded G2 data, zero for Grumpkin.
+    pub const fn g2_size(self) -> usize {
+        match self {
+            Curve::Bn254 => 128,
+            Curve::Grumpkin => 0,
+        }
+    }
+}
+
+/// A source of structured reference string (SRS) data for the C++ backend.
+///
+/// G1 points are encoded as 64 bytes each and the G2 point as 128 bytes, following the layout of
+/// the Aztec ignition transcript. The data is exposed as slices, so a source may borrow it from
+/// memory it does not own, such as a memory map or a static buffer.
+pub trait Srs {
+    /// Loads at least `num_points` G1 points together with the G2 point.
+    ///
+    /// # Arguments
+    /// * `num_points` - Number of G1 points required by the circuit.
+    ///
+    /// # Returns
+    /// * `Result<(), SrsError>` - Returns an empty result if successful, otherwise returns an `SrsError`.
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError>;
+
+    /// Returns the loaded G1 data.
+    fn g1_data(&self) -> &[u8];
+
+    /// Returns the loaded G2 data.
+    fn g2_data(&self) -> &[u8];
+
+    /// Returns the number of loaded G1 points.
+    fn num_points(&self) -> u32;
+
+    /// Frees the loaded G1 and G2 data once the backend holds its own copy of it.
+    ///
+    /// The number of loaded points is kept, and a later [`Srs::load_data`] loads the data again.
+    fn clear(&mut self);
+
+    /// Returns the largest number of points the source can load, if it is known without loading
+    /// them.
+    ///
+    /// Circuits needing more points are rejected before anything is downloaded or read. The
+    /// default implementation returns `None`, leaving [`Srs::load_data`] to fail.
+    fn max_points(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Lets a source chosen at runtime, such as a `Box<dyn Srs>` built from configuration, be passed
+/// wherever a generic source is expected.
+impl<S: Srs + ?Sized> Srs for Box<S> {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        (**self).load_data(num_points)
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        (**self).g1_data()
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        (**self).g2_data()
+    }
+
+    fn num_points(&self) -> u32 {
+        (**self).num_points()
+    }
+
+    fn clear(&mut self) {
+        (**self).clear()
+    }
+
+    fn max_points(&self) -> Option<u32> {
+        (**self).max_points()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SrsError {
+    #[error(
+        "SRS provides {available} points but {requested} were requested{}",
+        path.as_ref().map_or(String::new(), |path| format!(" from {}", path.display()))
+    )]
+    InsufficientPoints { available: u32, requested: u32, path: Option<PathBuf> },
+    #[error("SRS file {} is {len} bytes and ends before the G2 point at offset {g2_offset}", path.display())]
+    FileTooSmallForG2 { path: PathBuf, len: u64, g2_offset: u64 },
+    #[error("SRS download failed: {0}")]
+    Download(reqwest::Error),
+    #[error(
+        "SRS server answered {status}{} for {url}: {body}",
+        content_type.as_ref().map_or(String::new(), |content_type| format!(" with {}", content_type))
+    )]
+    UnexpectedResponse {
+        status: reqwest::StatusCode,
+        url: String,
+        content_type: Option<String>,
+        body: String,
+    },
+    #[error("SRS I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("SRS download cancelled")]
+    Cancelled,
+    #[error("Cannot read SRS file {}: {source}", path.display())]
+    File { path: PathBuf, source: std::io::Error },
+    #[error("Invalid SRS transcript: {0}")]
+    InvalidTranscript(String),
+    #[error("Invalid SRS configuration in {var}: {reason}")]
+    InvalidEnvVar { var: &'static str, reason: String },
+    #[error("SRS server ignores range requests and reaching the requested range takes {required} bytes, over the budget of {budget}")]
+    RangeNotSupported { required: u64, budget: u64 },
+    #[error(
+        "SRS server answered {received} for {url} instead of the requested bytes {start}-{end}"
+    )]
+    RangeMismatch { url: String, start: u64, end: u64, received: String },
+    #[error("SRS transcript at {url} kept changing while it was downloaded")]
+    TranscriptChanged { url: String },
+    #[error(
+        "SHA-256 of the first {num_points} SRS points is {}, expected {}",
+        hex(actual),
+        hex(expected)
+    )]
+    ChecksumMismatch { num_points: u32, expected: [u8; 32], actual: [u8; 32] },
+}
+
+impl From<reqwest::Error> for SrsError {
+    /// Drops the query string of the URL named by the error, which holds the credentials of a
+    /// presigned URL.
+    fn from(mut error: reqwest::Error) -> Self {
+        if let Some(url) = error.url_mut() {
+            url.set_query(None);
+            url.set_fragment(None);
+        }
+        SrsError::Download(error)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Checks that loaded SRS data looks like a bn254 transcript.
+///
+/// The first G1 point must be the bn254 generator and the G2 point must be a valid point of the
+/// G2 subgroup. This catches transcripts read with the wrong endianness, from the wrong offset or
+/// for the wrong curve, which would otherwise only surface as proofs failing to verify.
+///
+/// # Arguments
+/// * `g1_data` - A byte slice containing the G1 data.
+/// * `g2_data` - A byte slice containing the G2 data.
+///
+/// # Returns
+/// * `Result<(), SrsError>` - Returns an empty result if the data passes the checks, otherwise returns an `SrsError::InvalidTranscript`.
+pub fn check_transcript(g1_data: &[u8], g2_data: &[u8]) -> Result<(), SrsError> {
+    let first_point = g1_data.get(..64).ok_or_else(|| {
+        SrsError::InvalidTranscript(format!(
+            "generator check failed: expected at least 64 bytes of G1 data, found {}",
+            g1_data.len()
+        ))
+    })?;
+    match (read_fq(&first_point[..32]), read_fq(&first_point[32..])) {
+        (Some(x), Some(y)) if G1Affine::new_unchecked(x, y) == G1Affine::generator() => {}
+        (Some(x), Some(y)) => {
+            return Err(SrsError::InvalidTranscript(format!(
+                "generator check failed: the first G1 point is ({}, {}) instead of the bn254 generator",
+                x, y
+            )))
+        }
+        _ => {
+            return Err(SrsError::InvalidTranscript(
+                "generator check failed: the first G1 point has coordinates outside the bn254 base field, the transcript may use a different endianness or offset".to_string(),
+            ))
+        }
+    }
+
+    if g2_data.len() != 128 {
+        return Err(SrsError::InvalidTranscript(format!(
+            "expected 128 bytes of G2 data, found {}",
+            g2_data.len()
+        )));
+    }
+    let coordinates: Option<Vec<Fq>> = g2_data.chunks(32).map(read_fq).collect();
+    let point = coordinates.map(|coordinates| {
+        G2Affine::new_unchecked(
+            Fq2::new(coordinates[0], coordinates[1]),
+            Fq2::new(coordinates[2], coordinates[3]),
+        )
+    });
+    match point {
+        Some(point) if point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve() => {
+            Ok(())
+        }
+        _ => Err(SrsError::InvalidTranscript(
+            "the G2 data is not a point of the bn254 G2 subgroup".to_string(),
+        )),
+    }
+}
+
+/// Returns whether the first 64 bytes of `g1_data` encode the bn254 generator.
+pub(crate) fn starts_with_generator(g1_data: &[u8]) -> bool {
+    match g1_data.get(..64).map(|point| (read_fq(&point[..32]), read_fq(&point[32..]))) {
+        Some((Some(x), Some(y))) => G1Affine::new_unchecked(x, y) == G1Affine::generator(),
+        _ => false,
+    }
+}
+
+/// Reads a base field element stored as four big-endian 64-bit limbs, least significant limb
+/// first. Returns `None` if the value is not smaller than the field modulus.
+fn read_fq(bytes: &[u8]) -> Option<Fq> {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().ok()?);
+    }
+    Fq::from_bigint(BigInt::new(limbs))
+}
+
+/// The G2 point `[x]₂` of the Aztec ignition transcript, as the `x.c0`, `x.c1`, `y.c0` and `y.c1`
+/// coordinates of four 64-bit limbs each, least significant limb first.
+const IGNITION_G2_LIMBS: [[u64; 4]; 4] = [
+    [0x7e231fec938883b0, 0x9f5944073b32078b, 0xbc89b5b398b5974e, 0x0118c4d5b837bcc2],
+    [0x4efe30fac09383c1, 0xea51d87a358e038b, 0xe7ff4e580791dee8, 0x260e01b251f6f1c7],
+    [0x854a87d4dacc5e55, 0x11e6dd3f96e6cea2, 0x56475b4214e5615e, 0x22febda3c0c0632a],
+    [0xee413c80da6a5fe4, 0x9cf2a04641f99ba4, 0xd25156c1bb9a7285, 0x04fc6369f7110fe3],
+];
+
+/// The bn254 generator `(1, 2)`, which is the first G1 point of the ignition transcript.
+const GENERATOR_LIMBS: [[u64; 4]; 2] = [[1, 0, 0, 0], [2, 0, 0, 0]];
+
+/// Returns the first G1 point and the G2 point of the ignition transcript in transcript encoding.
+///
+/// Verification only needs these, so verifiers can initialize the backend without downloading
+/// the transcript.
+///
+/// # Returns
+/// * `(Vec<u8>, Vec<u8>)` - The 64 bytes of G1 data and the 128 bytes of G2 data.
+pub fn ignition_verifier_data() -> (Vec<u8>, Vec<u8>) {
+    let encode = |coordinates: &[[u64; 4]]| -> Vec<u8> {
+        coordinates.iter().flatten().flat_map(|limb| limb.to_be_bytes()).collect()
+    };
+    (encode(&GENERATOR_LIMBS), encode(&IGNITION_G2_LIMBS))
+}
 
 /// Initializes the SRS inside the C++ backend.
 ///
@@ -14,19 +290,58 @@ pub mod netsrs;
 /// * `g2_point_buf` - A byte slice containing the G2 data.
 ///
 /// # Returns
-/// * `Result<(), BackendError>` - Returns an empty result if successful, otherwise returns a `BackendError`.
+/// * `Result<(), BackendError>` - Returns an empty result if successful, otherwise returns a
+///   `BackendError`, also when `num_points` is zero, `points_buf` holds fewer points or
+///   `g2_point_buf` is shorter than a G2 point.
 pub fn srs_init(
     points_buf: &[u8],
     num_points: u32,
     g2_point_buf: &[u8],
 ) -> Result<(), BackendError> {
+    // The backend reads `num_points` points and a G2 point without knowing the buffer lengths.
+    if num_points == 0 || points_buf.len() < num_points as usize * 64 {
+        return Err(BackendError::BindingCallError(format!(
+            "SRS of {} bytes does not hold {} points",
+            points_buf.len(),
+            num_points
+        )));
+    }
+    if g2_point_buf.len() < Curve::Bn254.g2_size() {
+        return Err(BackendError::BindingCallError(format!(
+            "G2 point of {} bytes is shorter than {} bytes",
+            g2_point_buf.len(),
+            Curve::Bn254.g2_size()
+        )));
+    }
     let error_msg_ptr =
         unsafe { rust_srs_init_srs(points_buf.as_ptr(), &num_points, g2_point_buf.as_ptr()) };
     if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
+    Ok(())
+}
+
+/// Initializes the Grumpkin SRS inside the C++ backend.
+///
+/// # Arguments
+/// * `points_buf` - A byte slice containing the Grumpkin G1 data.
+/// * `num_points` - Number of points used for the G1 data.
+///
+/// # Returns
+/// * `Result<(), BackendError>` - Returns an empty result if successful, otherwise returns a
+///   `BackendError`, also when `num_points` is zero or `points_buf` holds fewer points.
+pub fn srs_init_grumpkin(points_buf: &[u8], num_points: u32) -> Result<(), BackendError> {
+    // The backend reads `num_points` points from the buffer without knowing its length.
+    if num_points == 0 || points_buf.len() < num_points as usize * 64 {
         return Err(BackendError::BindingCallError(format!(
-            "C++ error: {}",
-            unsafe { parse_c_str(error_msg_ptr) }.unwrap_or("Parsing c_str failed".to_string())
+            "Grumpkin SRS of {} bytes does not hold {} points",
+            points_buf.len(),
+            num_points
         )));
     }
+    let error_msg_ptr = unsafe { rust_srs_init_grumpkin_srs(points_buf.as_ptr(), &num_points) };
+    if !error_msg_ptr.is_null() {
+        return Err(unsafe { binding_error(error_msg_ptr) });
+    }
     Ok(())
 }
@@ -1,11 +1,141 @@
-use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, RANGE};
+use std::{
+    fmt,
+    io::{self, Read},
+    ops::Range,
+    sync::Arc,
+    thread,
+};
 
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{
+    HeaderMap, CONTENT_RANGE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RANGE,
+};
+use reqwest::StatusCode;
+
+use super::{
+    cancel::{CancellableRead, CancellationToken},
+    checksum::SrsChecksum,
+    http::{check_response, default_client, redact_url},
+    source::url_from_env,
+    Curve, Srs, SrsError,
+};
+
+pub const DEFAULT_SRS_URL: &str =
+    "https://aztec-ignition.s3.amazonaws.com/MAIN%20IGNITION/monomial/transcript00.dat";
+
+/// Number of concurrent range requests the G1 data is split into by default.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Number of attempts made for each range request on a mirror before moving to the next one.
+const RANGE_ATTEMPTS: usize = 3;
+
+/// Number of times the G1 points are downloaded when the transcript changes during the download.
+const TRANSCRIPT_ATTEMPTS: usize = 3;
+
+/// Number of bytes a server ignoring range requests may stream before the download fails by
+/// default, enough to read the G2 point at the end of the ignition `transcript00.dat`.
+pub const DEFAULT_FULL_BODY_BUDGET: u64 = 512 * 1024 * 1024;
+
+pub(super) const G1_START: u64 = 28;
+/// Number of G1 points of the ignition `transcript00.dat`, after which its G2 points are stored.
+pub const IGNITION_G1_POINTS: u32 = 5040001;
+pub(super) const G2_START: u64 = G1_START + IGNITION_G1_POINTS as u64 * 64;
+
+/// Identifies the version of the transcript served by the remote server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validator {
+    /// The `ETag` response header.
+    ETag(String),
+    /// The `Last-Modified` response header, used when the server sends no `ETag`.
+    LastModified(String),
+}
+
+impl Validator {
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        if let Some(etag) = header(ETAG) {
+            Some(Validator::ETag(etag.to_string()))
+        } else {
+            header(LAST_MODIFIED).map(|date| Validator::LastModified(date.to_string()))
+        }
+    }
+}
+
+/// Where a mirror of the transcript is downloaded from.
+#[derive(Clone)]
+enum UrlSource {
+    /// The same URL for every request.
+    Fixed(String),
+    /// A URL generated for each requested byte range, see [`NetSrs::with_url_provider`].
+    Provider(Arc<dyn Fn(Range<u64>) -> String + Send + Sync>),
+}
+
+impl UrlSource {
+    fn url(&self, range: Range<u64>) -> String {
+        match self {
+            UrlSource::Fixed(url) => url.clone(),
+            UrlSource::Provider(provider) => provider(range),
+        }
+    }
+}
+
+impl fmt::Debug for UrlSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlSource::Fixed(url) => f.debug_tuple("Fixed").field(&redact_url(url)).finish(),
+            UrlSource::Provider(_) => f.write_str("Provider"),
+        }
+    }
+}
+
+/// A successful response to a range request, whose body holds exactly the requested range.
+struct RangeResponse {
+    status: StatusCode,
+    validator: Option<Validator>,
+    body: Vec<u8>,
+    /// Index of the mirror that answered, as validators are only comparable for the same mirror.
+    mirror: usize,
+}
+
+/// Downloads the SRS from the Aztec ignition transcript using HTTP range requests.
+///
+/// The default client honors the `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` environment variables.
+/// Use [`NetSrs::with_client`] to supply a client with custom proxy or TLS configuration, and
+/// [`NetSrs::with_headers`] for servers requiring authentication.
+///
+/// The G1 data is split into [`DEFAULT_CONCURRENCY`] chunks that are fetched in parallel, see
+/// [`NetSrs::with_concurrency`]. Servers that ignore range requests are read sequentially, see
+/// [`NetSrs::with_full_body_budget`]. Mirrors are tried in order for every range, see
+/// [`NetSrs::with_mirrors`]. A `206 Partial Content` response whose `Content-Range`,
+/// `Content-Length` or body differs from the requested range, as served by a misconfigured caching
+/// proxy, fails the mirror with [`SrsError::RangeMismatch`]. A chunk whose `ETag` or
+/// `Last-Modified` shows it was read from a transcript replaced during the download restarts it.
+///
+/// URLs are requested as given, including their query string, and each request may use its own
+/// URL, see [`NetSrs::with_url_provider`]. Query strings are removed from the URLs named in errors
+/// and logs, as they hold the credentials of presigned URLs.
 #[derive(Debug)]
 pub struct NetSrs {
     pub data: Vec<u8>,
     pub g2_data: Vec<u8>,
     pub num_points: u32,
+    urls: Vec<UrlSource>,
+    g2_url: Option<String>,
+    max_points: Option<u32>,
+    client: Client,
+    headers: HeaderMap,
+    concurrency: usize,
+    full_body_budget: u64,
+    curve: Curve,
+    checksum: Option<SrsChecksum>,
+    validator: Option<Validator>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl Default for NetSrs {
+    fn default() -> Self {
+        Self::with_client(default_client())
+    }
 }
 
 impl NetSrs {
@@ -13,58 +143,566 @@ impl NetSrs {
     ///
     /// # Arguments
     /// * `num_points` - Number of points required for G1 data.
-    pub fn new(num_points: u32) -> Self {
+    pub fn new(num_points: u32) -> Result<Self, SrsError> {
+        let mut srs = NetSrs::default();
+        srs.load_data(num_points)?;
+        Ok(srs)
+    }
+
+    /// Creates a new NetSrs instance that downloads the SRS data using the given client.
+    ///
+    /// The client is reused for every request of the instance, the G1 chunks, the G2 point and
+    /// their retries, so that they share its connection pool and TLS sessions, and it can carry a
+    /// proxy, custom root certificates or a user agent. Its redirect policy applies instead of the default limit of
+    /// [`MAX_REDIRECTS`](super::http::MAX_REDIRECTS).
+    ///
+    /// # Arguments
+    /// * `client` - A fully configured blocking HTTP client.
+    pub fn with_client(client: Client) -> Self {
         NetSrs {
-            num_points,
-            data: Self::download_g1_data(num_points),
-            g2_data: Self::download_g2_data(),
+            data: Vec::new(),
+            g2_data: Vec::new(),
+            num_points: 0,
+            urls: vec![UrlSource::Fixed(DEFAULT_SRS_URL.to_string())],
+            g2_url: None,
+            max_points: None,
+            client,
+            headers: HeaderMap::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            full_body_budget: DEFAULT_FULL_BODY_BUDGET,
+            curve: Curve::Bn254,
+            checksum: None,
+            validator: None,
+            cancellation: None,
         }
     }
 
-    /// Downloads the G1 data from Noir Cloud based on the specified number of points.
+    /// Creates a new NetSrs instance that downloads the SRS data from the mirror set in the
+    /// `NOIR_RS_SRS_URL` environment variable, or from [`DEFAULT_SRS_URL`] when it is unset or
+    /// empty.
+    ///
+    /// # Returns
+    /// * `Result<NetSrs, SrsError>` - Returns the instance, or an `SrsError::InvalidEnvVar` if the
+    ///   URL cannot be parsed.
+    pub fn from_env() -> Result<Self, SrsError> {
+        Ok(match url_from_env()? {
+            Some(url) => Self::with_url(&url),
+            None => Self::default(),
+        })
+    }
+
+    /// Creates a new NetSrs instance that downloads the SRS data from the given URL.
+    ///
+    /// The URL must serve a transcript with the same layout as the ignition `transcript00.dat`.
     ///
     /// # Arguments
-    /// * `num_points` - Number of points required for G1 data.
+    /// * `url` - URL of the transcript file.
+    pub fn with_url(url: &str) -> Self {
+        Self::with_mirrors([url])
+    }
+
+    /// Creates a new NetSrs instance that downloads the SRS data from the first of the given URLs
+    /// able to serve each range.
+    ///
+    /// Server errors and timeouts are retried on the same mirror, while connection failures,
+    /// client errors such as `404 Not Found`, responses for another range and transcripts too
+    /// short for the range move on to the next mirror. The mirrors must serve the same transcript.
+    ///
+    /// # Arguments
+    /// * `urls` - URLs of the transcript file, in order of preference. An empty list falls back to
+    ///   [`DEFAULT_SRS_URL`].
+    pub fn with_mirrors<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let urls: Vec<UrlSource> =
+            urls.into_iter().map(|url| UrlSource::Fixed(url.into())).collect();
+        if urls.is_empty() {
+            return NetSrs::default();
+        }
+        NetSrs { urls, ..Default::default() }
+    }
+
+    /// Creates a new NetSrs instance that asks `provider` for the URL of every request, such as a
+    /// presigned URL that a server only accepts for a single ranged request.
+    ///
+    /// # Arguments
+    /// * `provider` - Returns the URL of the transcript to request the given byte range from, the
+    ///   end of the range being exclusive.
+    pub fn with_url_provider(
+        provider: impl Fn(Range<u64>) -> String + Send + Sync + 'static,
+    ) -> Self {
+        NetSrs { urls: vec![UrlSource::Provider(Arc::new(provider))], ..Default::default() }
+    }
+
+    /// Creates a new NetSrs instance that downloads the SRS data from the given URL, sending the
+    /// given headers with every request, e.g. an `Authorization` bearer token.
+    ///
+    /// The `Range`, `If-None-Match` and `If-Modified-Since` headers are set by NetSrs for each
+    /// request and are dropped from `headers`.
+    ///
+    /// # Arguments
+    /// * `url` - URL of the transcript file.
+    /// * `headers` - Headers added to every request.
+    pub fn with_headers(url: &str, mut headers: HeaderMap) -> Self {
+        for name in [RANGE, IF_NONE_MATCH, IF_MODIFIED_SINCE] {
+            headers.remove(name);
+        }
+        NetSrs { headers, ..Self::with_url(url) }
+    }
+
+    /// Sets the number of range requests the G1 data is split into and fetched concurrently.
+    ///
+    /// # Arguments
+    /// * `concurrency` - Number of concurrent requests, a value of `0` is treated as `1`.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets how many bytes a server answering range requests with the whole transcript may stream.
+    ///
+    /// The bytes before the requested range are read and discarded as they arrive, so the budget
+    /// bounds the transfer, not memory use. Reaching a range past the budget fails with
+    /// [`SrsError::RangeNotSupported`].
+    ///
+    /// # Arguments
+    /// * `budget` - Maximum number of bytes streamed for a single range.
+    pub fn with_full_body_budget(mut self, budget: u64) -> Self {
+        self.full_body_budget = budget;
+        self
+    }
+
+    /// Downloads the G2 point from its own URL instead of the transcript, for setups whose G2
+    /// point is not stored where the ignition layout places it, such as the result of a custom
+    /// ceremony.
+    ///
+    /// Only the G1 points are requested from the mirrors. The headers set with
+    /// [`NetSrs::with_headers`] are sent with the request for the G2 point too.
+    ///
+    /// # Arguments
+    /// * `url` - URL of a file holding exactly the 128 bytes of the G2 point.
+    pub fn with_g2_url(mut self, url: &str) -> Self {
+        self.g2_url = Some(url.to_string());
+        self
+    }
+
+    /// Sets the number of G1 points the mirrors serve, for transcripts holding fewer points than
+    /// the [`IGNITION_G1_POINTS`] of the ignition transcript, such as a trimmed copy.
+    ///
+    /// Circuits needing more points are rejected before anything is downloaded, see
+    /// [`Srs::max_points`].
+    ///
+    /// # Arguments
+    /// * `max_points` - Number of G1 points of the transcript.
+    pub fn with_max_points(mut self, max_points: u32) -> Self {
+        self.max_points = Some(max_points);
+        self
+    }
+
+    /// Sets the curve of the transcript. A [`Curve::Grumpkin`] transcript has no G2 point, so only
+    /// the G1 data is downloaded.
+    ///
+    /// # Arguments
+    /// * `curve` - The curve the transcript is defined over.
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Verifies the downloaded data against a pinned checksum, failing with
+    /// [`SrsError::ChecksumMismatch`] when a mirror serves corrupted or tampered data.
+    ///
+    /// Loads download at least the points covered by the checksum, see [`SrsChecksum`].
+    ///
+    /// # Arguments
+    /// * `checksum` - The expected digest of the transcript.
+    pub fn with_checksum(mut self, checksum: SrsChecksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Aborts downloads with [`SrsError::Cancelled`] once the token is cancelled or its deadline
+    /// passes.
+    ///
+    /// The token is checked before every request and between the reads of a response body, and
+    /// requests time out at the deadline, so a stalled server does not hold the download past it.
+    ///
+    /// # Arguments
+    /// * `token` - The token to watch.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Returns the validator of the transcript the loaded data was downloaded from, if the server
+    /// sent an `ETag` or `Last-Modified` header.
+    pub fn validator(&self) -> Option<&Validator> {
+        self.validator.as_ref()
+    }
+
+    /// Checks whether the server still serves the transcript identified by `validator`.
+    ///
+    /// Issues a conditional request for the first G1 point, so only a few bytes are transferred
+    /// when the transcript has changed. The first mirror that answers decides.
+    ///
+    /// # Arguments
+    /// * `validator` - Validator of a previously downloaded transcript.
+    ///
+    /// # Returns
+    /// * `Result<bool, SrsError>` - `true` if the server answered `304 Not Modified`.
+    pub fn is_current(&self, validator: &Validator) -> Result<bool, SrsError> {
+        let (name, value) = match validator {
+            Validator::ETag(etag) => (IF_NONE_MATCH, etag),
+            Validator::LastModified(date) => (IF_MODIFIED_SINCE, date),
+        };
+        self.with_failover(G1_START..G1_START + 64, |_, url| {
+            let response = self
+                .client
+                .get(url)
+                .headers(self.headers.clone())
+                .header(RANGE, format!("bytes={}-{}", G1_START, G1_START + 63))
+                .header(name.clone(), value.as_str())
+                .send()?;
+            let expected = [StatusCode::OK, StatusCode::PARTIAL_CONTENT, StatusCode::NOT_MODIFIED];
+            Ok(check_response(response, &expected)?.status() == StatusCode::NOT_MODIFIED)
+        })
+    }
+
+    /// Downloads `num_points` G1 points starting at the point of index `first_point`.
+    ///
+    /// The first point is fetched on its own to find out whether the server honors range
+    /// requests. If it does, the remaining points are split into chunks downloaded in parallel and
+    /// reassembled in order, otherwise they are read from a single full response body.
+    ///
+    /// A chunk served by the same mirror as the first point with another `ETag` or
+    /// `Last-Modified` was read from a newer transcript, so the points are downloaded again, up to
+    /// [`TRANSCRIPT_ATTEMPTS`] times before failing with [`SrsError::TranscriptChanged`].
+    ///
+    /// # Arguments
+    /// * `first_point` - Index of the first point to download.
+    /// * `num_points` - Number of points to download, at least one point is always downloaded.
     ///
     /// # Returns
-    /// * `Vec<u8>` - A byte vector containing the G1 data.
-    fn download_g1_data(num_points: u32) -> Vec<u8> {
-        const G1_START: u32 = 28;
-        let g1_end: u32 = G1_START + num_points * 64 - 1;
+    /// * `Result<(Vec<u8>, Option<Validator>), SrsError>` - A byte vector containing the G1 data and
+    ///   the validator of the transcript it was read from.
+    pub(super) fn download_g1_points(
+        &self,
+        first_point: u32,
+        num_points: u32,
+    ) -> Result<(Vec<u8>, Option<Validator>), SrsError> {
+        let start = G1_START + first_point as u64 * 64;
+        let g1_len = num_points as u64 * 64;
+        let mut attempt = 1;
+        loop {
+            let probe = self.download_range(start, 64)?;
+            if num_points <= 1 {
+                return Ok((probe.body, probe.validator));
+            }
+            if probe.status != StatusCode::PARTIAL_CONTENT {
+                let response = self.download_range(start, g1_len)?;
+                return Ok((response.body, response.validator));
+            }
+
+            let chunks = chunk_ranges(start + 64, g1_len - 64, self.concurrency);
+            let downloaded = thread::scope(|scope| {
+                let handles: Vec<_> = chunks
+                    .iter()
+                    .map(|&(start, len)| scope.spawn(move || self.download_range(start, len)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("SRS download thread panicked"))
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
 
-        let mut headers = HeaderMap::new();
-        headers.insert(RANGE, format!("bytes={}-{}", G1_START, g1_end).parse().unwrap());
+            let changed = downloaded
+                .iter()
+                .any(|chunk| chunk.mirror == probe.mirror && chunk.validator != probe.validator);
+            if changed {
+                if attempt < TRANSCRIPT_ATTEMPTS {
+                    attempt += 1;
+                    continue;
+                }
+                let url = self.urls[probe.mirror].url(start..start + 64);
+                return Err(SrsError::TranscriptChanged { url: redact_url(&url) });
+            }
 
-        let response = Client::new()
-            .get(
-                "https://aztec-ignition.s3.amazonaws.com/MAIN%20IGNITION/monomial/transcript00.dat",
-            )
-            .headers(headers)
-            .send()
-            .unwrap();
+            let mut data = Vec::with_capacity(g1_len as usize);
+            data.extend_from_slice(&probe.body);
+            for chunk in downloaded {
+                data.extend_from_slice(&chunk.body);
+            }
+            return Ok((data, probe.validator));
+        }
+    }
 
-        response.bytes().unwrap().to_vec()
+    /// Downloads the G2 data from Noir Cloud, or from the URL set with [`NetSrs::with_g2_url`].
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>, SrsError>` - A byte vector containing the G2 data.
+    pub(super) fn download_g2_data(&self) -> Result<Vec<u8>, SrsError> {
+        match &self.g2_url {
+            Some(url) => self.download_g2_file(url),
+            None => Ok(self.download_range(G2_START, 128)?.body),
+        }
     }
 
-    /// Downloads the G2 data from Noir Cloud.
+    /// Downloads a file holding only the G2 point, checking it holds exactly its 128 bytes.
+    fn download_g2_file(&self, url: &str) -> Result<Vec<u8>, SrsError> {
+        self.check_cancelled()?;
+        let mut request = self.client.get(url).headers(self.headers.clone());
+        if let Some(remaining) = self.cancellation.as_ref().and_then(CancellationToken::remaining) {
+            request = request.timeout(remaining);
+        }
+        let response = check_response(request.send()?, &[StatusCode::OK])?;
+        let response = CancellableRead { inner: response, token: self.cancellation.as_ref() };
+        // Reading a single byte past the point is enough to reject a longer file.
+        let mut g2_data = Vec::with_capacity(129);
+        response.take(129).read_to_end(&mut g2_data)?;
+        match g2_data.len() {
+            128 => Ok(g2_data),
+            129 => Err(SrsError::InvalidTranscript(format!(
+                "{} is longer than a single 128-byte G2 point",
+                redact_url(url)
+            ))),
+            len => Err(SrsError::InvalidTranscript(format!(
+                "{} is {} bytes instead of a single 128-byte G2 point",
+                redact_url(url),
+                len
+            ))),
+        }
+    }
+
+    /// Runs `request` against each mirror in turn until one succeeds.
+    ///
+    /// # Arguments
+    /// * `range` - The byte range requested, passed to the URL providers.
+    /// * `request` - Sends the request to the mirror of the given index, at the given URL.
     ///
     /// # Returns
-    /// * `Vec<u8>` - A byte vector containing the G2 data.
-    fn download_g2_data() -> Vec<u8> {
-        const G2_START: usize = 28 + 5040001 * 64;
-        const G2_END: usize = G2_START + 128 - 1;
-
-        let mut headers = HeaderMap::new();
-        headers.insert(RANGE, format!("bytes={}-{}", G2_START, G2_END).parse().unwrap());
-
-        let response = Client::new()
-            .get(
-                "https://aztec-ignition.s3.amazonaws.com/MAIN%20IGNITION/monomial/transcript00.dat",
-            )
-            .headers(headers)
-            .send()
-            .unwrap();
-
-        response.bytes().unwrap().to_vec()
+    /// * `Result<T, SrsError>` - The first successful result, or the error of the last mirror.
+    fn with_failover<T>(
+        &self,
+        range: Range<u64>,
+        request: impl Fn(usize, &str) -> Result<T, SrsError>,
+    ) -> Result<T, SrsError> {
+        let mut result = Err(SrsError::InvalidTranscript(String::from("no SRS URL configured")));
+        for (mirror, source) in self.urls.iter().enumerate() {
+            result = request(mirror, &source.url(range.clone()));
+            if matches!(result, Ok(_) | Err(SrsError::Cancelled)) {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Downloads `len` bytes starting at `start`, retrying transient failures and falling back to
+    /// the next mirror otherwise.
+    ///
+    /// # Returns
+    /// * `Result<RangeResponse, SrsError>` - The response status, validator and body.
+    fn download_range(&self, start: u64, len: u64) -> Result<RangeResponse, SrsError> {
+        self.with_failover(start..start + len, |mirror, url| {
+            let mut attempt = 1;
+            loop {
+                self.check_cancelled()?;
+                match self.try_download_range(url, mirror, start, len) {
+                    Ok(response) => return Ok(response),
+                    // Errors caused by the cancellation, such as the request timing out at the
+                    // deadline, are reported as such.
+                    Err(_) if self.is_cancelled() => return Err(SrsError::Cancelled),
+                    Err(error) if is_transient(&error) && attempt < RANGE_ATTEMPTS => attempt += 1,
+                    Err(error) => return Err(error),
+                }
+            }
+        })
+    }
+
+    fn try_download_range(
+        &self,
+        url: &str,
+        mirror: usize,
+        start: u64,
+        len: u64,
+    ) -> Result<RangeResponse, SrsError> {
+        let mut request = self
+            .client
+            .get(url)
+            .headers(self.headers.clone())
+            .header(RANGE, format!("bytes={}-{}", start, start + len - 1));
+        if let Some(remaining) = self.cancellation.as_ref().and_then(CancellationToken::remaining) {
+            request = request.timeout(remaining);
+        }
+        let response =
+            check_response(request.send()?, &[StatusCode::OK, StatusCode::PARTIAL_CONTENT])?;
+        let status = response.status();
+        if status == StatusCode::PARTIAL_CONTENT {
+            check_content_range(&response, url, start, len)?;
+        }
+        let validator = Validator::from_headers(response.headers());
+        let mut response = CancellableRead { inner: response, token: self.cancellation.as_ref() };
+        let body = if status == StatusCode::PARTIAL_CONTENT {
+            let mut body = Vec::with_capacity(len as usize);
+            response.read_to_end(&mut body)?;
+            if body.len() as u64 != len {
+                return Err(range_mismatch(url, start, len, format!("{} bytes", body.len())));
+            }
+            body
+        } else {
+            self.read_full_body(response, start, len)?
+        };
+        Ok(RangeResponse { status, validator, body, mirror })
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellation.as_ref().map_or(false, CancellationToken::is_cancelled)
+    }
+
+    fn check_cancelled(&self) -> Result<(), SrsError> {
+        match self.is_cancelled() {
+            true => Err(SrsError::Cancelled),
+            false => Ok(()),
+        }
+    }
+
+    /// Reads `len` bytes starting at `start` from a response carrying the whole transcript.
+    ///
+    /// The bytes before `start` are discarded as they are read, and the response is dropped once
+    /// the range is read, which closes the connection instead of transferring the rest of it.
+    fn read_full_body(
+        &self,
+        response: impl Read,
+        start: u64,
+        len: u64,
+    ) -> Result<Vec<u8>, SrsError> {
+        let required = start + len;
+        if required > self.full_body_budget {
+            return Err(SrsError::RangeNotSupported { required, budget: self.full_body_budget });
+        }
+
+        let mut response = response.take(required);
+        let skipped = io::copy(&mut (&mut response).take(start), &mut io::sink())?;
+        let mut body = Vec::with_capacity(len as usize);
+        response.read_to_end(&mut body)?;
+        if (body.len() as u64) < len {
+            return Err(SrsError::InsufficientPoints {
+                available: ((skipped + body.len() as u64).saturating_sub(G1_START) / 64) as u32,
+                requested: ((required - G1_START) / 64) as u32,
+                path: None,
+            });
+        }
+        Ok(body)
+    }
+}
+
+/// Returns whether a failed range request may succeed when retried on the same mirror.
+///
+/// Server errors, `408 Request Timeout`, `429 Too Many Requests`, timeouts and connections
+/// dropped while reading the body are transient. Connection failures, other client errors and
+/// unexpected successful responses are not, the mirror is unreachable or does not serve the
+/// transcript.
+fn is_transient(error: &SrsError) -> bool {
+    match error {
+        SrsError::Download(error) => !error.is_connect() && (error.is_timeout() || error.is_body()),
+        SrsError::UnexpectedResponse { status, .. } => {
+            status.is_server_error()
+                || *status == StatusCode::REQUEST_TIMEOUT
+                || *status == StatusCode::TOO_MANY_REQUESTS
+        }
+        SrsError::Io(_) => true,
+        _ => false,
+    }
+}
+
+/// Checks that a `206 Partial Content` response announces exactly the requested range, which a
+/// misconfigured caching proxy may replace with another range or a truncated one.
+fn check_content_range(
+    response: &Response,
+    url: &str,
+    start: u64,
+    len: u64,
+) -> Result<(), SrsError> {
+    let end = start + len - 1;
+    let content_range = response.headers().get(CONTENT_RANGE).and_then(|value| value.to_str().ok());
+    if content_range.and_then(parse_content_range) != Some((start, end)) {
+        let received = content_range.map_or_else(
+            || String::from("no Content-Range"),
+            |content_range| format!("Content-Range {}", content_range),
+        );
+        return Err(range_mismatch(url, start, len, received));
+    }
+    match response.content_length() {
+        Some(content_length) if content_length != len => {
+            Err(range_mismatch(url, start, len, format!("Content-Length {}", content_length)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parses the first and last byte of a `Content-Range` header, such as `bytes 28-91/322560220`.
+fn parse_content_range(content_range: &str) -> Option<(u64, u64)> {
+    let (range, _) = content_range.strip_prefix("bytes ")?.split_once('/')?;
+    let (first, last) = range.split_once('-')?;
+    Some((first.trim().parse().ok()?, last.trim().parse().ok()?))
+}
+
+fn range_mismatch(url: &str, start: u64, len: u64, received: String) -> SrsError {
+    SrsError::RangeMismatch { url: redact_url(url), start, end: start + len - 1, received }
+}
+
+/// Splits `len` bytes starting at `start` into at most `count` chunks aligned to G1 points.
+fn chunk_ranges(start: u64, len: u64, count: usize) -> Vec<(u64, u64)> {
+    let points = len / 64;
+    let chunk_points = (points + count as u64 - 1) / count as u64;
+    (0..points)
+        .step_by(chunk_points as usize)
+        .map(|point| (start + point * 64, chunk_points.min(points - point) * 64))
+        .collect()
+}
+
+impl Srs for NetSrs {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        let pinned_points = self.checksum.map_or(0, |checksum| checksum.num_points);
+        let (mut data, validator) = self.download_g1_points(0, num_points.max(pinned_points))?;
+        let g2_data = match self.curve {
+            Curve::Bn254 => self.download_g2_data()?,
+            Curve::Grumpkin => Vec::new(),
+        };
+        if let Some(checksum) = &self.checksum {
+            checksum.verify(&data, &g2_data)?;
+        }
+        data.truncate((num_points as usize).max(1) * 64);
+
+        (self.data, self.g2_data, self.validator) = (data, g2_data, validator);
+        self.num_points = num_points;
+        Ok(())
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        &self.g2_data
+    }
+
+    fn num_points(&self) -> u32 {
+        self.num_points
+    }
+
+    fn clear(&mut self) {
+        self.data = Vec::new();
+        self.g2_data = Vec::new();
+    }
+
+    /// Returns the points set with [`NetSrs::with_max_points`] or, for bn254, the
+    /// [`IGNITION_G1_POINTS`] preceding the G2 point of the ignition layout.
+    fn max_points(&self) -> Option<u32> {
+        match self.curve {
+            Curve::Bn254 => Some(self.max_points.unwrap_or(IGNITION_G1_POINTS)),
+            Curve::Grumpkin => self.max_points,
+        }
     }
 }
@@ -0,0 +1,75 @@
+use std::{env, path::PathBuf};
+
+use reqwest::Url;
+
+use super::{local_srs::LocalSrs, netsrs::NetSrs, Srs, SrsError};
+
+/// Environment variable holding the path of a local SRS, see [`LocalSrs::new`].
+pub const SRS_PATH_VAR: &str = "NOIR_RS_SRS_PATH";
+/// Environment variable holding the URL of a transcript mirror, see [`NetSrs::with_url`].
+pub const SRS_URL_VAR: &str = "NOIR_RS_SRS_URL";
+
+/// Where the SRS is loaded from, resolved from the deployment configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SrsSource {
+    /// An ignition transcript, a `bn254_g1.dat` file or a flat CRS directory on disk.
+    Local(PathBuf),
+    /// A transcript mirror with the same layout as the ignition `transcript00.dat`.
+    Url(String),
+    /// The default ignition transcript.
+    Default,
+}
+
+impl SrsSource {
+    /// Resolves the source from the `NOIR_RS_SRS_PATH` and `NOIR_RS_SRS_URL` environment
+    /// variables.
+    ///
+    /// The path takes precedence over the URL, and the default transcript is used when neither is
+    /// set. Empty values count as unset.
+    ///
+    /// # Returns
+    /// * `Result<SrsSource, SrsError>` - Returns the source, or an `SrsError::InvalidEnvVar` naming
+    ///   the variable if the path does not exist or the URL cannot be parsed.
+    pub fn from_env() -> Result<Self, SrsError> {
+        if let Some(path) = env::var_os(SRS_PATH_VAR).filter(|path| !path.is_empty()) {
+            let path = PathBuf::from(path);
+            if !path.exists() {
+                return Err(SrsError::InvalidEnvVar {
+                    var: SRS_PATH_VAR,
+                    reason: format!("{} does not exist", path.display()),
+                });
+            }
+            return Ok(SrsSource::Local(path));
+        }
+
+        Ok(match url_from_env()? {
+            Some(url) => SrsSource::Url(url),
+            None => SrsSource::Default,
+        })
+    }
+
+    /// Creates the SRS implementation reading from this source.
+    pub fn into_srs(self) -> Box<dyn Srs> {
+        match self {
+            SrsSource::Local(path) => Box::new(LocalSrs::new(path)),
+            SrsSource::Url(url) => Box::new(NetSrs::with_url(&url)),
+            SrsSource::Default => Box::<NetSrs>::default(),
+        }
+    }
+}
+
+/// Reads the transcript mirror URL from `NOIR_RS_SRS_URL`, or `None` when it is unset or empty.
+pub(crate) fn url_from_env() -> Result<Option<String>, SrsError> {
+    match env::var(SRS_URL_VAR) {
+        Ok(url) if url.is_empty() => Ok(None),
+        Ok(url) => match Url::parse(&url) {
+            Ok(_) => Ok(Some(url)),
+            Err(error) => Err(SrsError::InvalidEnvVar {
+                var: SRS_URL_VAR,
+                reason: format!("{} is not a valid URL: {}", url, error),
+            }),
+        },
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(error) => Err(SrsError::InvalidEnvVar { var: SRS_URL_VAR, reason: error.to_string() }),
+    }
+}
@@ -0,0 +1,1389 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::{
+    blocking::Client,
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, RANGE},
+    Proxy, StatusCode,
+};
+
+use crate::{
+    srs::{
+        cached_srs::{CachePolicy, CachedSrs},
+        cancel::CancellationToken,
+        check_transcript,
+        checksum::SrsChecksum,
+        dev_srs::DevSrs,
+        download::{download_srs, ensure_transcript},
+        grumpkin_srs::GrumpkinSrs,
+        ignition_verifier_data,
+        local_srs::{trim_transcript, LocalSrs, TranscriptFormat, FLAT_G1_FILE, FLAT_G2_FILE},
+        mock::{MockSrsServer, MockTranscript},
+        netsrs::{NetSrs, Validator, IGNITION_G1_POINTS},
+        source::{SrsSource, SRS_PATH_VAR, SRS_URL_VAR},
+        srs_init, srs_init_grumpkin, Curve, Srs, SrsError,
+    },
+    BackendError,
+};
+
+/// Starts a proxy that records the first request line of each connection and rejects it.
+fn spawn_mock_proxy() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            let request = String::from_utf8_lossy(&request);
+            let request_line = request.lines().next().unwrap_or_default().to_string();
+            if sender.send(request_line).is_err() {
+                return;
+            }
+            stream.write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n").unwrap();
+        }
+    });
+    (address, receiver)
+}
+
+/// Byte served by the mock transcript server at the given offset for a transcript version.
+fn transcript_byte(offset: u64, version: u8) -> u8 {
+    (offset.wrapping_mul(31).wrapping_add(version as u64 * 7) % 251) as u8
+}
+
+/// Reads one request head from the stream, returning `None` once the connection is closed.
+fn read_request(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1];
+    while !request.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut buf).ok()? == 0 {
+            return None;
+        }
+        request.push(buf[0]);
+    }
+    Some(String::from_utf8_lossy(&request).into_owned())
+}
+
+/// Returns the value of a request header, matching its name case-insensitively.
+fn header_value<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    request.lines().find_map(|line| {
+        let (header, value) = line.split_once(':')?;
+        header.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+/// A server answering range requests with [`transcript_byte`] data.
+///
+/// Responses carry an ETag derived from `version`, and conditional requests matching it are
+/// answered with `304 Not Modified`. Ranges served with a body are sent on `requests`.
+struct MockTranscriptServer {
+    url: String,
+    requests: mpsc::Receiver<(u64, u64)>,
+    version: Arc<AtomicU8>,
+    not_modified: Arc<AtomicUsize>,
+}
+
+impl MockTranscriptServer {
+    /// Starts the server. When `fail_first` is set, the first request for every range fails
+    /// with 503.
+    fn spawn(fail_first: bool) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/transcript00.dat", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+        let version = Arc::new(AtomicU8::new(0));
+        let not_modified = Arc::new(AtomicUsize::new(0));
+        let failed = Arc::new(Mutex::new(HashSet::new()));
+        let (server_version, server_not_modified) = (version.clone(), not_modified.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let sender = sender.clone();
+                let failed = failed.clone();
+                let version = server_version.clone();
+                let not_modified = server_not_modified.clone();
+                thread::spawn(move || {
+                    while let Some(request) = read_request(&mut stream) {
+                        let range = header_value(&request, "range").unwrap();
+                        let (start, end) =
+                            range.strip_prefix("bytes=").unwrap().split_once('-').unwrap();
+                        let (start, end): (u64, u64) =
+                            (start.parse().unwrap(), end.parse().unwrap());
+
+                        if fail_first && failed.lock().unwrap().insert((start, end)) {
+                            stream
+                                .write_all(
+                                    b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n",
+                                )
+                                .unwrap();
+                            continue;
+                        }
+
+                        let version = version.load(Ordering::SeqCst);
+                        let etag = format!("\"v{}\"", version);
+                        if header_value(&request, "if-none-match") == Some(etag.as_str()) {
+                            not_modified.fetch_add(1, Ordering::SeqCst);
+                            let head =
+                                format!("HTTP/1.1 304 Not Modified\r\nETag: {}\r\n\r\n", etag);
+                            stream.write_all(head.as_bytes()).unwrap();
+                            continue;
+                        }
+                        let _ = sender.send((start, end));
+
+                        let body: Vec<u8> =
+                            (start..=end).map(|offset| transcript_byte(offset, version)).collect();
+                        let head = format!(
+                            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/*\r\nContent-Length: {}\r\nETag: {}\r\n\r\n",
+                            start,
+                            end,
+                            body.len(),
+                            etag
+                        );
+                        stream.write_all(head.as_bytes()).unwrap();
+                        stream.write_all(&body).unwrap();
+                    }
+                });
+            }
+        });
+        MockTranscriptServer { url, requests, version, not_modified }
+    }
+}
+
+/// Expected transcript bytes for the given range.
+fn expected_range(start: u64, len: u64, version: u8) -> Vec<u8> {
+    (start..start + len).map(|offset| transcript_byte(offset, version)).collect()
+}
+
+/// Returns an empty directory under the system temporary directory.
+fn empty_cache_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("noir_rs_srs_{}_{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_netsrs_reassembles_concurrent_chunks() {
+    let server = MockTranscriptServer::spawn(false);
+    let num_points = 1000;
+
+    let mut srs = NetSrs::with_url(&server.url).with_concurrency(4);
+    srs.load_data(num_points).unwrap();
+
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert_eq!(srs.g2_data(), &expected_range(28 + 5040001 * 64, 128, 0));
+    assert_eq!(srs.num_points(), num_points);
+
+    let mut ranges: Vec<_> = server.requests.try_iter().collect();
+    ranges.sort();
+    // The probe for the first point, four G1 chunks and the G2 point.
+    assert_eq!(ranges.len(), 6);
+    assert_eq!(ranges[0], (28, 28 + 63));
+}
+
+#[test]
+fn test_netsrs_retries_failed_chunks() {
+    let server = MockTranscriptServer::spawn(true);
+    let num_points = 100;
+
+    let mut srs = NetSrs::with_url(&server.url).with_concurrency(8);
+    srs.load_data(num_points).unwrap();
+
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert_eq!(srs.g2_data(), &expected_range(28 + 5040001 * 64, 128, 0));
+}
+
+/// Starts a server answering every request with `404 Not Found`, and counts the requests.
+fn spawn_not_found_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/transcript00.dat", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let server_requests = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let requests = server_requests.clone();
+            thread::spawn(move || {
+                while read_request(&mut stream).is_some() {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    stream
+                        .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                        .unwrap();
+                }
+            });
+        }
+    });
+    (url, requests)
+}
+
+#[test]
+fn test_netsrs_fails_over_to_mirrors() {
+    let (not_found_url, not_found_requests) = spawn_not_found_server();
+    // Nothing listens on a port freed right after binding it.
+    let closed_url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/transcript00.dat", listener.local_addr().unwrap())
+    };
+    let server = MockTranscriptServer::spawn(false);
+    let num_points = 100;
+
+    let mut srs = NetSrs::with_mirrors([&closed_url, &not_found_url, &server.url]);
+    srs.load_data(num_points).unwrap();
+
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert_eq!(srs.g2_data(), &expected_range(28 + 5040001 * 64, 128, 0));
+    // Every range served by the last mirror was requested once from the 404 mirror, not retried.
+    let served = server.requests.try_iter().count();
+    assert!(served > 0);
+    assert_eq!(not_found_requests.load(Ordering::SeqCst), served);
+
+    let mut srs = NetSrs::with_mirrors([&closed_url, &not_found_url]);
+    assert!(matches!(
+        srs.load_data(num_points),
+        Err(SrsError::UnexpectedResponse { status: StatusCode::NOT_FOUND, .. })
+    ));
+}
+
+/// Starts a server answering every request with `response`, with `{url}` replaced by its own URL.
+fn spawn_fixed_response_server(response: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/transcript00.dat", listener.local_addr().unwrap());
+    let response = response.replace("{url}", &url);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let response = response.clone();
+            thread::spawn(move || {
+                while read_request(&mut stream).is_some() {
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+        }
+    });
+    url
+}
+
+#[test]
+fn test_netsrs_reports_unexpected_responses() {
+    let xml = "<?xml version=\"1.0\"?><Error><Code>AccessDenied</Code></Error>";
+    let xml_response = |status: &str| {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/xml\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            xml.len(),
+            xml
+        )
+    };
+    let forbidden_url = spawn_fixed_response_server(&xml_response("403 Forbidden"));
+    let error = NetSrs::with_url(&forbidden_url).load_data(100).unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            SrsError::UnexpectedResponse { status: StatusCode::FORBIDDEN, url, content_type: Some(content_type), body }
+                if *url == forbidden_url && content_type == "application/xml" && body == xml
+        ),
+        "{:?}",
+        error
+    );
+    assert!(error.to_string().contains("403 Forbidden"), "{}", error);
+    let dir = empty_cache_dir("forbidden");
+    fs::create_dir_all(&dir).unwrap();
+    assert!(matches!(
+        download_srs(Some(&forbidden_url), &dir.join("transcript00.dat"), None),
+        Err(SrsError::UnexpectedResponse { status: StatusCode::FORBIDDEN, .. })
+    ));
+
+    // An error page served as a success is not read as points either.
+    let ok_url = spawn_fixed_response_server(&xml_response("200 OK"));
+    assert!(matches!(
+        NetSrs::with_url(&ok_url).load_data(100),
+        Err(SrsError::UnexpectedResponse { status: StatusCode::OK, .. })
+    ));
+
+    // The error names the URL the response came from after redirects.
+    let redirect_url = spawn_fixed_response_server(&format!(
+        "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\n\r\n",
+        forbidden_url
+    ));
+    assert!(matches!(
+        NetSrs::with_url(&redirect_url).load_data(100),
+        Err(SrsError::UnexpectedResponse { url, .. }) if url == forbidden_url
+    ));
+
+    let loop_url = spawn_fixed_response_server(
+        "HTTP/1.1 302 Found\r\nLocation: {url}\r\nContent-Length: 0\r\n\r\n",
+    );
+    assert!(matches!(
+        NetSrs::with_url(&loop_url).load_data(100),
+        Err(SrsError::Download(error)) if error.is_redirect()
+    ));
+}
+
+/// Starts a server that accepts connections and reads requests, but never answers them.
+fn spawn_stalled_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/transcript00.dat", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            thread::spawn(move || while read_request(&mut stream).is_some() {});
+        }
+    });
+    url
+}
+
+#[test]
+fn test_netsrs_cancellation() {
+    let server = MockTranscriptServer::spawn(false);
+    let token = CancellationToken::new();
+    let mut srs = NetSrs::with_url(&server.url).with_cancellation(token.clone());
+    token.cancel();
+    assert!(matches!(srs.load_data(100), Err(SrsError::Cancelled)));
+    assert_eq!(server.requests.try_iter().count(), 0);
+
+    // The deadline interrupts a request the server never answers, without trying other mirrors.
+    let stalled_url = spawn_stalled_server();
+    let start = Instant::now();
+    let token = CancellationToken::new().with_deadline(start + Duration::from_millis(300));
+    let mut srs = NetSrs::with_mirrors([&stalled_url, &server.url]).with_cancellation(token);
+    assert!(matches!(srs.load_data(100), Err(SrsError::Cancelled)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(server.requests.try_iter().count(), 0);
+}
+
+/// Starts a server answering range requests with [`transcript_byte`] data only when they carry the
+/// `Authorization: Bearer secret` and `X-Tenant: noir` headers, and `401 Unauthorized` otherwise.
+/// The `Range` headers of each request are sent on the returned channel.
+fn spawn_auth_server() -> (String, mpsc::Receiver<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/transcript00.dat", listener.local_addr().unwrap());
+    let (sender, ranges) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                while let Some(request) = read_request(&mut stream) {
+                    let authorized = header_value(&request, "authorization")
+                        == Some("Bearer secret")
+                        && header_value(&request, "x-tenant") == Some("noir");
+                    if !authorized {
+                        stream
+                            .write_all(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n")
+                            .unwrap();
+                        continue;
+                    }
+
+                    let range_headers: Vec<String> = request
+                        .lines()
+                        .filter_map(|line| line.split_once(':'))
+                        .filter(|(name, _)| name.eq_ignore_ascii_case("range"))
+                        .map(|(_, value)| value.trim().to_string())
+                        .collect();
+                    let (start, end) =
+                        range_headers[0].strip_prefix("bytes=").unwrap().split_once('-').unwrap();
+                    let (start, end): (u64, u64) = (start.parse().unwrap(), end.parse().unwrap());
+                    let _ = sender.send(range_headers);
+
+                    let body: Vec<u8> =
+                        (start..=end).map(|offset| transcript_byte(offset, 0)).collect();
+                    let head = format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/*\r\nContent-Length: {}\r\n\r\n",
+                        start,
+                        end,
+                        body.len()
+                    );
+                    stream.write_all(head.as_bytes()).unwrap();
+                    stream.write_all(&body).unwrap();
+                }
+            });
+        }
+    });
+    (url, ranges)
+}
+
+#[test]
+fn test_netsrs_with_headers() {
+    let (url, ranges) = spawn_auth_server();
+    let num_points = 100;
+
+    let mut srs = NetSrs::with_url(&url);
+    match srs.load_data(num_points) {
+        Err(SrsError::UnexpectedResponse { status, .. }) => {
+            assert_eq!(status, StatusCode::UNAUTHORIZED)
+        }
+        other => panic!("expected a 401 response error, got {:?}", other),
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+    headers.insert("x-tenant", HeaderValue::from_static("noir"));
+    // A user supplied range must not replace the ranges NetSrs requests.
+    headers.insert(RANGE, HeaderValue::from_static("bytes=0-0"));
+    let mut srs = NetSrs::with_headers(&url, headers);
+    srs.load_data(num_points).unwrap();
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert_eq!(srs.g2_data(), &expected_range(28 + 5040001 * 64, 128, 0));
+
+    let ranges: Vec<_> = ranges.try_iter().collect();
+    assert!(!ranges.is_empty());
+    for range in ranges {
+        assert_eq!(range.len(), 1);
+        assert_ne!(range[0], "bytes=0-0");
+    }
+}
+
+#[test]
+fn test_cached_srs_revalidates_with_etag() {
+    let server = MockTranscriptServer::spawn(false);
+    let cache_dir = empty_cache_dir("revalidate");
+    let num_points = 100;
+    let load = || {
+        let mut srs = CachedSrs::with_net_srs(
+            &cache_dir,
+            CachePolicy::Revalidate,
+            NetSrs::with_url(&server.url),
+        );
+        srs.load_data(num_points).unwrap();
+        srs
+    };
+
+    // An empty cache is filled from the server.
+    let srs = load();
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert!(server.requests.try_iter().count() > 0);
+
+    // An unchanged transcript is answered with 304 and the cache is reused.
+    let srs = load();
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert_eq!(server.not_modified.load(Ordering::SeqCst), 1);
+    assert_eq!(server.requests.try_iter().count(), 0);
+
+    // A changed transcript invalidates the cache.
+    server.version.store(1, Ordering::SeqCst);
+    let srs = load();
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 1));
+    assert_eq!(srs.g2_data(), &expected_range(28 + 5040001 * 64, 128, 1));
+    assert_eq!(server.not_modified.load(Ordering::SeqCst), 1);
+
+    let mut offline = CachedSrs::new(&cache_dir, CachePolicy::Offline);
+    offline.load_data(num_points).unwrap();
+    assert_eq!(offline.g1_data(), &expected_range(28, num_points as u64 * 64, 1));
+
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+fn test_netsrs_checksum() {
+    let server = MockTranscriptServer::spawn(false);
+    let g2_data = expected_range(28 + 5040001 * 64, 128, 0);
+    let checksum = SrsChecksum::compute(&expected_range(28, 50 * 64, 0), &g2_data, 50).unwrap();
+
+    let mut srs = NetSrs::with_url(&server.url).with_checksum(checksum);
+    srs.load_data(100).unwrap();
+    assert_eq!(srs.g1_data(), &expected_range(28, 100 * 64, 0));
+
+    // Loading fewer points than pinned still downloads and checks the pinned ones.
+    srs.load_data(10).unwrap();
+    assert_eq!(srs.g1_data(), &expected_range(28, 10 * 64, 0));
+    assert_eq!(srs.num_points(), 10);
+
+    server.version.store(1, Ordering::SeqCst);
+    assert!(matches!(
+        srs.load_data(100),
+        Err(SrsError::ChecksumMismatch { num_points: 50, expected, .. }) if expected == checksum.sha256
+    ));
+}
+
+#[test]
+fn test_cached_srs_checksum() {
+    let server = MockTranscriptServer::spawn(false);
+    let cache_dir = empty_cache_dir("checksum");
+    let g2_data = expected_range(28 + 5040001 * 64, 128, 0);
+    let checksum = SrsChecksum::compute(&expected_range(28, 20 * 64, 0), &g2_data, 20).unwrap();
+    let cached = |policy| {
+        CachedSrs::with_net_srs(&cache_dir, policy, NetSrs::with_url(&server.url))
+            .with_checksum(checksum)
+    };
+
+    cached(CachePolicy::Always).load_data(20).unwrap();
+    assert!(server.requests.try_iter().count() > 0);
+
+    // A tampered cache fails offline and is downloaded again otherwise.
+    let mut g1_data = fs::read(cache_dir.join("g1.dat")).unwrap();
+    g1_data[100] ^= 1;
+    fs::write(cache_dir.join("g1.dat"), g1_data).unwrap();
+    assert!(matches!(
+        cached(CachePolicy::Offline).load_data(20),
+        Err(SrsError::ChecksumMismatch { .. })
+    ));
+    let mut srs = cached(CachePolicy::Always);
+    srs.load_data(20).unwrap();
+    assert_eq!(srs.g1_data(), &expected_range(28, 20 * 64, 0));
+    assert!(server.requests.try_iter().count() > 0);
+
+    // Data failing the check is not cached.
+    fs::remove_dir_all(&cache_dir).unwrap();
+    server.version.store(1, Ordering::SeqCst);
+    assert!(matches!(
+        cached(CachePolicy::Always).load_data(20),
+        Err(SrsError::ChecksumMismatch { .. })
+    ));
+    assert!(!cache_dir.join("g1.dat").exists());
+}
+
+#[test]
+fn test_cached_srs_policies() {
+    let server = MockTranscriptServer::spawn(false);
+    let cache_dir = empty_cache_dir("policies");
+
+    let mut offline = CachedSrs::new(&cache_dir, CachePolicy::Offline);
+    assert!(matches!(
+        offline.load_data(10),
+        Err(SrsError::InsufficientPoints { available: 0, requested: 10, .. })
+    ));
+
+    let mut always =
+        CachedSrs::with_net_srs(&cache_dir, CachePolicy::Always, NetSrs::with_url(&server.url));
+    always.load_data(10).unwrap();
+    assert!(server.requests.try_iter().count() > 0);
+
+    // A cache holding enough points is trusted without contacting the server.
+    server.version.store(1, Ordering::SeqCst);
+    let mut always =
+        CachedSrs::with_net_srs(&cache_dir, CachePolicy::Always, NetSrs::with_url(&server.url));
+    always.load_data(5).unwrap();
+    assert_eq!(always.g1_data(), &expected_range(28, 10 * 64, 0));
+    assert_eq!(always.num_points(), 10);
+    assert_eq!(server.requests.try_iter().count(), 0);
+
+    let mut offline = CachedSrs::new(&cache_dir, CachePolicy::Offline);
+    assert!(matches!(
+        offline.load_data(20),
+        Err(SrsError::InsufficientPoints { available: 10, requested: 20, .. })
+    ));
+
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+fn test_cached_srs_resumes_partial_download() {
+    let server = MockTranscriptServer::spawn(false);
+    let cache_dir = empty_cache_dir("resume");
+    let num_points = 100;
+    let seed_partial_download = |version: u8| {
+        fs::create_dir_all(&cache_dir).unwrap();
+        // 40 complete points followed by a point cut off by the interruption.
+        fs::write(cache_dir.join("g1.dat.part"), expected_range(28, 40 * 64 + 10, version))
+            .unwrap();
+        fs::write(cache_dir.join("validator.part"), format!("etag \"v{}\"", version)).unwrap();
+    };
+    let load = || {
+        let mut srs = CachedSrs::with_net_srs(
+            &cache_dir,
+            CachePolicy::Revalidate,
+            NetSrs::with_url(&server.url),
+        );
+        srs.load_data(num_points).unwrap();
+        srs
+    };
+
+    // Only the points missing from the partial file are requested.
+    seed_partial_download(0);
+    let srs = load();
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 0));
+    assert_eq!(server.not_modified.load(Ordering::SeqCst), 1);
+    let g2_start = 28 + 5040001 * 64;
+    let g1_starts: Vec<_> = server
+        .requests
+        .try_iter()
+        .map(|(start, _)| start)
+        .filter(|&start| start < g2_start)
+        .collect();
+    assert_eq!(g1_starts.iter().min(), Some(&(28 + 40 * 64)));
+    assert!(!cache_dir.join("g1.dat.part").exists());
+    assert!(!cache_dir.join("validator.part").exists());
+    assert_eq!(fs::read(cache_dir.join("g1.dat")).unwrap().len(), num_points as usize * 64);
+
+    // A partial file downloaded from a transcript that changed since is discarded.
+    fs::remove_dir_all(&cache_dir).unwrap();
+    seed_partial_download(0);
+    server.version.store(1, Ordering::SeqCst);
+    let srs = load();
+    assert_eq!(srs.g1_data(), &expected_range(28, num_points as u64 * 64, 1));
+    assert!(server.requests.try_iter().any(|(start, _)| start == 28));
+
+    fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+fn test_netsrs_with_client_uses_proxy() {
+    let (proxy_address, requests) = spawn_mock_proxy();
+    let client = Client::builder().proxy(Proxy::all(proxy_address).unwrap()).build().unwrap();
+
+    let mut srs = NetSrs::with_client(client);
+    assert!(srs.load_data(16).is_err());
+
+    let request_line = requests.recv().unwrap();
+    assert!(request_line.starts_with("CONNECT aztec-ignition.s3.amazonaws.com:443"));
+}
+
+#[test]
+fn test_check_transcript_accepts_dev_srs() {
+    let mut srs = DevSrs::new(16);
+    srs.load_data(16).unwrap();
+    check_transcript(srs.g1_data(), srs.g2_data()).unwrap();
+}
+
+#[test]
+fn test_clear_frees_data_and_reloads() {
+    let mut srs = DevSrs::new(16);
+    srs.load_data(16).unwrap();
+    let (g1_data, g2_data) = (srs.g1_data().to_vec(), srs.g2_data().to_vec());
+
+    srs.clear();
+    assert_eq!((srs.data.capacity(), srs.g2_data.capacity()), (0, 0));
+    assert_eq!(srs.num_points(), 16);
+
+    srs.load_data(16).unwrap();
+    assert_eq!((srs.g1_data(), srs.g2_data()), (&g1_data[..], &g2_data[..]));
+}
+
+#[test]
+fn test_check_transcript_accepts_ignition_verifier_data() {
+    let (g1_data, g2_data) = ignition_verifier_data();
+    assert_eq!((g1_data.len(), g2_data.len()), (64, 128));
+    check_transcript(&g1_data, &g2_data).unwrap();
+}
+
+#[test]
+fn test_check_transcript_rejects_byte_swapped_g1() {
+    let mut srs = DevSrs::new(16);
+    srs.load_data(16).unwrap();
+    let swapped: Vec<u8> =
+        srs.g1_data().chunks(8).flat_map(|limb| limb.iter().rev().copied()).collect();
+
+    let error = check_transcript(&swapped, srs.g2_data()).unwrap_err();
+    assert!(matches!(error, SrsError::InvalidTranscript(_)));
+    assert!(error.to_string().contains("generator check failed"));
+
+    // Skipping the first point, as a wrong offset would, is caught as well.
+    let error = check_transcript(&srs.g1_data()[64..], srs.g2_data()).unwrap_err();
+    assert!(error.to_string().contains("generator check failed"));
+}
+
+#[test]
+fn test_check_transcript_rejects_invalid_g2() {
+    let mut srs = DevSrs::new(16);
+    srs.load_data(16).unwrap();
+    let mut g2_data = srs.g2_data().to_vec();
+    g2_data.swap(0, 8);
+
+    let error = check_transcript(srs.g1_data(), &g2_data).unwrap_err();
+    assert!(error.to_string().contains("G2"));
+}
+
+/// Lays out the SRS data as an ignition transcript.
+fn ignition_transcript(g1_data: &[u8], g2_data: &[u8]) -> Vec<u8> {
+    let num_g1_points = (g1_data.len() / 64) as u32;
+    let mut transcript = Vec::new();
+    for word in [0, 1, num_g1_points, 1, num_g1_points, 1, 0] {
+        transcript.extend_from_slice(&u32::to_be_bytes(word));
+    }
+    transcript.extend_from_slice(g1_data);
+    transcript.extend_from_slice(g2_data);
+    transcript
+}
+
+/// Writes the SRS data as an ignition transcript `transcript00.dat` and as flat `bn254_g1.dat`
+/// and `bn254_g2.dat` files in `dir`.
+fn write_transcripts(dir: &Path, g1_data: &[u8], g2_data: &[u8]) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join("transcript00.dat"), ignition_transcript(g1_data, g2_data)).unwrap();
+    fs::write(dir.join(FLAT_G1_FILE), g1_data).unwrap();
+    fs::write(dir.join(FLAT_G2_FILE), g2_data).unwrap();
+}
+
+#[test]
+fn test_local_srs_reads_both_formats() {
+    let dir = empty_cache_dir("local_formats");
+    let mut dev_srs = DevSrs::new(32);
+    dev_srs.load_data(32).unwrap();
+    write_transcripts(&dir, dev_srs.g1_data(), dev_srs.g2_data());
+
+    for mut srs in [
+        LocalSrs::new(dir.join("transcript00.dat")),
+        LocalSrs::new(&dir),
+        LocalSrs::new(dir.join(FLAT_G1_FILE)),
+        LocalSrs::with_format(dir.join("transcript00.dat"), TranscriptFormat::Ignition),
+        LocalSrs::with_format(&dir, TranscriptFormat::Flat),
+    ] {
+        assert_eq!(srs.max_points().unwrap(), 32);
+        srs.load_data(16).unwrap();
+        assert_eq!(srs.g1_data()[..], dev_srs.g1_data()[..16 * 64]);
+        assert_eq!(srs.g2_data(), dev_srs.g2_data());
+        assert_eq!(srs.num_points(), 16);
+        check_transcript(srs.g1_data(), srs.g2_data()).unwrap();
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_local_srs_reads_g2_sidecar() {
+    let dir = empty_cache_dir("local_g2_sidecar");
+    let mut dev_srs = DevSrs::new(16);
+    dev_srs.load_data(16).unwrap();
+    // The files of a custom ceremony, whose G2 point is not where the layouts place it.
+    write_transcripts(&dir, dev_srs.g1_data(), &[0; 128]);
+    let g1_only = dir.join("g1_only.dat");
+    fs::write(&g1_only, ignition_transcript(dev_srs.g1_data(), &[])).unwrap();
+    // A transcript whose manifest describes no G2 point, as written for G1 only.
+    let no_g2 = dir.join("no_g2.dat");
+    let mut transcript = ignition_transcript(dev_srs.g1_data(), &[]);
+    transcript[12..16].copy_from_slice(&[0; 4]);
+    transcript[20..24].copy_from_slice(&[0; 4]);
+    fs::write(&no_g2, transcript).unwrap();
+    let g2_path = dir.join("g2.dat");
+    fs::write(&g2_path, dev_srs.g2_data()).unwrap();
+
+    assert!(matches!(
+        LocalSrs::new(&g1_only).load_data(16),
+        Err(SrsError::FileTooSmallForG2 { .. })
+    ));
+    assert!(matches!(LocalSrs::new(&no_g2).load_data(16), Err(SrsError::InvalidTranscript(_))));
+    for path in [dir.join("transcript00.dat"), g1_only, no_g2, dir.clone()] {
+        let mut srs = LocalSrs::new(&path).with_g2_path(&g2_path);
+        assert_eq!(srs.max_points().unwrap(), 16);
+        srs.load_data(16).unwrap();
+        assert_eq!(srs.g1_data(), dev_srs.g1_data());
+        assert_eq!(srs.g2_data(), dev_srs.g2_data());
+        check_transcript(srs.g1_data(), srs.g2_data()).unwrap();
+    }
+
+    let mut srs = LocalSrs::new(dir.join("transcript00.dat")).with_g2_path(&g2_path);
+    fs::write(&g2_path, [dev_srs.g2_data(), &[0]].concat()).unwrap();
+    assert!(matches!(srs.load_data(16), Err(SrsError::InvalidTranscript(_))));
+    fs::write(&g2_path, &dev_srs.g2_data()[..64]).unwrap();
+    assert!(matches!(srs.load_data(16), Err(SrsError::FileTooSmallForG2 { len: 64, .. })));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_netsrs_downloads_g2_from_its_url() {
+    let mut dev_srs = DevSrs::new(16);
+    dev_srs.load_data(16).unwrap();
+    let transcript = MockTranscript::ignition(dev_srs.g1_data(), &[0; 128]);
+    let (url, server) = MockSrsServer::serve(transcript);
+    let (g2_url, g2_server) = MockSrsServer::serve(dev_srs.g2_data().to_vec());
+
+    let mut srs = NetSrs::with_url(&url).with_g2_url(&g2_url);
+    srs.load_data(16).unwrap();
+    assert_eq!(srs.g1_data(), dev_srs.g1_data());
+    assert_eq!(srs.g2_data(), dev_srs.g2_data());
+    // The G2 point of the transcript is not requested, and the G2 file is fetched whole.
+    assert!(taken_ranges(&server).iter().all(|&(start, _)| start < 28 + 16 * 64));
+    assert_eq!(
+        g2_server.take_requests().iter().map(|request| request.range).collect::<Vec<_>>(),
+        [None]
+    );
+
+    let (long_url, _long_server) = MockSrsServer::serve([dev_srs.g2_data(), &[0]].concat());
+    let error = NetSrs::with_url(&url).with_g2_url(&long_url).load_data(16).unwrap_err();
+    assert!(
+        matches!(&error, SrsError::InvalidTranscript(message) if message.contains("longer")),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn test_srs_max_points() {
+    let dir = empty_cache_dir("max_points");
+    let mut dev_srs = DevSrs::new(8);
+    dev_srs.load_data(8).unwrap();
+    write_transcripts(&dir, dev_srs.g1_data(), dev_srs.g2_data());
+
+    assert_eq!(Srs::max_points(&dev_srs), Some(8));
+    assert_eq!(Srs::max_points(&LocalSrs::new(dir.join("transcript00.dat"))), Some(8));
+    assert_eq!(Srs::max_points(&LocalSrs::new(dir.join("missing.dat"))), None);
+    assert_eq!(Srs::max_points(&NetSrs::default()), Some(IGNITION_G1_POINTS));
+    assert_eq!(Srs::max_points(&NetSrs::default().with_max_points(100)), Some(100));
+    assert_eq!(Srs::max_points(&NetSrs::default().with_curve(Curve::Grumpkin)), None);
+    let cached =
+        CachedSrs::with_net_srs(&dir, CachePolicy::Always, NetSrs::default().with_max_points(100));
+    assert_eq!(Srs::max_points(&cached), Some(100));
+    let boxed: Box<dyn Srs> = Box::new(DevSrs::new(16));
+    assert_eq!(boxed.max_points(), Some(16));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_trim_transcript_keeps_needed_points() {
+    let dir = empty_cache_dir("trim_transcript");
+    let mut dev_srs = DevSrs::new(32);
+    dev_srs.load_data(32).unwrap();
+    write_transcripts(&dir, dev_srs.g1_data(), dev_srs.g2_data());
+    let (src, dst) = (dir.join("transcript00.dat"), dir.join("trimmed.dat"));
+
+    trim_transcript(&src, &dst, 8).unwrap();
+    // The manifest keeps the total points of the ceremony but counts the points of the file.
+    let trimmed = fs::read(&dst).unwrap();
+    let expected = ignition_transcript(&dev_srs.g1_data()[..8 * 64], dev_srs.g2_data());
+    assert_eq!(trimmed[8..12], u32::to_be_bytes(32));
+    assert_eq!(trimmed[12..], expected[12..]);
+    let mut srs = LocalSrs::new(&dst);
+    // The G2 point follows the G1 points, so the size of the file tells how many it holds.
+    assert_eq!(srs.max_points().unwrap(), (trimmed.len() as u32 - 28 - 128) / 64);
+    assert_eq!(srs.max_points().unwrap(), 8);
+    srs.load_data(8).unwrap();
+    check_transcript(srs.g1_data(), srs.g2_data()).unwrap();
+    assert!(matches!(srs.load_data(9), Err(SrsError::InsufficientPoints { available: 8, .. })));
+    assert!(matches!(
+        trim_transcript(&src, &dst, 33),
+        Err(SrsError::InsufficientPoints { available: 32, .. })
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_local_srs_validates_sizes() {
+    let dir = empty_cache_dir("local_sizes");
+    let mut dev_srs = DevSrs::new(8);
+    dev_srs.load_data(8).unwrap();
+    write_transcripts(&dir, dev_srs.g1_data(), dev_srs.g2_data());
+
+    let g1_path = dir.join(FLAT_G1_FILE);
+    let transcript_path = dir.join("transcript00.dat");
+
+    let mut srs = LocalSrs::new(&dir);
+    assert!(matches!(
+        srs.load_data(9),
+        Err(SrsError::InsufficientPoints { available: 8, requested: 9, path: Some(path) })
+            if path == g1_path
+    ));
+    let mut srs = LocalSrs::new(&transcript_path);
+    assert!(matches!(
+        srs.load_data(9),
+        Err(SrsError::InsufficientPoints { available: 8, requested: 9, path: Some(path) })
+            if path == transcript_path
+    ));
+
+    // A transcript cut in the middle of the sixth G1 point still holds the first five.
+    let transcript = fs::read(&transcript_path).unwrap();
+    fs::write(&transcript_path, &transcript[..28 + 5 * 64 + 10]).unwrap();
+    let mut srs = LocalSrs::new(&transcript_path);
+    let error = srs.load_data(6).unwrap_err();
+    assert!(matches!(error, SrsError::InsufficientPoints { available: 5, requested: 6, .. }));
+    assert!(error.to_string().contains(transcript_path.to_str().unwrap()), "{}", error);
+    assert!(matches!(
+        srs.load_data(4),
+        Err(SrsError::FileTooSmallForG2 { len, g2_offset, .. })
+            if len == 28 + 5 * 64 + 10 && g2_offset == 28 + 8 * 64
+    ));
+    assert!(matches!(srs.max_points(), Err(SrsError::FileTooSmallForG2 { .. })));
+    fs::write(&transcript_path, &transcript[..transcript.len() - 1]).unwrap();
+    assert!(matches!(srs.load_data(8), Err(SrsError::FileTooSmallForG2 { .. })));
+    fs::write(&transcript_path, &transcript[..20]).unwrap();
+    assert!(matches!(srs.load_data(1), Err(SrsError::InvalidTranscript(_))));
+
+    fs::write(dir.join(FLAT_G2_FILE), &dev_srs.g2_data()[..64]).unwrap();
+    let mut srs = LocalSrs::new(&dir);
+    assert!(matches!(
+        srs.load_data(4),
+        Err(SrsError::FileTooSmallForG2 { len: 64, g2_offset: 0, .. })
+    ));
+    fs::write(dir.join(FLAT_G2_FILE), [dev_srs.g2_data(), &[0]].concat()).unwrap();
+    assert!(matches!(srs.load_data(4), Err(SrsError::InvalidTranscript(_))));
+
+    let missing = dir.join("missing.dat");
+    let mut srs = LocalSrs::with_format(&missing, TranscriptFormat::Ignition);
+    assert!(matches!(srs.load_data(1), Err(SrsError::File { path, .. }) if path == missing));
+
+    fs::write(dir.join(FLAT_G1_FILE), &dev_srs.g1_data()[..100]).unwrap();
+    let mut srs = LocalSrs::with_format(dir.join(FLAT_G1_FILE), TranscriptFormat::Flat);
+    assert!(matches!(srs.load_data(1), Err(SrsError::InvalidTranscript(_))));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_srs_source_from_env() {
+    let dir = empty_cache_dir("source_env");
+    let mut dev_srs = DevSrs::new(8);
+    dev_srs.load_data(8).unwrap();
+    write_transcripts(&dir, dev_srs.g1_data(), dev_srs.g2_data());
+    let dir_str = dir.to_str().unwrap();
+    let missing = dir.join("missing.dat");
+    let missing_str = missing.to_str().unwrap();
+    let url = "https://mirror.example.com/transcript00.dat";
+
+    temp_env::with_vars([(SRS_PATH_VAR, None::<&str>), (SRS_URL_VAR, None)], || {
+        assert_eq!(SrsSource::from_env().unwrap(), SrsSource::Default);
+    });
+    temp_env::with_vars([(SRS_PATH_VAR, Some("")), (SRS_URL_VAR, Some(url))], || {
+        assert_eq!(SrsSource::from_env().unwrap(), SrsSource::Url(url.to_string()));
+    });
+    temp_env::with_vars([(SRS_PATH_VAR, Some(dir_str)), (SRS_URL_VAR, Some(url))], || {
+        let source = SrsSource::from_env().unwrap();
+        assert_eq!(source, SrsSource::Local(dir.clone()));
+        let mut srs = source.into_srs();
+        srs.load_data(4).unwrap();
+        assert_eq!(srs.g1_data()[..], dev_srs.g1_data()[..4 * 64]);
+    });
+
+    temp_env::with_vars([(SRS_PATH_VAR, Some(missing_str)), (SRS_URL_VAR, Some(url))], || {
+        assert!(matches!(
+            SrsSource::from_env(),
+            Err(SrsError::InvalidEnvVar { var: SRS_PATH_VAR, .. })
+        ));
+    });
+    temp_env::with_vars([(SRS_PATH_VAR, None), (SRS_URL_VAR, Some("not a url"))], || {
+        assert!(matches!(
+            SrsSource::from_env(),
+            Err(SrsError::InvalidEnvVar { var: SRS_URL_VAR, .. })
+        ));
+    });
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_netsrs_from_env_overrides_default_url() {
+    let server = MockTranscriptServer::spawn(false);
+
+    temp_env::with_var(SRS_URL_VAR, Some(&server.url), || {
+        let mut srs = NetSrs::from_env().unwrap();
+        srs.load_data(4).unwrap();
+        assert_eq!(srs.g1_data(), &expected_range(28, 4 * 64, 0));
+    });
+    assert!(server.requests.try_iter().count() > 0);
+
+    temp_env::with_var(SRS_URL_VAR, Some("not a url"), || {
+        assert!(matches!(
+            NetSrs::from_env(),
+            Err(SrsError::InvalidEnvVar { var: SRS_URL_VAR, .. })
+        ));
+    });
+}
+
+#[test]
+fn test_grumpkin_srs_reads_synthetic_transcript() {
+    let dir = empty_cache_dir("grumpkin");
+    fs::create_dir_all(dir.join("monomial")).unwrap();
+    let num_g1_points = 12u32;
+    let g1_data: Vec<u8> =
+        (0..num_g1_points * 64).map(|offset| transcript_byte(offset as u64, 3)).collect();
+    let mut transcript = Vec::new();
+    for word in [0, 1, num_g1_points, 0, num_g1_points, 0, 0] {
+        transcript.extend_from_slice(&u32::to_be_bytes(word));
+    }
+    transcript.extend_from_slice(&g1_data);
+    let path = dir.join("monomial").join("transcript00.dat");
+    fs::write(&path, &transcript).unwrap();
+
+    for mut srs in [GrumpkinSrs::local(&dir), GrumpkinSrs::local(&path)] {
+        srs.load_data(5).unwrap();
+        assert_eq!(srs.num_points(), 5);
+        assert_eq!(srs.g1_data()[..], g1_data[..5 * 64]);
+        assert!(srs.g2_data().is_empty());
+
+        srs.load_data(num_g1_points).unwrap();
+        assert_eq!(srs.num_points(), num_g1_points);
+        assert_eq!(srs.g1_data(), &g1_data);
+
+        assert!(matches!(
+            srs.load_data(num_g1_points + 1),
+            Err(SrsError::InsufficientPoints { available: 12, requested: 13, .. })
+        ));
+    }
+
+    // Without a G2 point the transcript is not a valid bn254 SRS.
+    let mut srs = LocalSrs::with_format(&path, TranscriptFormat::Ignition);
+    assert!(matches!(srs.load_data(5), Err(SrsError::InvalidTranscript(_))));
+
+    // Grumpkin needs no G2 point, so a truncated transcript serves the G1 points it holds.
+    fs::write(&path, &transcript[..transcript.len() - 1]).unwrap();
+    let mut srs = GrumpkinSrs::local(&path);
+    srs.load_data(5).unwrap();
+    assert!(matches!(
+        srs.load_data(12),
+        Err(SrsError::InsufficientPoints { available: 11, requested: 12, .. })
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_srs_init_grumpkin_rejects_short_buffers() {
+    // Rejected before reaching the backend, which would read past the end of the buffer.
+    let points = vec![0u8; 4 * 64];
+    assert!(matches!(srs_init_grumpkin(&points, 5), Err(BackendError::BindingCallError(_))));
+    assert!(matches!(
+        srs_init_grumpkin(&points[..4 * 64 - 1], 4),
+        Err(BackendError::BindingCallError(_))
+    ));
+    assert!(matches!(srs_init_grumpkin(&[], 0), Err(BackendError::BindingCallError(_))));
+    assert!(matches!(srs_init_grumpkin(&points, 0), Err(BackendError::BindingCallError(_))));
+}
+
+#[test]
+fn test_srs_init_rejects_short_buffers() {
+    // Rejected before reaching the backend, which would read past the end of the buffers.
+    let points = vec![0u8; 4 * 64];
+    let g2_point = vec![0u8; 128];
+    assert!(matches!(srs_init(&points, 5, &g2_point), Err(BackendError::BindingCallError(_))));
+    assert!(matches!(
+        srs_init(&points[..4 * 64 - 1], 4, &g2_point),
+        Err(BackendError::BindingCallError(_))
+    ));
+    assert!(matches!(srs_init(&points, 0, &g2_point), Err(BackendError::BindingCallError(_))));
+    assert!(matches!(
+        srs_init(&points, 4, &g2_point[..127]),
+        Err(BackendError::BindingCallError(_))
+    ));
+    assert!(matches!(srs_init(&points, 4, &[]), Err(BackendError::BindingCallError(_))));
+}
+
+/// Returns the ranges requested from the server since the last call.
+fn taken_ranges(server: &MockSrsServer) -> Vec<(u64, Option<u64>)> {
+    server.take_requests().into_iter().map(|request| request.range.unwrap()).collect()
+}
+
+/// Returns an ignition transcript holding `num_points` points of the dev SRS.
+fn dev_transcript(num_points: u32) -> (DevSrs, Vec<u8>) {
+    let mut dev_srs = DevSrs::new(num_points);
+    dev_srs.load_data(num_points).unwrap();
+    let transcript = ignition_transcript(dev_srs.g1_data(), dev_srs.g2_data());
+    (dev_srs, transcript)
+}
+
+#[test]
+fn test_download_srs_resumes_partial_file() {
+    let dir = empty_cache_dir("download_resume");
+    fs::create_dir_all(&dir).unwrap();
+    let (dev_srs, transcript) = dev_transcript(32);
+    let (url, server) = MockSrsServer::serve(transcript.clone());
+
+    let dest = dir.join("transcript00.dat");
+    fs::write(dir.join("transcript00.dat.part"), &transcript[..1000]).unwrap();
+    let written = download_srs(Some(&url), &dest, None).unwrap();
+    assert_eq!(written, transcript.len() as u64 - 1000);
+    assert_eq!(taken_ranges(&server), vec![(1000, None)]);
+    assert_eq!(fs::read(&dest).unwrap(), transcript);
+    assert!(!dir.join("transcript00.dat.part").exists());
+
+    // Only the needed points and the G2 point are fetched, and the manifest is rewritten.
+    let dest = dir.join("trimmed.dat");
+    fs::write(dir.join("trimmed.dat.part"), &transcript[..500]).unwrap();
+    let written = download_srs(Some(&url), &dest, Some(16)).unwrap();
+    assert_eq!(written, 28 + 16 * 64 - 500 + 128);
+    let g2_start = 28 + 32 * 64;
+    assert_eq!(
+        taken_ranges(&server),
+        vec![(500, Some(28 + 16 * 64 - 1)), (g2_start, Some(g2_start + 127))]
+    );
+
+    let mut srs = LocalSrs::new(&dest);
+    srs.load_data(16).unwrap();
+    assert_eq!(srs.g1_data()[..], dev_srs.g1_data()[..16 * 64]);
+    assert_eq!(srs.g2_data(), dev_srs.g2_data());
+    assert!(matches!(
+        srs.load_data(17),
+        Err(SrsError::InsufficientPoints { available: 16, requested: 17, .. })
+    ));
+    assert!(matches!(
+        download_srs(Some(&url), &dir.join("too_many.dat"), Some(33)),
+        Err(SrsError::InsufficientPoints { available: 32, requested: 33, .. })
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_ensure_transcript_downloads_only_missing_points() {
+    let dir = empty_cache_dir("ensure");
+    fs::create_dir_all(&dir).unwrap();
+    let (dev_srs, transcript) = dev_transcript(32);
+    let (url, server) = MockSrsServer::serve(transcript);
+    let dest = dir.join("transcript00.dat");
+    let g2_start = 28 + 32 * 64;
+
+    let written = ensure_transcript(Some(&url), &dest, 8).unwrap();
+    assert_eq!(written, 28 + 8 * 64 + 128);
+    assert_eq!(
+        taken_ranges(&server),
+        vec![(0, Some(28 + 8 * 64 - 1)), (g2_start, Some(g2_start + 127))]
+    );
+
+    // A transcript holding enough points is left untouched.
+    assert_eq!(ensure_transcript(Some(&url), &dest, 8).unwrap(), 0);
+    assert_eq!(ensure_transcript(Some(&url), &dest, 4).unwrap(), 0);
+    assert!(taken_ranges(&server).is_empty());
+
+    // A larger size fetches the manifest, the missing points and the G2 point.
+    let written = ensure_transcript(Some(&url), &dest, 16).unwrap();
+    assert_eq!(written, 28 + 8 * 64 + 128);
+    assert_eq!(
+        taken_ranges(&server),
+        vec![
+            (0, Some(27)),
+            (28 + 8 * 64, Some(28 + 16 * 64 - 1)),
+            (g2_start, Some(g2_start + 127))
+        ]
+    );
+    let mut srs = LocalSrs::new(&dest);
+    srs.load_data(16).unwrap();
+    assert_eq!(srs.g1_data()[..], dev_srs.g1_data()[..16 * 64]);
+    assert_eq!(srs.g2_data(), dev_srs.g2_data());
+
+    // Concurrent calls download the transcript once.
+    let dest = dir.join("concurrent.dat");
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let (url, dest) = (url.clone(), dest.clone());
+            thread::spawn(move || ensure_transcript(Some(&url), &dest, 24).unwrap())
+        })
+        .collect();
+    let written: u64 = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+    assert_eq!(written, 28 + 24 * 64 + 128);
+    assert_eq!(taken_ranges(&server).len(), 2);
+    LocalSrs::new(&dest).load_data(24).unwrap();
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_downloads_reuse_connections() {
+    let (dev_srs, transcript) = dev_transcript(32);
+    let (url, server) =
+        MockSrsServer::serve(MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data()));
+
+    // The probe for the first point, the remaining G1 points and the G2 point.
+    let mut srs = NetSrs::with_url(&url).with_concurrency(1);
+    srs.load_data(16).unwrap();
+    assert_eq!(srs.g1_data(), &dev_srs.g1_data()[..16 * 64]);
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(server.connections(), 1);
+
+    // Extending a transcript fetches its manifest, the missing points and the G2 point.
+    let (url, server) = MockSrsServer::serve(transcript);
+    let dir = empty_cache_dir("reuse");
+    fs::create_dir_all(&dir).unwrap();
+    let dest = dir.join("transcript00.dat");
+    ensure_transcript(Some(&url), &dest, 8).unwrap();
+    ensure_transcript(Some(&url), &dest, 16).unwrap();
+    assert_eq!(server.requests().len(), 2 + 3);
+    assert_eq!(server.connections(), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_download_srs_renames_on_success() {
+    let dir = empty_cache_dir("download_rename");
+    fs::create_dir_all(&dir).unwrap();
+    let (_, transcript) = dev_transcript(32);
+    let dest = dir.join("transcript00.dat");
+    let part = dir.join("transcript00.dat.part");
+
+    // An interrupted download leaves the partial file and no transcript.
+    let (failing_url, _failing) =
+        MockSrsServer::builder(transcript.clone()).truncate_at(700).serve();
+    assert!(download_srs(Some(&failing_url), &dest, None).is_err());
+    assert!(!dest.exists());
+    assert_eq!(fs::read(&part).unwrap(), transcript[..700]);
+
+    // The download is completed from a server ignoring the range.
+    let (url, _server) = MockSrsServer::builder(transcript.clone()).ignore_range().serve();
+    let written = download_srs(Some(&url), &dest, None).unwrap();
+    assert_eq!(written, transcript.len() as u64 - 700);
+    assert_eq!(fs::read(&dest).unwrap(), transcript);
+    assert!(!part.exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_netsrs_reads_range_from_full_body() {
+    let transcript = expected_range(0, 28 + 1000 * 64, 0);
+    let (url, server) = MockSrsServer::builder(transcript).ignore_range().serve();
+
+    // Grumpkin transcripts hold no G2 point, which the ignition layout places after 5040001 points.
+    let mut srs = NetSrs::with_url(&url).with_curve(Curve::Grumpkin);
+    srs.load_data(100).unwrap();
+    assert_eq!(srs.g1_data(), &expected_range(28, 100 * 64, 0));
+    // The probe for the first point and a single request for all the points.
+    assert_eq!(server.take_requests().len(), 2);
+
+    assert!(matches!(
+        srs.load_data(1001),
+        Err(SrsError::InsufficientPoints { available: 1000, requested: 1001, .. })
+    ));
+}
+
+#[test]
+fn test_netsrs_full_body_budget() {
+    let transcript = expected_range(0, 28 + 1000 * 64, 0);
+    let (url, _server) = MockSrsServer::builder(transcript).ignore_range().serve();
+
+    let mut srs =
+        NetSrs::with_url(&url).with_curve(Curve::Grumpkin).with_full_body_budget(28 + 50 * 64);
+    srs.load_data(50).unwrap();
+    assert_eq!(srs.g1_data(), &expected_range(28, 50 * 64, 0));
+    assert!(matches!(
+        srs.load_data(51),
+        Err(SrsError::RangeNotSupported { required, budget })
+            if required == 28 + 51 * 64 && budget == 28 + 50 * 64
+    ));
+
+    // The G2 point of a bn254 transcript lies past the budget.
+    let mut srs = NetSrs::with_url(&url).with_full_body_budget(1 << 20);
+    assert!(matches!(srs.load_data(10), Err(SrsError::RangeNotSupported { .. })));
+}
+
+#[test]
+fn test_netsrs_rejects_mismatched_ranges() {
+    let mut dev_srs = DevSrs::new(64);
+    dev_srs.load_data(64).unwrap();
+    let transcript = MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data());
+    let (short_url, short_server) =
+        MockSrsServer::builder(transcript.clone()).short_ranges(32).serve();
+
+    // The probe for the first point is answered with half of it.
+    let error = NetSrs::with_url(&short_url).load_data(64).unwrap_err();
+    assert!(
+        matches!(
+            &error,
+            SrsError::RangeMismatch { url, start: 28, end: 91, received }
+                if *url == short_url && received == "Content-Range bytes 28-59/322560220"
+        ),
+        "{:?}",
+        error
+    );
+    // The mismatch is not retried, as the proxy would serve the same range again.
+    assert_eq!(short_server.take_requests().len(), 1);
+
+    // The next mirror serves the range.
+    let (url, _server) = MockSrsServer::serve(transcript);
+    let mut srs = NetSrs::with_mirrors([&short_url, &url]);
+    srs.load_data(64).unwrap();
+    assert_eq!(srs.g1_data(), dev_srs.g1_data());
+    assert_eq!(srs.g2_data(), dev_srs.g2_data());
+}
+
+#[test]
+fn test_netsrs_restarts_when_the_transcript_changes() {
+    let mut dev_srs = DevSrs::new(64);
+    dev_srs.load_data(64).unwrap();
+    let transcript = MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data());
+
+    // One of the two chunks is served from a newer transcript than the first point, so the points
+    // are downloaded again.
+    let (url, server) =
+        MockSrsServer::builder(transcript.clone()).etags(["v1", "v1", "v2"]).serve();
+    let mut srs = NetSrs::with_url(&url).with_concurrency(2);
+    srs.load_data(64).unwrap();
+    assert_eq!(srs.g1_data(), dev_srs.g1_data());
+    assert_eq!(srs.validator(), Some(&Validator::ETag(String::from("\"v2\""))));
+    let probes =
+        server.requests().into_iter().filter(|request| request.range == Some((28, Some(91))));
+    assert_eq!(probes.count(), 2);
+
+    // A transcript changing with every request fails the download after a few attempts.
+    let (url, server) = MockSrsServer::builder(transcript)
+        .etags((0..10).map(|version| format!("v{}", version)))
+        .serve();
+    let error = NetSrs::with_url(&url).with_concurrency(2).load_data(64).unwrap_err();
+    assert!(
+        matches!(&error, SrsError::TranscriptChanged { url: error_url } if *error_url == url),
+        "{:?}",
+        error
+    );
+    assert_eq!(server.requests().len(), 9);
+}
+
+#[test]
+fn test_netsrs_with_url_provider() {
+    let mut dev_srs = DevSrs::new(64);
+    dev_srs.load_data(64).unwrap();
+    let transcript = MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data());
+    let (url, server) = MockSrsServer::builder(transcript).signed_ranges().serve();
+
+    // A URL signed for one range is rejected for the others, and its signature is not reported.
+    let signed_url = format!("{}?X-Goog-Signature=28-91&X-Goog-Expires=900", url);
+    let error = NetSrs::with_url(&signed_url).load_data(64).unwrap_err();
+    assert!(
+        matches!(&error, SrsError::UnexpectedResponse { status: StatusCode::FORBIDDEN, url: error_url, .. } if *error_url == url),
+        "{:?}",
+        error
+    );
+    assert!(!error.to_string().contains("X-Goog-Signature"), "{}", error);
+    assert!(!format!("{:?}", error).contains("28-91"), "{:?}", error);
+    server.take_requests();
+
+    let provider_url = url.clone();
+    let mut srs = NetSrs::with_url_provider(move |range| {
+        format!("{}?X-Goog-Signature={}-{}", provider_url, range.start, range.end - 1)
+    })
+    .with_concurrency(2);
+    srs.load_data(64).unwrap();
+    assert_eq!(srs.g1_data(), dev_srs.g1_data());
+    assert_eq!(srs.g2_data(), dev_srs.g2_data());
+    // The probe, two G1 chunks and the G2 point, each with its own URL.
+    let paths: HashSet<String> =
+        server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(paths.len(), 4);
+}
+
+#[test]
+fn test_mock_srs_server_injects_failures() {
+    let mut dev_srs = DevSrs::new(64);
+    dev_srs.load_data(64).unwrap();
+    let transcript = MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data());
+
+    // Each of the two failures is retried.
+    let (url, server) = MockSrsServer::builder(transcript.clone()).failures(2).serve();
+    let mut srs = NetSrs::with_url(&url).with_concurrency(2);
+    srs.load_data(64).unwrap();
+    assert_eq!(srs.g1_data(), dev_srs.g1_data());
+    assert_eq!(srs.g2_data(), dev_srs.g2_data());
+    let requests = server.requests();
+    // The probe, two G1 chunks, the G2 point and the two failed attempts.
+    assert_eq!(requests.len(), 6);
+    assert!(requests.iter().all(|request| request.method == "GET"));
+    assert!(requests.iter().any(|request| request.range == Some((28, Some(28 + 63)))));
+    assert_eq!(server.take_requests(), requests);
+    assert!(server.requests().is_empty());
+
+    // Truncated bodies fail every attempt.
+    let (url, _server) = MockSrsServer::builder(transcript.clone()).truncate_at(10).serve();
+    assert!(NetSrs::with_url(&url).load_data(64).is_err());
+
+    let (url, _server) =
+        MockSrsServer::builder(transcript).delay(Duration::from_millis(200)).serve();
+    let start = Instant::now();
+    NetSrs::with_url(&url).load_data(1).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(400));
+}
@@ -1,5 +1,82 @@
+use acvm::FieldElement;
+use noir_rs_barretenberg::{schnorr, BackendError};
+
 use super::{BlackboxSolver, RuntimeError};
-use noir_rs_barretenberg::schnorr;
+
+/// A Schnorr signature over the Grumpkin curve, as checked by the `schnorr_verify` black box
+/// function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchnorrSignature {
+    pub s: [u8; 32],
+    pub e: [u8; 32],
+}
+
+impl SchnorrSignature {
+    /// Returns the signature as the 64 bytes `s || e` taken by the `signature` input of
+    /// `schnorr_verify`, one byte per witness.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.s);
+        bytes[32..].copy_from_slice(&self.e);
+        bytes
+    }
+
+    /// Reads a signature from the 64 bytes `s || e`, as returned by [`SchnorrSignature::to_bytes`].
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        let (s, e) = bytes.split_at(32);
+        SchnorrSignature { s: s.try_into().unwrap(), e: e.try_into().unwrap() }
+    }
+}
+
+/// Derives the Grumpkin public key of a private key, as taken by the `public_key_x` and
+/// `public_key_y` inputs of `schnorr_verify`.
+/// # Arguments
+/// * `private_key` - The private key, a Grumpkin scalar.
+/// # Returns
+/// * `Result<(FieldElement, FieldElement), BackendError>` - Returns the coordinates of the public
+///   key or a BackendError.
+pub fn derive_public_key(
+    private_key: FieldElement,
+) -> Result<(FieldElement, FieldElement), BackendError> {
+    let public_key = schnorr::compute_public_key(&private_key.to_be_bytes())?;
+    Ok((
+        FieldElement::from_be_bytes_reduce(&public_key[..32]),
+        FieldElement::from_be_bytes_reduce(&public_key[32..]),
+    ))
+}
+
+/// Signs a message with the scheme the `schnorr_verify` black box function checks in a circuit,
+/// so that the signature satisfies the circuit for the public key of `private_key`.
+/// # Arguments
+/// * `message` - The message to sign, given to the circuit as one byte per witness.
+/// * `private_key` - The private key, a Grumpkin scalar.
+/// # Returns
+/// * `Result<SchnorrSignature, BackendError>` - Returns the signature or a BackendError.
+pub fn sign(message: &[u8], private_key: FieldElement) -> Result<SchnorrSignature, BackendError> {
+    let private_key: [u8; 32] = private_key.to_be_bytes().try_into().unwrap();
+    let (s, e) = schnorr::construct_signature(message, &private_key)?;
+    Ok(SchnorrSignature { s, e })
+}
+
+/// Verifies a signature the way the `schnorr_verify` black box function does when executing a
+/// circuit.
+/// # Arguments
+/// * `public_key_x` - The x coordinate of the public key, see [`derive_public_key`].
+/// * `public_key_y` - The y coordinate of the public key.
+/// * `signature` - The signature to verify.
+/// * `message` - The signed message.
+/// # Returns
+/// * `Result<bool, BackendError>` - Returns whether the signature is valid, or a BackendError.
+pub fn verify(
+    public_key_x: &FieldElement,
+    public_key_y: &FieldElement,
+    signature: &SchnorrSignature,
+    message: &[u8],
+) -> Result<bool, BackendError> {
+    let public_key: Vec<u8> =
+        public_key_x.to_be_bytes().into_iter().chain(public_key_y.to_be_bytes()).collect();
+    schnorr::verify_signature(message, public_key.try_into().unwrap(), signature.s, signature.e)
+}
 
 /// Trait defining the operations associated with the Schnorr signature scheme.
 pub(crate) trait SchnorrSig {
@@ -60,7 +137,7 @@ impl SchnorrSig for BlackboxSolver {
         message: &[u8],
         private_key: [u8; 32],
     ) -> Result<([u8; 32], [u8; 32]), RuntimeError> {
-        let (sig_s, sig_e) = unsafe { schnorr::construct_signature(message, &private_key) }?;
+        let (sig_s, sig_e) = schnorr::construct_signature(message, &private_key)?;
         Ok((sig_s, sig_e))
     }
 
@@ -77,7 +154,7 @@ impl SchnorrSig for BlackboxSolver {
         sig_e: [u8; 32],
         message: &[u8],
     ) -> Result<bool, RuntimeError> {
-        let verified = unsafe { schnorr::verify_signature(message, pub_key, sig_s, sig_e) }?;
+        let verified = schnorr::verify_signature(message, pub_key, sig_s, sig_e)?;
 
         // Note: If the signature verification fails for Barretenberg plonk,
         // then the entire circuit evaluation fails.
@@ -1,7 +1,4 @@
-use noir_rs::{
-    native_types::{Witness, WitnessMap},
-    FieldElement,
-};
+use noir_rs::prelude::*;
 use serde_json::Value;
 use std::fs;
 
@@ -20,7 +17,7 @@ fn main() {
     initial_witness.insert(Witness(3), FieldElement::from(5_i128));
     initial_witness.insert(Witness(4), FieldElement::from(7_i128));
     println!("Generating proof...");
-    let (proof, vk) = noir_rs::prove(String::from(bytecode), initial_witness).unwrap();
+    let (proof, vk) = noir_rs::prove(bytecode, &initial_witness).unwrap();
     println!("Verifying proof...");
     let verdict = noir_rs::verify(String::from(bytecode), proof, vk).unwrap();
     assert!(verdict);
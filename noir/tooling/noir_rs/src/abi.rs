@@ -0,0 +1,160 @@
+use std::{collections::BTreeMap, fmt};
+
+use acir::{
+    circuit::{opcodes::BlackBoxFuncCall, Circuit, Opcode},
+    native_types::{Witness, WitnessMap},
+};
+use acvm::FieldElement;
+use noirc_abi::{errors::InputParserError, input_parser::Format, Abi, AbiType, InputMap};
+
+use crate::errors::NoirRsError;
+
+/// An input whose value does not fit in the number of bits it is declared with, as reported by
+/// [`validate_inputs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputViolation {
+    /// The input witness.
+    pub witness: Witness,
+    /// Number of bits the value must fit in.
+    pub num_bits: u32,
+    /// The value given for the witness.
+    pub value: FieldElement,
+}
+
+impl fmt::Display for InputViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "w{} = {} does not fit in {} bits", self.witness.0, self.value, self.num_bits)
+    }
+}
+
+/// Builds the initial witness of a circuit from the contents of a `Prover.toml` file.
+///
+/// The values are parsed and assigned to witnesses the same way `nargo execute` does: fields and
+/// integers are given as decimal or `0x` prefixed hex strings or as integers, and arrays, structs,
+/// tuples, booleans and strings follow the types declared in the ABI. A return value listed in
+/// the file is ignored, the solver computes it.
+///
+/// # Arguments
+/// * `abi` - The ABI of the circuit, as found in the artifact written by `nargo compile`.
+/// * `toml_str` - The contents of the `Prover.toml` file.
+///
+/// # Returns
+/// * `Result<WitnessMap, NoirRsError>` - Returns the initial witness or a NoirRsError naming the
+///   parameter that could not be read.
+pub fn witness_from_toml(abi: &Abi, toml_str: &str) -> Result<WitnessMap, NoirRsError> {
+    let mut inputs = InputMap::new();
+    for parameter in &abi.parameters {
+        // Parsing one parameter at a time attributes a failure to the key it was read from.
+        let parameter_abi = Abi {
+            parameters: vec![parameter.clone()],
+            param_witnesses: Default::default(),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        };
+        let mut parsed =
+            Format::Toml.parse(toml_str, &parameter_abi).map_err(|error| match error {
+                InputParserError::ParseInputMap(reason) => NoirRsError::InvalidToml(reason),
+                error => {
+                    NoirRsError::InvalidTomlValue { key: parameter.name.clone(), source: error }
+                }
+            })?;
+        inputs.append(&mut parsed);
+    }
+    Ok(abi.encode(&inputs, None)?)
+}
+
+/// Checks that the inputs of a circuit fit in the number of bits they are declared with, so that
+/// a value out of range, such as 300 for a `u8`, is reported before the circuit is executed
+/// instead of failing one of its constraints.
+///
+/// An input is declared with the width of its type in the ABI, where booleans take 1 bit and
+/// strings 8 bits per character, and with the width of the range constraints the circuit puts on
+/// it. Field inputs and inputs without a value are not checked.
+/// # Arguments
+/// * `circuit` - The circuit the inputs are given to.
+/// * `abi` - The ABI of the circuit, if it is known.
+/// * `witness` - The initial witness of the circuit.
+/// # Returns
+/// * `Result<(), Vec<InputViolation>>` - Returns the inputs that do not fit, in increasing
+///   witness order.
+pub fn validate_inputs(
+    circuit: &Circuit,
+    abi: Option<&Abi>,
+    witness: &WitnessMap,
+) -> Result<(), Vec<InputViolation>> {
+    let parameters = circuit.circuit_arguments();
+    let mut widths: BTreeMap<Witness, u32> = BTreeMap::new();
+    let mut declare = |witness: Witness, num_bits: u32| {
+        if parameters.contains(&witness) {
+            let width = widths.entry(witness).or_insert(num_bits);
+            *width = (*width).min(num_bits);
+        }
+    };
+
+    for opcode in &circuit.opcodes {
+        if let Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) = opcode {
+            declare(input.witness, input.num_bits);
+        }
+    }
+    if let Some(abi) = abi {
+        for parameter in &abi.parameters {
+            let mut parameter_widths = Vec::new();
+            type_widths(&parameter.typ, &mut parameter_widths);
+            // The values of a parameter are laid out over its witness ranges in order.
+            let witnesses = abi
+                .param_witnesses
+                .get(&parameter.name)
+                .into_iter()
+                .flatten()
+                .flat_map(|range| (range.start.0..range.end.0).map(Witness));
+            for (witness, num_bits) in witnesses.zip(parameter_widths) {
+                if let Some(num_bits) = num_bits {
+                    declare(witness, num_bits);
+                }
+            }
+        }
+    }
+
+    let violations: Vec<InputViolation> = widths
+        .into_iter()
+        .filter_map(|(input, num_bits)| {
+            let value = *witness.get(&input)?;
+            (value.num_bits() > num_bits).then_some(InputViolation {
+                witness: input,
+                num_bits,
+                value,
+            })
+        })
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Appends the width of each value of `typ`, flattened the way the ABI encodes it, or `None` for
+/// a field.
+fn type_widths(typ: &AbiType, widths: &mut Vec<Option<u32>>) {
+    match typ {
+        AbiType::Field => widths.push(None),
+        AbiType::Integer { width, .. } => widths.push(Some(*width)),
+        AbiType::Boolean => widths.push(Some(1)),
+        AbiType::String { length } => widths.extend((0..*length).map(|_| Some(8))),
+        AbiType::Array { length, typ } => {
+            for _ in 0..*length {
+                type_widths(typ, widths);
+            }
+        }
+        AbiType::Struct { fields, .. } => {
+            for (_, typ) in fields {
+                type_widths(typ, widths);
+            }
+        }
+        AbiType::Tuple { fields } => {
+            for typ in fields {
+                type_widths(typ, widths);
+            }
+        }
+    }
+}
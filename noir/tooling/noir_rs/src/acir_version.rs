@@ -0,0 +1,146 @@
+use std::io;
+
+use acir::circuit::Circuit;
+use noir_rs_barretenberg::BACKEND_COMMIT;
+
+use crate::errors::NoirRsError;
+
+/// The nargo release whose circuits this build reads.
+pub const NARGO_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The version of the vendored acir crate, whose `Circuit` serialization this build reads.
+pub const ACIR_VERSION: &str = "0.35.0";
+
+/// The versions a proof is produced with, to tell which build produced a proof that does not
+/// verify elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendInfo {
+    /// The barretenberg sources this build links, see [`BACKEND_COMMIT`].
+    pub backend_commit: &'static str,
+    /// The acir version of the circuits this build reads, see [`ACIR_VERSION`].
+    pub acir_version: &'static str,
+    /// The version of this crate.
+    pub crate_version: &'static str,
+}
+
+/// Returns the versions of the backend, acir and this crate, which are fixed when building.
+///
+/// Versioned proofs and verification keys record the backend commit too, see
+/// [`Proof::to_versioned_bytes`](crate::Proof::to_versioned_bytes).
+pub fn backend_info() -> BackendInfo {
+    BackendInfo {
+        backend_commit: BACKEND_COMMIT,
+        acir_version: ACIR_VERSION,
+        crate_version: env!("CARGO_PKG_VERSION"),
+    }
+}
+
+/// Number of `Opcode` variants a circuit header may start with, leaving room for ones added by
+/// later acir versions.
+const MAX_OPCODE_TAG: u32 = 16;
+/// Largest number of functions a `Program` is expected to hold.
+const MAX_PROGRAM_FUNCTIONS: u64 = 1 << 10;
+/// Largest expression width a `Circuit` is expected to be bounded to.
+const MAX_EXPRESSION_WIDTH: u64 = 1 << 16;
+
+/// Deserializes decompressed ACIR bytecode, telling a circuit written by another acir version
+/// apart from a corrupted one.
+///
+/// bincode carries no version marker, so when the bytecode does not deserialize, the known shapes
+/// later acir versions write are probed for. A match fails with
+/// `NoirRsError::UnsupportedAcirVersion`, anything else with the original error.
+pub(crate) fn deserialize_circuit(acir_buffer_uncompressed: &[u8]) -> Result<Circuit, NoirRsError> {
+    let error = match bincode::deserialize(acir_buffer_uncompressed) {
+        Ok(circuit) => return Ok(circuit),
+        Err(error) => error,
+    };
+    if let Some(detected) = detect_format(acir_buffer_uncompressed) {
+        return Err(NoirRsError::UnsupportedAcirVersion {
+            detected,
+            supported: format!("a bincode Circuit from acir {}", ACIR_VERSION),
+        });
+    }
+    // Reported as `Circuit::deserialize_circuit` does.
+    Err(io::Error::new(io::ErrorKind::InvalidInput, error).into())
+}
+
+/// Names the serialization shape of decompressed bytecode that is not a supported `Circuit`.
+fn detect_format(bytes: &[u8]) -> Option<String> {
+    // Later acir versions wrap circuits in a `Program` starting with the number of functions.
+    let mut reader = Reader::new(bytes);
+    if reader.u64().map_or(false, |functions| (1..=MAX_PROGRAM_FUNCTIONS).contains(&functions)) {
+        let circuit = reader.rest();
+        if is_circuit_header(circuit, false) || is_circuit_header(circuit, true) {
+            return Some(String::from("a bincode Program"));
+        }
+    }
+    // Later acir versions also write an `expression_width` after the witness index.
+    if is_circuit_header(bytes, true) {
+        return Some(String::from("a bincode Circuit with an expression width"));
+    }
+    // Recent nargo releases serialize programs with msgpack, possibly after a format marker byte.
+    let is_msgpack_map = |byte: &u8| matches!(byte, 0x80..=0x8f | 0xde | 0xdf);
+    match bytes {
+        [first, ..] if is_msgpack_map(first) => Some(String::from("a msgpack Program")),
+        [marker, second, ..] if *marker < 0x10 && is_msgpack_map(second) => {
+            Some(String::from("a msgpack Program"))
+        }
+        _ => None,
+    }
+}
+
+/// Checks whether `bytes` start like a bincode `Circuit`: a witness index, an optional expression
+/// width, and a plausible number of opcodes followed by a known opcode tag.
+fn is_circuit_header(bytes: &[u8], with_expression_width: bool) -> bool {
+    let mut reader = Reader::new(bytes);
+    if reader.u32().is_none() {
+        return false;
+    }
+    if with_expression_width {
+        match reader.u32() {
+            // ExpressionWidth::Unbounded
+            Some(0) => {}
+            // ExpressionWidth::Bounded { width }
+            Some(1) => match reader.u64() {
+                Some(width) if (1..=MAX_EXPRESSION_WIDTH).contains(&width) => {}
+                _ => return false,
+            },
+            _ => return false,
+        }
+    }
+    match reader.u64() {
+        Some(0) => true,
+        // Every opcode takes at least the 4 bytes of its tag.
+        Some(opcodes) if opcodes <= reader.rest().len() as u64 / 4 => {
+            reader.u32().map_or(false, |tag| tag < MAX_OPCODE_TAG)
+        }
+        _ => false,
+    }
+}
+
+/// Reads the little-endian integers bincode writes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let (value, rest) = self.bytes.split_at(4.min(self.bytes.len()));
+        self.bytes = rest;
+        Some(u32::from_le_bytes(value.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        let (value, rest) = self.bytes.split_at(8.min(self.bytes.len()));
+        self.bytes = rest;
+        Some(u64::from_le_bytes(value.try_into().ok()?))
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
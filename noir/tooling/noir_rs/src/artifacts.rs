@@ -0,0 +1,95 @@
+use std::{fs, path::Path};
+
+use crate::errors::NoirRsError;
+
+/// Writes a proof the way `nargo prove` does, as lowercase hex without a `0x` prefix or a trailing
+/// newline, so the file can be read by nargo and bb.js tooling.
+///
+/// # Arguments
+/// * `path` - The file to write, such as `proofs/<name>.proof`.
+/// * `proof` - The proof, as returned by [`prove`](crate::prove).
+///
+/// # Returns
+/// * `Result<(), NoirRsError>` - Returns a NoirRsError naming the file if it cannot be written.
+pub fn write_proof_file(path: impl AsRef<Path>, proof: &[u8]) -> Result<(), NoirRsError> {
+    write(path.as_ref(), hex::encode(proof).as_bytes())
+}
+
+/// Reads a proof written by [`write_proof_file`] or by `nargo prove`.
+///
+/// Surrounding whitespace and a `0x` prefix, as left by editors and some JavaScript tooling, are
+/// ignored.
+///
+/// # Arguments
+/// * `path` - The hex encoded proof file.
+///
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the proof, or a NoirRsError naming the file if it
+///   cannot be read or is not hex.
+pub fn read_proof_file(path: impl AsRef<Path>) -> Result<Vec<u8>, NoirRsError> {
+    let path = path.as_ref();
+    let contents = read(path)?;
+    let contents = String::from_utf8_lossy(&contents);
+    let contents = contents.trim();
+    let hex_proof = contents.strip_prefix("0x").unwrap_or(contents);
+    hex::decode(hex_proof)
+        .map_err(|source| NoirRsError::InvalidProofFile { path: path.to_path_buf(), source })
+}
+
+/// Writes a verification key as raw bytes, the layout of the `vk` file written by `bb write_vk`.
+///
+/// # Arguments
+/// * `path` - The file to write, such as `target/vk`.
+/// * `verification_key` - The verification key, as returned by [`prove`](crate::prove).
+///
+/// # Returns
+/// * `Result<(), NoirRsError>` - Returns a NoirRsError naming the file if it cannot be written.
+pub fn write_vk_file(path: impl AsRef<Path>, verification_key: &[u8]) -> Result<(), NoirRsError> {
+    write(path.as_ref(), verification_key)
+}
+
+/// Reads a verification key written by [`write_vk_file`] or by `bb write_vk`.
+///
+/// # Arguments
+/// * `path` - The raw verification key file.
+///
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the verification key, or a NoirRsError naming the
+///   file if it cannot be read.
+pub fn read_vk_file(path: impl AsRef<Path>) -> Result<Vec<u8>, NoirRsError> {
+    read(path.as_ref())
+}
+
+/// Writes a proving key as raw bytes, as returned by
+/// [`proving_key_with_srs`](crate::proving_key_with_srs).
+///
+/// # Arguments
+/// * `path` - The file to write, such as `target/pk`.
+/// * `proving_key` - The serialized proving key.
+///
+/// # Returns
+/// * `Result<(), NoirRsError>` - Returns a NoirRsError naming the file if it cannot be written.
+pub fn write_pk_file(path: impl AsRef<Path>, proving_key: &[u8]) -> Result<(), NoirRsError> {
+    write(path.as_ref(), proving_key)
+}
+
+/// Reads a proving key written by [`write_pk_file`].
+///
+/// # Arguments
+/// * `path` - The raw proving key file.
+///
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the proving key, or a NoirRsError naming the file
+///   if it cannot be read.
+pub fn read_pk_file(path: impl AsRef<Path>) -> Result<Vec<u8>, NoirRsError> {
+    read(path.as_ref())
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, NoirRsError> {
+    fs::read(path).map_err(|source| NoirRsError::File { path: path.to_path_buf(), source })
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<(), NoirRsError> {
+    fs::write(path, contents)
+        .map_err(|source| NoirRsError::File { path: path.to_path_buf(), source })
+}
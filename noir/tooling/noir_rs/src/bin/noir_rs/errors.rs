@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use noir_rs::NoirRsError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub(crate) enum CliError {
+    #[error("Failed to read {path}: {source}")]
+    ReadFile { path: PathBuf, source: std::io::Error },
+
+    #[error("Failed to read stdin: {0}")]
+    ReadStdin(std::io::Error),
+
+    #[error("{input} is empty, expected {expected}")]
+    EmptyInput { input: PathBuf, expected: &'static str },
+
+    #[error("Both --{first} and --{second} are read from stdin, which can only be read once")]
+    StdinReadTwice { first: &'static str, second: &'static str },
+
+    #[error("Failed to write {path}: {source}")]
+    WriteFile { path: PathBuf, source: std::io::Error },
+
+    #[error("Invalid witness file {path}: {reason}")]
+    InvalidWitness { path: PathBuf, reason: String },
+
+    #[error("Invalid artifact {path}: {reason}")]
+    InvalidArtifact { path: PathBuf, reason: String },
+
+    #[error(transparent)]
+    NoirRs(#[from] NoirRsError),
+}
+
+impl CliError {
+    /// Returns the `code` reported for the error in JSON output.
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            CliError::ReadFile { .. } => "read_file",
+            CliError::ReadStdin(_) => "read_stdin",
+            CliError::EmptyInput { .. } => "empty_input",
+            CliError::StdinReadTwice { .. } => "stdin_read_twice",
+            CliError::WriteFile { .. } => "write_file",
+            CliError::InvalidWitness { .. } => "invalid_witness",
+            CliError::InvalidArtifact { .. } => "invalid_artifact",
+            CliError::NoirRs(error) => error.code(),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use noir_rs::{prelude::WitnessMap, witness_from_json, NoirRsError};
+
+use crate::errors::CliError;
+
+pub(crate) fn read_file(path: &Path) -> Result<Vec<u8>, CliError> {
+    fs::read(path).map_err(|source| CliError::ReadFile { path: path.to_path_buf(), source })
+}
+
+pub(crate) fn write_file(path: &Path, bytes: &[u8]) -> Result<(), CliError> {
+    fs::write(path, bytes)
+        .map_err(|source| CliError::WriteFile { path: path.to_path_buf(), source })
+}
+
+/// Path standing for the standard input, as in `--bytecode -`.
+const STDIN_PATH: &str = "-";
+
+/// Returns the name of an input in error messages, `stdin` for [`STDIN_PATH`].
+fn input_name(path: &Path) -> PathBuf {
+    if path == Path::new(STDIN_PATH) {
+        PathBuf::from("stdin")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Reads a file, or the standard input to its end when `path` is `-`, rejecting input that is
+/// empty or only holds whitespace.
+fn read_input(path: &Path, expected: &'static str) -> Result<Vec<u8>, CliError> {
+    let bytes = if path == Path::new(STDIN_PATH) {
+        let mut bytes = Vec::new();
+        io::stdin().lock().read_to_end(&mut bytes).map_err(CliError::ReadStdin)?;
+        bytes
+    } else {
+        read_file(path)?
+    };
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Err(CliError::EmptyInput { input: input_name(path), expected });
+    }
+    Ok(bytes)
+}
+
+/// Checks that at most one of the inputs is read from the standard input, which can only be read
+/// once.
+pub(crate) fn check_single_stdin(inputs: &[(&'static str, &Path)]) -> Result<(), CliError> {
+    let mut stdin_inputs = inputs.iter().filter(|(_, path)| *path == Path::new(STDIN_PATH));
+    match (stdin_inputs.next(), stdin_inputs.next()) {
+        (Some((first, _)), Some((second, _))) => Err(CliError::StdinReadTwice { first, second }),
+        _ => Ok(()),
+    }
+}
+
+/// Reads base64 encoded, gzipped ACIR bytecode from a file, or from the standard input when `path`
+/// is `-`, ignoring surrounding whitespace such as a trailing newline.
+pub(crate) fn read_bytecode(path: &Path) -> Result<String, CliError> {
+    let bytecode = read_input(path, "base64 encoded bytecode")?;
+    let bytecode = String::from_utf8(bytecode).map_err(|error| {
+        let source = io::Error::new(io::ErrorKind::InvalidData, error);
+        CliError::ReadFile { path: input_name(path), source }
+    })?;
+    Ok(bytecode.trim().to_string())
+}
+
+/// Reads a witness from a JSON object mapping witness indices to field elements, such as
+/// `{"1": "0x00", "2": "1"}`.
+///
+/// Values are hexadecimal strings prefixed with `0x`, decimal strings or non-negative integers. The
+/// witness is read from the standard input when `path` is `-`.
+pub(crate) fn read_witness(path: &Path) -> Result<WitnessMap, CliError> {
+    let invalid = |reason: String| CliError::InvalidWitness { path: input_name(path), reason };
+
+    let json = String::from_utf8(read_input(path, "a JSON witness")?)
+        .map_err(|error| invalid(error.to_string()))?;
+    witness_from_json(&json).map_err(|error| match error {
+        NoirRsError::InvalidWitnessJson(reason) => invalid(reason),
+        error => error.into(),
+    })
+}
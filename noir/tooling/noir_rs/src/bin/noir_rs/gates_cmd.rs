@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use noir_rs::{
+    circuit_stats, decode_bytecode, decompress_bytecode, get_circuit_sizes, NoirRsError,
+};
+use serde_json::json;
+
+use crate::{errors::CliError, fs::read_bytecode};
+
+/// Prints the gate count and subgroup size of a circuit, without downloading the SRS
+#[derive(Debug, Clone, Args)]
+pub(crate) struct GatesCommand {
+    /// File holding the base64 encoded, gzipped ACIR bytecode, or `-` to read it from stdin
+    #[arg(long)]
+    bytecode: PathBuf,
+}
+
+pub(crate) fn run(args: GatesCommand, json: bool) -> Result<(), CliError> {
+    let bytecode = read_bytecode(&args.bytecode)?;
+    let stats = circuit_stats(&bytecode)?;
+    let acir_buffer_uncompressed = decompress_bytecode(&decode_bytecode(&bytecode)?)?;
+    let circuit_sizes = get_circuit_sizes(&acir_buffer_uncompressed).map_err(NoirRsError::from)?;
+    let log2_subgroup = circuit_sizes.subgroup.trailing_zeros();
+
+    if json {
+        let gates = json!({
+            "opcodes": stats.total_opcodes(),
+            "exact_gates": circuit_sizes.exact,
+            "total_gates": circuit_sizes.total,
+            "subgroup_size": circuit_sizes.subgroup,
+            "log2_subgroup_size": log2_subgroup,
+        });
+        println!("{gates}");
+    } else {
+        println!("opcodes: {}", stats.total_opcodes());
+        println!("exact gates: {}", circuit_sizes.exact);
+        println!("total gates: {}", circuit_sizes.total);
+        println!("subgroup: {} (2^{log2_subgroup})", circuit_sizes.subgroup);
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use noir_rs::{
+    backend_info, decode_bytecode, decompress_bytecode, get_circuit_sizes, proof_layout,
+    NoirRsError,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    errors::CliError,
+    fs::{read_bytecode, read_file},
+};
+
+/// Prints the sizes of a circuit reported by the backend, the size of its proofs, and the versions
+/// of the backend
+#[derive(Debug, Clone, Args)]
+pub(crate) struct InfoCommand {
+    /// File holding the base64 encoded, gzipped ACIR bytecode, or `-` to read it from stdin
+    #[arg(long, required_unless_present = "vk")]
+    bytecode: Option<PathBuf>,
+
+    /// File holding the verification key of the circuit, to print the size of its proofs
+    #[arg(long)]
+    vk: Option<PathBuf>,
+}
+
+pub(crate) fn run(args: InfoCommand, json: bool) -> Result<(), CliError> {
+    // Printed in this order as text, and as the fields of an object with `--json`.
+    let mut fields: Vec<(&str, Value)> = Vec::new();
+    if let Some(path) = &args.bytecode {
+        let bytecode = read_bytecode(path)?;
+        let acir_buffer = decode_bytecode(&bytecode)?;
+        let acir_buffer_uncompressed = decompress_bytecode(&acir_buffer)?;
+        let circuit_sizes =
+            get_circuit_sizes(&acir_buffer_uncompressed).map_err(NoirRsError::from)?;
+        fields.push(("exact", json!(circuit_sizes.exact)));
+        fields.push(("total", json!(circuit_sizes.total)));
+        fields.push(("subgroup", json!(circuit_sizes.subgroup)));
+    }
+    if let Some(path) = &args.vk {
+        let layout = proof_layout(&read_file(path)?)?;
+        fields.push(("num_public_inputs", json!(layout.num_public_inputs)));
+        fields.push(("proof_bytes_without_inputs", json!(layout.proof_bytes_without_inputs)));
+        fields.push(("proof_bytes", json!(layout.total_bytes)));
+    }
+    let info = backend_info();
+    fields.push(("backend_commit", json!(info.backend_commit)));
+    fields.push(("acir_version", json!(info.acir_version)));
+    fields.push(("crate_version", json!(info.crate_version)));
+
+    if json {
+        let object: serde_json::Map<String, Value> =
+            fields.into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+        println!("{}", Value::Object(object));
+    } else {
+        for (name, value) in fields {
+            let value = match value {
+                Value::String(value) => value,
+                value => value.to_string(),
+            };
+            println!("{}: {}", name.replace('_', " "), value);
+        }
+    }
+    Ok(())
+}
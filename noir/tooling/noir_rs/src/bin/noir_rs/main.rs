@@ -0,0 +1,73 @@
+//! Command line interface to prove and verify ACIR circuits with noir_rs.
+//!
+//! Exits with 0 on success, 1 when `verify` rejects the proof and 2 on any error, which is
+//! printed to stderr. With `--json`, results and errors are printed to stdout as JSON objects
+//! instead, errors as `{"error": {"code": ..., "message": ...}}`.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde_json::json;
+
+mod errors;
+mod fs;
+mod srs;
+
+mod gates_cmd;
+mod info_cmd;
+mod prove_cmd;
+mod verify_cmd;
+mod witness_cmd;
+
+/// Exit code of a proof the verifier rejected.
+const EXIT_INVALID_PROOF: u8 = 1;
+/// Exit code of a command that failed.
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Parser, Debug)]
+#[command(name = "noir_rs", version, about, long_about = None)]
+struct NoirRsCli {
+    #[command(subcommand)]
+    command: NoirRsCommand,
+
+    /// Print results and errors as JSON objects
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+enum NoirRsCommand {
+    Prove(prove_cmd::ProveCommand),
+    Verify(verify_cmd::VerifyCommand),
+    Info(info_cmd::InfoCommand),
+    Gates(gates_cmd::GatesCommand),
+    Witness(witness_cmd::WitnessCommand),
+}
+
+fn main() -> ExitCode {
+    let NoirRsCli { command, json } = NoirRsCli::parse();
+
+    let result = match command {
+        NoirRsCommand::Prove(args) => prove_cmd::run(args, json).map(|()| true),
+        NoirRsCommand::Verify(args) => verify_cmd::run(args, json),
+        NoirRsCommand::Info(args) => info_cmd::run(args, json).map(|()| true),
+        NoirRsCommand::Gates(args) => gates_cmd::run(args, json).map(|()| true),
+        NoirRsCommand::Witness(args) => witness_cmd::run(args, json).map(|()| true),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_INVALID_PROOF),
+        Err(error) if json => {
+            println!(
+                "{}",
+                json!({ "error": { "code": error.code(), "message": error.to_string() } })
+            );
+            ExitCode::from(EXIT_ERROR)
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::{path::PathBuf, time::Instant};
+
+use clap::Args;
+use noir_rs::{ProveOptions, SrsSource};
+use serde_json::json;
+
+use crate::{
+    errors::CliError,
+    fs::{check_single_stdin, read_bytecode, read_witness, write_file},
+    srs::parse_srs_source,
+};
+
+/// Executes a circuit and writes a proof of the execution and the verification key
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ProveCommand {
+    /// File holding the base64 encoded, gzipped ACIR bytecode, or `-` to read it from stdin
+    #[arg(long)]
+    bytecode: PathBuf,
+
+    /// JSON file mapping witness indices to the initial witness values, or `-` to read it from
+    /// stdin
+    #[arg(long)]
+    witness: PathBuf,
+
+    /// Ignition transcript, `bn254_g1.dat` file or flat CRS directory to read the SRS from, or
+    /// URL of a transcript mirror to download it from.
+    /// The ignition transcript is downloaded when omitted
+    #[arg(long, visible_alias = "srs-path", value_parser = parse_srs_source)]
+    srs: Option<SrsSource>,
+
+    /// File the proof is written to
+    #[arg(long)]
+    out: PathBuf,
+
+    /// File the verification key is written to
+    #[arg(long)]
+    vk_out: PathBuf,
+}
+
+pub(crate) fn run(args: ProveCommand, json: bool) -> Result<(), CliError> {
+    check_single_stdin(&[("bytecode", &args.bytecode), ("witness", &args.witness)])?;
+    let bytecode = read_bytecode(&args.bytecode)?;
+    let initial_witness = read_witness(&args.witness)?;
+
+    let mut options = ProveOptions::new();
+    if let Some(srs) = args.srs {
+        options = options.srs_source(srs);
+    }
+    let start = Instant::now();
+    let (proof, verification_key) = options.prove(&bytecode, &initial_witness)?;
+    let elapsed = start.elapsed();
+
+    write_file(&args.out, &proof)?;
+    write_file(&args.vk_out, &verification_key)?;
+    if json {
+        let output = json!({
+            "proof": args.out,
+            "vk": args.vk_out,
+            "proof_size": proof.len(),
+            "elapsed_ms": elapsed.as_millis() as u64,
+        });
+        println!("{output}");
+    }
+    Ok(())
+}
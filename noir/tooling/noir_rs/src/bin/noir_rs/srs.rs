@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use noir_rs::SrsSource;
+
+/// Parses the value of `--srs`: an `http://` or `https://` URL is read as a transcript mirror,
+/// anything else as a local path.
+pub(crate) fn parse_srs_source(value: &str) -> Result<SrsSource, String> {
+    if value.is_empty() {
+        return Err(String::from("expected a path or a URL"));
+    }
+    Ok(if value.starts_with("http://") || value.starts_with("https://") {
+        SrsSource::Url(value.to_string())
+    } else {
+        SrsSource::Local(PathBuf::from(value))
+    })
+}
@@ -0,0 +1,57 @@
+use std::{path::PathBuf, time::Instant};
+
+use clap::Args;
+use noir_rs::{SrsSource, Verifier, VerifyOutcome};
+use serde_json::json;
+
+use crate::{errors::CliError, fs::read_file, srs::parse_srs_source};
+
+/// Verifies a proof against a verification key
+#[derive(Debug, Clone, Args)]
+pub(crate) struct VerifyCommand {
+    /// File holding the proof
+    #[arg(long)]
+    proof: PathBuf,
+
+    /// File holding the verification key
+    #[arg(long)]
+    vk: PathBuf,
+
+    /// Path or URL of the SRS the proof was generated with, of which only the G2 point is read.
+    /// The G2 point of the ignition transcript is used when omitted
+    #[arg(long, visible_alias = "srs-path", value_parser = parse_srs_source)]
+    srs: Option<SrsSource>,
+}
+
+/// Returns whether the proof is valid.
+pub(crate) fn run(args: VerifyCommand, json: bool) -> Result<bool, CliError> {
+    let proof = read_file(&args.proof)?;
+    let verification_key = read_file(&args.vk)?;
+
+    let verifier = match args.srs {
+        Some(srs) => Verifier::from_vk_with_srs(&verification_key, srs.into_srs())?,
+        None => Verifier::from_vk(&verification_key)?,
+    };
+    let start = Instant::now();
+    let outcome = verifier.verify_detailed(&proof);
+    let elapsed = start.elapsed();
+    let reason = match &outcome {
+        VerifyOutcome::Valid => None,
+        VerifyOutcome::Invalid { reason } => Some(reason),
+    };
+
+    if json {
+        let output = json!({
+            "valid": reason.is_none(),
+            "reason": reason,
+            "elapsed_ms": elapsed.as_millis() as u64,
+        });
+        println!("{output}");
+    } else {
+        match reason {
+            None => println!("Proof is valid"),
+            Some(reason) => println!("Proof is invalid: {reason}"),
+        }
+    }
+    Ok(reason.is_none())
+}
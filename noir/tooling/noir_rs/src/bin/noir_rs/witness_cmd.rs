@@ -0,0 +1,53 @@
+use std::{fs, io, path::PathBuf};
+
+use clap::Args;
+use noir_rs::{witness_from_toml, Abi, NoirRsError};
+use serde_json::{json, Value};
+
+use crate::{
+    errors::CliError,
+    fs::{read_file, write_file},
+};
+
+/// Builds the initial witness of a circuit from the inputs in a Prover.toml file
+#[derive(Debug, Clone, Args)]
+pub(crate) struct WitnessCommand {
+    /// Artifact written by `nargo compile`, whose ABI maps the inputs to witness indices
+    #[arg(long)]
+    artifact: PathBuf,
+
+    /// Prover.toml file holding the values of the circuit inputs
+    #[arg(long)]
+    prover_toml: PathBuf,
+
+    /// File the gzipped witness is written to, as `nargo execute` writes it
+    #[arg(long)]
+    out: PathBuf,
+}
+
+pub(crate) fn run(args: WitnessCommand, json: bool) -> Result<(), CliError> {
+    let invalid_artifact =
+        |reason: String| CliError::InvalidArtifact { path: args.artifact.clone(), reason };
+    let artifact: Value = serde_json::from_slice(&read_file(&args.artifact)?)
+        .map_err(|error| invalid_artifact(error.to_string()))?;
+    let abi = match artifact.get("abi") {
+        Some(abi) => serde_json::from_value::<Abi>(abi.clone())
+            .map_err(|error| invalid_artifact(error.to_string()))?,
+        None => return Err(invalid_artifact(String::from("missing the abi field"))),
+    };
+    let toml_str = fs::read_to_string(&args.prover_toml)
+        .map_err(|source| CliError::ReadFile { path: args.prover_toml.clone(), source })?;
+
+    let witness = witness_from_toml(&abi, &toml_str)?;
+    let num_witnesses = witness.clone().into_iter().count();
+    let witness_file: Vec<u8> = witness
+        .try_into()
+        .map_err(|error| NoirRsError::from(io::Error::new(io::ErrorKind::InvalidData, error)))?;
+    write_file(&args.out, &witness_file)?;
+
+    if json {
+        let output = json!({ "witness": args.out, "num_witnesses": num_witnesses });
+        println!("{output}");
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use std::io::Read;
+
+use acir::circuit::Circuit;
+use base64::{
+    engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    DecodeError, Engine,
+};
+use flate2::bufread::{GzDecoder, ZlibDecoder};
+
+use crate::{acir_version, errors::NoirRsError};
+
+/// The standard alphabet, reading padded and unpadded input alike.
+const LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &base64::alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Decodes base64 encoded circuit bytecode, as copied out of a nargo artifact or a shell pipe.
+///
+/// ASCII whitespace, such as line breaks and a trailing newline, is ignored, the standard and the
+/// URL-safe alphabets are both accepted, and the trailing `=` padding may be missing. Input mixing
+/// both alphabets is rejected.
+///
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+///
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the gzipped bytecode, or
+///   `NoirRsError::InvalidBytecodeCharacter` naming the byte offset of the first character that is
+///   not part of the encoding.
+pub fn decode_bytecode(circuit_bytecode: &str) -> Result<Vec<u8>, NoirRsError> {
+    // Fast path for bytecode as nargo writes it.
+    if let Ok(bytes) = general_purpose::STANDARD.decode(circuit_bytecode) {
+        return Ok(bytes);
+    }
+
+    // Offsets in `circuit_bytecode` of the characters kept in `normalized`.
+    let mut offsets = Vec::with_capacity(circuit_bytecode.len());
+    let mut normalized = Vec::with_capacity(circuit_bytecode.len());
+    let mut url_safe = None;
+    for (offset, character) in circuit_bytecode.char_indices() {
+        let invalid = || NoirRsError::InvalidBytecodeCharacter { position: offset, character };
+        let byte = match character {
+            _ if character.is_ascii_whitespace() => continue,
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '=' => character as u8,
+            '+' | '/' if url_safe != Some(true) => {
+                url_safe = Some(false);
+                character as u8
+            }
+            '-' | '_' if url_safe != Some(false) => {
+                url_safe = Some(true);
+                if character == '-' {
+                    b'+'
+                } else {
+                    b'/'
+                }
+            }
+            _ => return Err(invalid()),
+        };
+        offsets.push(offset);
+        normalized.push(byte);
+    }
+
+    LENIENT.decode(&normalized).map_err(|error| match error {
+        DecodeError::InvalidByte(index, _) | DecodeError::InvalidLastSymbol(index, _) => {
+            let position = offsets[index];
+            // Only ASCII characters were kept, so the character starts and ends at `position`.
+            let character = circuit_bytecode.as_bytes()[position] as char;
+            NoirRsError::InvalidBytecodeCharacter { position, character }
+        }
+        error => error.into(),
+    })
+}
+
+#[cfg(test)]
+thread_local! {
+    /// The number of times [`decompress_bytecode`] ran on the current thread, which lets tests
+    /// check that proving decompresses the bytecode once.
+    pub(crate) static DECOMPRESSIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Decompresses decoded circuit bytecode into the serialized ACIR the backend reads.
+///
+/// nargo gzips the bytecode, but some tooling compresses it with zlib instead, both are read. The
+/// compression is told apart by the leading bytes, so that raw or otherwise compressed bytecode is
+/// reported as such instead of failing to decompress.
+///
+/// # Arguments
+/// * `acir_buffer` - Gzip or zlib compressed ACIR bytecode, as returned by [`decode_bytecode`].
+///
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the serialized ACIR, or
+///   `NoirRsError::UnknownBytecodeCompression` if the bytecode is neither gzip nor zlib compressed.
+pub fn decompress_bytecode(acir_buffer: &[u8]) -> Result<Vec<u8>, NoirRsError> {
+    #[cfg(test)]
+    DECOMPRESSIONS.with(|count| count.set(count.get() + 1));
+    let mut acir_buffer_uncompressed = Vec::new();
+    match acir_buffer {
+        [0x1f, 0x8b, ..] => {
+            GzDecoder::new(acir_buffer).read_to_end(&mut acir_buffer_uncompressed)?
+        }
+        // A deflate zlib header, `0x78` for the default window size, whose check bits make it a
+        // multiple of 31.
+        [cmf, flg, ..] if cmf & 0x0f == 8 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0 => {
+            ZlibDecoder::new(acir_buffer).read_to_end(&mut acir_buffer_uncompressed)?
+        }
+        _ => {
+            return Err(NoirRsError::UnknownBytecodeCompression {
+                prefix: acir_buffer.iter().take(4).copied().collect(),
+            })
+        }
+    };
+    Ok(acir_buffer_uncompressed)
+}
+
+/// A circuit read from its bytecode, along with the serialized ACIR the backend reads.
+pub struct DecodedCircuit {
+    /// The circuit the ACVM executes.
+    pub circuit: Circuit,
+    /// The decompressed ACIR the circuit was deserialized from, which the composer builds the
+    /// constraint system from.
+    pub raw_acir: Vec<u8>,
+}
+
+/// Decodes, decompresses and deserializes circuit bytecode.
+///
+/// The bytecode is decompressed once, and both the circuit and the serialized ACIR are returned,
+/// so that executing and proving a circuit do not decompress it twice.
+///
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+///
+/// # Returns
+/// * `Result<DecodedCircuit, NoirRsError>` - Returns the circuit and its serialized ACIR, or a
+///   NoirRsError.
+pub fn decode_circuit(circuit_bytecode: &str) -> Result<DecodedCircuit, NoirRsError> {
+    let raw_acir = decompress_bytecode(&decode_bytecode(circuit_bytecode)?)?;
+    let circuit = acir_version::deserialize_circuit(&raw_acir)?;
+    Ok(DecodedCircuit { circuit, raw_acir })
+}
@@ -0,0 +1,114 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use acir::native_types::{Witness, WitnessMap};
+use acvm::FieldElement;
+use noir_rs_barretenberg::srs::source::SrsSource;
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::NoirRsError, ProveOptions, ProveOutput};
+
+/// The SRS a [`ProveRequestDto`] is proven with, serialized as an object tagged by `kind`, such as
+/// `{"kind": "local", "path": "/srs/bn254_g1.dat"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SrsDto {
+    /// The default ignition transcript, see [`SrsSource::Default`].
+    #[default]
+    Default,
+    /// A transcript on disk, see [`SrsSource::Local`].
+    Local { path: PathBuf },
+    /// A transcript mirror, see [`SrsSource::Url`].
+    Url { url: String },
+}
+
+impl From<SrsDto> for SrsSource {
+    fn from(srs: SrsDto) -> Self {
+        match srs {
+            SrsDto::Default => SrsSource::Default,
+            SrsDto::Local { path } => SrsSource::Local(path),
+            SrsDto::Url { url } => SrsSource::Url(url),
+        }
+    }
+}
+
+/// A prove request as received by a service, such as
+/// `{"bytecode": "H4sI...", "inputs": {"1": "0x00", "2": "1"}, "srs": {"kind": "default"}}`.
+///
+/// Inputs map witness indices to field elements written as hexadecimal strings prefixed with `0x`
+/// or as decimal strings, which keeps values above 2^53 intact through JSON parsers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProveRequestDto {
+    /// Base64 encoded, gzipped ACIR bytecode.
+    pub bytecode: String,
+    /// The values of the initial witness, keyed by witness index.
+    pub inputs: BTreeMap<u32, String>,
+    /// The SRS to prove with, the default transcript when omitted.
+    #[serde(default)]
+    pub srs: SrsDto,
+}
+
+impl ProveRequestDto {
+    /// Parses the inputs into the initial witness of the circuit.
+    /// # Returns
+    /// * `Result<WitnessMap, NoirRsError>` - Returns the initial witness, or a
+    ///   `NoirRsError::InvalidInputValue` naming the first input that is not a field element.
+    pub fn initial_witness(&self) -> Result<WitnessMap, NoirRsError> {
+        let mut witness_map = WitnessMap::new();
+        for (&witness, value) in &self.inputs {
+            let field = FieldElement::try_from_str(value)
+                .ok_or_else(|| NoirRsError::InvalidInputValue { witness, value: value.clone() })?;
+            witness_map.insert(Witness(witness), field);
+        }
+        Ok(witness_map)
+    }
+
+    /// Returns the options the request is proven with, which can be further configured before
+    /// passing them to [`ProveRequestDto::prove_with`].
+    pub fn options(&self) -> ProveOptions {
+        ProveOptions::new().srs_source(self.srs.clone().into())
+    }
+
+    /// Proves the request with the SRS it names.
+    /// # Returns
+    /// * `Result<ProveResponseDto, NoirRsError>` - Returns the response, or a NoirRsError if an
+    ///   input is invalid or proving fails.
+    pub fn prove(&self) -> Result<ProveResponseDto, NoirRsError> {
+        self.prove_with(self.options())
+    }
+
+    /// Proves the request with the given options, ignoring the SRS it names if `options` sets one.
+    /// # Arguments
+    /// * `options` - The options to prove with, such as [`ProveRequestDto::options`].
+    /// # Returns
+    /// * `Result<ProveResponseDto, NoirRsError>` - Returns the response, or a NoirRsError if an
+    ///   input is invalid or proving fails.
+    pub fn prove_with(&self, options: ProveOptions) -> Result<ProveResponseDto, NoirRsError> {
+        let initial_witness = self.initial_witness()?;
+        options.prove_detailed(&self.bytecode, &initial_witness).map(ProveResponseDto::from)
+    }
+}
+
+/// The response to a [`ProveRequestDto`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProveResponseDto {
+    /// The proof, hex encoded without prefix as written by [`write_proof_file`](crate::write_proof_file).
+    pub proof_hex: String,
+    /// The verification key, hex encoded without prefix.
+    pub vk_hex: String,
+    /// The values of the public inputs, as hexadecimal strings prefixed with `0x`.
+    pub public_inputs: Vec<String>,
+}
+
+impl From<ProveOutput> for ProveResponseDto {
+    fn from(output: ProveOutput) -> Self {
+        ProveResponseDto {
+            proof_hex: hex::encode(output.proof),
+            vk_hex: hex::encode(output.verification_key),
+            public_inputs: output
+                .public_inputs
+                .iter()
+                .map(|value| format!("0x{}", value.to_hex()))
+                .collect(),
+        }
+    }
+}
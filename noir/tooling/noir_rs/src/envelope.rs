@@ -0,0 +1,130 @@
+use std::fmt;
+
+use noir_rs_barretenberg::BACKEND_COMMIT;
+
+use crate::errors::NoirRsError;
+
+/// Version of the envelope layout and of the byte formats it wraps. Bump it whenever the layout of
+/// proofs or verification keys changes without a change of the barretenberg sources.
+pub const FORMAT_VERSION: u8 = 1;
+
+const PROOF_MAGIC: &[u8; 4] = b"NRSP";
+const VERIFICATION_KEY_MAGIC: &[u8; 4] = b"NRSK";
+
+/// Identifies the byte format of a proof or a verification key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatVersion {
+    /// The envelope format version, see [`FORMAT_VERSION`].
+    pub format: u8,
+    /// The barretenberg sources the bytes were produced with, see [`BACKEND_COMMIT`].
+    pub backend: String,
+}
+
+impl FormatVersion {
+    /// The version of the bytes produced by this build.
+    pub fn current() -> Self {
+        FormatVersion { format: FORMAT_VERSION, backend: BACKEND_COMMIT.to_string() }
+    }
+}
+
+impl fmt::Display for FormatVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "format {} from barretenberg {}", self.format, self.backend)
+    }
+}
+
+/// A proof, with public inputs prepended as returned by [`prove`](crate::prove).
+///
+/// Raw bytes carry no indication of the barretenberg version they were produced with, and stop
+/// verifying silently when its format changes. Persist proofs with
+/// [`Proof::to_versioned_bytes`] to detect this when reading them back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof(pub Vec<u8>);
+
+/// A serialized verification key, as returned by [`prove`](crate::prove).
+///
+/// See [`Proof`] for why to persist it with [`VerificationKey::to_versioned_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationKey(pub Vec<u8>);
+
+impl Proof {
+    /// Wraps the proof in an envelope holding a magic, [`FORMAT_VERSION`] and [`BACKEND_COMMIT`].
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        seal(PROOF_MAGIC, &self.0)
+    }
+
+    /// Reads a proof written by [`Proof::to_versioned_bytes`].
+    /// # Arguments
+    /// * `bytes` - The enveloped proof.
+    /// # Returns
+    /// * `Result<Proof, NoirRsError>` - Returns the proof, or `NoirRsError::IncompatibleProofVersion`
+    ///   if it was produced by another format or barretenberg version.
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, NoirRsError> {
+        Ok(Proof(open(PROOF_MAGIC, bytes)?))
+    }
+}
+
+impl VerificationKey {
+    /// Wraps the key in an envelope holding a magic, [`FORMAT_VERSION`] and [`BACKEND_COMMIT`].
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        seal(VERIFICATION_KEY_MAGIC, &self.0)
+    }
+
+    /// Reads a verification key written by [`VerificationKey::to_versioned_bytes`].
+    /// # Arguments
+    /// * `bytes` - The enveloped verification key.
+    /// # Returns
+    /// * `Result<VerificationKey, NoirRsError>` - Returns the key, or
+    ///   `NoirRsError::IncompatibleProofVersion` if it was produced by another format or
+    ///   barretenberg version.
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, NoirRsError> {
+        Ok(VerificationKey(open(VERIFICATION_KEY_MAGIC, bytes)?))
+    }
+}
+
+impl From<Vec<u8>> for Proof {
+    fn from(bytes: Vec<u8>) -> Self {
+        Proof(bytes)
+    }
+}
+
+impl From<Vec<u8>> for VerificationKey {
+    fn from(bytes: Vec<u8>) -> Self {
+        VerificationKey(bytes)
+    }
+}
+
+/// Lays out `magic`, the format version, the length-prefixed backend commit and the payload.
+fn seal(magic: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let backend = BACKEND_COMMIT.as_bytes();
+    let mut bytes = Vec::with_capacity(magic.len() + 2 + backend.len() + payload.len());
+    bytes.extend_from_slice(magic);
+    bytes.push(FORMAT_VERSION);
+    bytes.push(backend.len() as u8);
+    bytes.extend_from_slice(backend);
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+/// Checks the header written by [`seal`] and returns the payload.
+fn open(magic: &[u8; 4], bytes: &[u8]) -> Result<Vec<u8>, NoirRsError> {
+    let header = magic.len() + 2;
+    if bytes.len() < header || !bytes.starts_with(magic) {
+        return Err(NoirRsError::InvalidEnvelope(format!(
+            "missing the {} magic",
+            String::from_utf8_lossy(magic)
+        )));
+    }
+    let format = bytes[magic.len()];
+    let backend_len = bytes[magic.len() + 1] as usize;
+    let backend = bytes.get(header..header + backend_len).ok_or_else(|| {
+        NoirRsError::InvalidEnvelope(format!("truncated after {} bytes", bytes.len()))
+    })?;
+
+    let stored = FormatVersion { format, backend: String::from_utf8_lossy(backend).into_owned() };
+    let current = FormatVersion::current();
+    if stored != current {
+        return Err(NoirRsError::IncompatibleProofVersion { stored, current });
+    }
+    Ok(bytes[header + backend_len..].to_vec())
+}
@@ -0,0 +1,211 @@
+use std::path::PathBuf;
+
+use acvm::BlackBoxResolutionError;
+use noir_rs_acir_composer::AcirComposerError;
+use noir_rs_acvm_runtime::errors::ACVMError;
+use noir_rs_barretenberg::{srs::SrsError, BackendError};
+use noirc_abi::errors::{AbiError, InputParserError};
+use thiserror::Error;
+
+use crate::{
+    abi::InputViolation, acir_version::NARGO_VERSION, envelope::FormatVersion,
+    threads::NUM_THREADS_VAR, SizingMode,
+};
+
+#[derive(Debug, Error)]
+pub enum NoirRsError {
+    /// Circuit bytecode is not valid base64
+    #[error(transparent)]
+    Base64Decode(#[from] base64::DecodeError),
+
+    #[error("Invalid character {character:?} at byte {position} of the circuit bytecode")]
+    InvalidBytecodeCharacter { position: usize, character: char },
+
+    #[error("Circuit bytecode is neither gzip nor zlib compressed, it starts with {prefix:02x?}")]
+    UnknownBytecodeCompression { prefix: Vec<u8> },
+
+    /// Reading or decompressing input failed
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("Cannot access {}: {source}", path.display())]
+    File { path: PathBuf, source: std::io::Error },
+
+    #[error("Invalid proof file {}, expected hex: {source}", path.display())]
+    InvalidProofFile { path: PathBuf, source: hex::FromHexError },
+
+    /// Witness generation failed
+    #[error(transparent)]
+    Execution(#[from] ACVMError),
+
+    /// Solved witness could not be serialized for the backend
+    #[error(transparent)]
+    WitnessSerialization(#[from] bincode::Error),
+
+    /// Hashing the circuit failed
+    #[error(transparent)]
+    Hash(#[from] BlackBoxResolutionError),
+
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+
+    #[error(transparent)]
+    AcirComposer(#[from] AcirComposerError),
+
+    #[error(transparent)]
+    Srs(#[from] SrsError),
+
+    #[error("Invalid Prover.toml: {0}")]
+    InvalidToml(String),
+
+    #[error("Invalid value for `{key}` in Prover.toml: {source}")]
+    InvalidTomlValue { key: String, source: InputParserError },
+
+    /// Inputs do not match the ABI of the circuit
+    #[error(transparent)]
+    Abi(#[from] AbiError),
+
+    #[error("Circuit bytecode is {detected}, but this build reads {supported}: recompile the circuit with nargo {NARGO_VERSION}")]
+    UnsupportedAcirVersion { detected: String, supported: String },
+
+    #[error("Verification key of {0} bytes is too short")]
+    InvalidVerificationKey(usize),
+
+    #[error("Subgroup size {subgroup_size} computed with {sizing:?} sizing is smaller than the subgroup of {required} reported by the composer")]
+    SubgroupTooSmall { subgroup_size: u32, sizing: SizingMode, required: u32 },
+
+    #[error(transparent)]
+    Size(#[from] SizeError),
+
+    #[error("Invalid proof envelope: {0}")]
+    InvalidEnvelope(String),
+
+    #[error("Invalid transport encoding: {0}")]
+    InvalidTransport(String),
+
+    #[error("Transport encoding decompresses to more than {limit} bytes")]
+    TransportTooLarge { limit: usize },
+
+    #[error("Proof is longer than {limit} bytes")]
+    ProofTooLarge { limit: usize },
+
+    #[error("Stored bytes use {stored}, but this build uses {current}")]
+    IncompatibleProofVersion { stored: FormatVersion, current: FormatVersion },
+
+    #[error("Invalid number of threads {0:?} in {NUM_THREADS_VAR} or ProveOptions::num_threads, expected a positive integer")]
+    InvalidNumThreads(String),
+
+    #[error("The backend thread pool was already sized for {}, so it cannot use {requested} threads", .configured.map_or(String::from("every core"), |threads| format!("{} threads", threads)))]
+    NumThreadsFixed { configured: Option<usize>, requested: usize },
+
+    #[error("Proving was cancelled before the proof was generated")]
+    Cancelled,
+
+    #[error("Circuit parameter w{witness} has no value, only {num_inputs} inputs were given")]
+    MissingInput { witness: u32, num_inputs: usize },
+
+    #[error("Circuit inputs {} have no value in the initial witness", witnesses.iter().map(|witness| format!("w{}", witness)).collect::<Vec<_>>().join(", "))]
+    MissingInputs { witnesses: Vec<u32> },
+
+    #[error("Inputs do not fit their declared types: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    InvalidInputs(Vec<InputViolation>),
+
+    #[error("Input w{witness} is not a parameter of the circuit, its parameters are not its first witnesses")]
+    UnexpectedInput { witness: u32 },
+
+    #[error("Invalid witness JSON: {0}")]
+    InvalidWitnessJson(String),
+
+    #[error("Value {value:?} of input w{witness} is not a field element")]
+    InvalidInputValue { witness: u32, value: String },
+
+    #[error("The verification key does not record an aggregation object, its circuit does not verify a proof recursively")]
+    NotRecursiveProof,
+
+    #[error("Aggregation object public input {index} is missing, the proof only has {num_public_inputs} public inputs")]
+    ProofTooShort { num_public_inputs: usize, index: u32 },
+
+    #[error(
+        "Proof of {actual} bytes does not match the {expected} bytes the verification key expects"
+    )]
+    ProofLengthMismatch { expected: usize, actual: usize },
+
+    #[error("Invalid aggregation object: {0}")]
+    InvalidAggregationObject(String),
+
+    #[error(
+        "Proving is estimated to use {estimated} bytes of memory, over the budget of {budget}"
+    )]
+    MemoryBudgetExceeded { estimated: u64, budget: u64 },
+
+    #[error(
+        "The circuit needs {needed} SRS points but the SRS source provides at most {available}"
+    )]
+    CircuitTooLargeForSrs { needed: u32, available: u32 },
+
+    /// A check of the output of the backend failed, which is a bug
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error("The {artifact} in {} was not computed for this circuit: its hash is {found}, but the circuit's is {expected}", path.display())]
+    ArtifactMismatch { artifact: &'static str, path: PathBuf, expected: String, found: String },
+}
+
+impl NoirRsError {
+    /// Returns a stable, snake_case name of the error variant, for programs that report errors
+    /// without parsing their messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NoirRsError::Base64Decode(_) => "base64_decode",
+            NoirRsError::InvalidBytecodeCharacter { .. } => "invalid_bytecode_character",
+            NoirRsError::UnknownBytecodeCompression { .. } => "unknown_bytecode_compression",
+            NoirRsError::Io(_) => "io",
+            NoirRsError::File { .. } => "file",
+            NoirRsError::InvalidProofFile { .. } => "invalid_proof_file",
+            NoirRsError::Execution(_) => "execution",
+            NoirRsError::WitnessSerialization(_) => "witness_serialization",
+            NoirRsError::Hash(_) => "hash",
+            NoirRsError::Backend(_) => "backend",
+            NoirRsError::AcirComposer(_) => "acir_composer",
+            NoirRsError::Srs(_) => "srs",
+            NoirRsError::InvalidToml(_) => "invalid_toml",
+            NoirRsError::InvalidTomlValue { .. } => "invalid_toml_value",
+            NoirRsError::Abi(_) => "abi",
+            NoirRsError::UnsupportedAcirVersion { .. } => "unsupported_acir_version",
+            NoirRsError::InvalidVerificationKey(_) => "invalid_verification_key",
+            NoirRsError::SubgroupTooSmall { .. } => "subgroup_too_small",
+            NoirRsError::Size(SizeError::TooLarge { .. }) => "subgroup_too_large",
+            NoirRsError::InvalidEnvelope(_) => "invalid_envelope",
+            NoirRsError::InvalidTransport(_) => "invalid_transport",
+            NoirRsError::TransportTooLarge { .. } => "transport_too_large",
+            NoirRsError::ProofTooLarge { .. } => "proof_too_large",
+            NoirRsError::IncompatibleProofVersion { .. } => "incompatible_proof_version",
+            NoirRsError::InvalidNumThreads(_) => "invalid_num_threads",
+            NoirRsError::NumThreadsFixed { .. } => "num_threads_fixed",
+            NoirRsError::Cancelled => "cancelled",
+            NoirRsError::ArtifactMismatch { .. } => "artifact_mismatch",
+            NoirRsError::InvalidWitnessJson(_) => "invalid_witness_json",
+            NoirRsError::InvalidInputValue { .. } => "invalid_input_value",
+            NoirRsError::MissingInput { .. } => "missing_input",
+            NoirRsError::MissingInputs { .. } => "missing_inputs",
+            NoirRsError::InvalidInputs(_) => "invalid_inputs",
+            NoirRsError::UnexpectedInput { .. } => "unexpected_input",
+            NoirRsError::NotRecursiveProof => "not_recursive_proof",
+            NoirRsError::ProofTooShort { .. } => "proof_too_short",
+            NoirRsError::ProofLengthMismatch { .. } => "proof_length_mismatch",
+            NoirRsError::InvalidAggregationObject(_) => "invalid_aggregation_object",
+            NoirRsError::MemoryBudgetExceeded { .. } => "memory_budget_exceeded",
+            NoirRsError::CircuitTooLargeForSrs { .. } => "circuit_too_large_for_srs",
+            NoirRsError::Internal(_) => "internal",
+        }
+    }
+}
+
+/// Error computing the subgroup size of a circuit with [`subgroup_size_for`](crate::subgroup_size_for).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SizeError {
+    #[error(
+        "A circuit of {total_gates} gates needs a subgroup larger than the maximum size of {max}"
+    )]
+    TooLarge { total_gates: u32, max: u32 },
+}
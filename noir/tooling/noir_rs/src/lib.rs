@@ -1,96 +1,3732 @@
-use std::io::Read;
+use std::{io::Read, path::Path, sync::Arc, time::Instant};
 
-use acir::{circuit::Circuit, native_types::WitnessMap};
-use base64::{engine::general_purpose, Engine};
-use flate2::bufread::GzDecoder;
+use acir::{
+    circuit::Circuit,
+    native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+use acvm::{blackbox_solver::sha256, BlackBoxFunctionSolver};
 use noir_rs_acir_composer::AcirComposer;
 use noir_rs_acvm_runtime::execute::execute_circuit;
 use noir_rs_barretenberg::{
-    circuit::circuit_size::get_circuit_sizes,
-    srs::{netsrs::NetSrs, srs_init},
+    acir::{get_ultra_honk_verification_key, prove_ultra_honk, verify_ultra_honk},
+    srs::{check_transcript, download::ensure_transcript, local_srs::trim_transcript, srs_init},
+};
+use size_cache::cached_circuit_sizes;
+use threads::configure_num_threads;
+use trace::span;
+use verifier::{vk_circuit_size, IgnitionVerifierSrs};
+use verify_cache::cached_verify;
+
+pub mod abi;
+mod acir_version;
+pub mod artifacts;
+mod bytecode;
+pub mod dto;
+pub mod envelope;
+pub mod errors;
+pub mod prelude;
+pub mod prover;
+pub mod recursion;
+pub mod report;
+mod size_cache;
+pub mod solidity;
+mod threads;
+mod trace;
+pub mod transport;
+pub mod verifier;
+mod verify_cache;
+pub mod vk_cache;
+pub mod witness;
+
+pub use abi::{validate_inputs, witness_from_toml, InputViolation};
+pub use acir;
+pub use acir_version::{backend_info, BackendInfo, ACIR_VERSION, NARGO_VERSION};
+pub use acvm;
+pub use artifacts::{
+    read_pk_file, read_proof_file, read_vk_file, write_pk_file, write_proof_file, write_vk_file,
+};
+pub use bytecode::{decode_bytecode, decode_circuit, decompress_bytecode, DecodedCircuit};
+pub use dto::{ProveRequestDto, ProveResponseDto, SrsDto};
+pub use envelope::{FormatVersion, Proof, VerificationKey};
+pub use errors::{NoirRsError, SizeError};
+pub use noir_rs_acvm_runtime::executor::{CircuitExecutor, StepResult};
+pub use noir_rs_barretenberg::circuit::circuit_size::{get_circuit_sizes, CircuitSizes};
+#[cfg(feature = "test-utils")]
+pub use noir_rs_barretenberg::srs::mock::{MockRequest, MockSrsServer, MockTranscript};
+pub use noir_rs_barretenberg::srs::{
+    cached_srs::{CachePolicy, CachedSrs},
+    cancel::CancellationToken,
+    checksum::SrsChecksum,
+    dev_srs::DevSrs,
+    download::download_srs,
+    grumpkin_srs::GrumpkinSrs,
+    local_srs::{LocalSrs, TranscriptFormat},
+    netsrs::{NetSrs, DEFAULT_SRS_URL},
+    source::{SrsSource, SRS_PATH_VAR, SRS_URL_VAR},
+    Curve, Srs, SrsError,
+};
+pub use noir_rs_blackbox_solver::{schnorr, BlackboxSolver};
+pub use noirc_abi::Abi;
+pub use prover::Prover;
+pub use recursion::{
+    extract_aggregation_object, inject_aggregation_object, proof_as_fields,
+    validate_aggregation_object, verify_recursive, verify_recursive_with_srs, vk_as_fields,
+    vk_as_fields_with_srs, AGGREGATION_OBJECT_SIZE,
+};
+pub use report::{
+    circuit_stats, estimate_memory, estimate_peak_memory, gate_report, CircuitStats, GateReport,
+    MemoryEstimate,
+};
+pub use size_cache::clear_size_cache;
+pub use solidity::{
+    proof_layout, solidity_verifier, solidity_verifier_with_srs, verifier_stats, vk_hash,
+    ProofLayout, VerifierStats,
 };
-use noir_rs_blackbox_solver::BlackboxSolver;
+pub use threads::NUM_THREADS_VAR;
+pub use transport::{decode_from_transport, encode_for_transport};
+pub use verifier::Verifier;
+pub use verify_cache::{clear_verify_cache, set_verify_cache_capacity};
+pub use vk_cache::{VkCache, DEFAULT_VK_CACHE_CAPACITY};
+pub use witness::{witness_from_json, witness_to_json};
+
+/// Selects which circuit size reported by barretenberg feeds the subgroup size computation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SizingMode {
+    /// Uses the exact number of gates, which leaves out the gates the composer reserves on top.
+    ///
+    /// The composer still proves over the subgroup holding the total size, so proving fails with
+    /// [`NoirRsError::SubgroupTooSmall`] when the two round to different powers of two.
+    Exact,
+    /// Uses the total circuit size, including ROM/RAM and lookup overhead.
+    #[default]
+    Total,
+}
+
+/// Selects the Fiat-Shamir transcript, and therefore the verifier, a proof is made for.
+///
+/// Both flavors encode the public inputs the same way, as 32-byte big-endian field elements at the
+/// start of the proof, so they can be extracted without knowing the flavor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProofFlavor {
+    /// UltraPlonk with a Pedersen/Blake3s transcript, which a recursive verifier circuit can
+    /// replay.
+    Plonk,
+    /// UltraPlonk with a Keccak transcript, which is what the Solidity verifier expects.
+    #[default]
+    UltraKeccak,
+}
+
+impl ProofFlavor {
+    /// Returns the `is_recursive` flag barretenberg uses to select the flavor.
+    fn is_recursive(self) -> bool {
+        self == ProofFlavor::Plonk
+    }
+}
+
+/// Selects the proving system a proof is generated and verified with.
+///
+/// Each backend has its own proof and verification key encoding, so a proof must be verified with
+/// the backend it was generated with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// UltraPlonk, whose transcript is selected by the [`ProofFlavor`].
+    #[default]
+    Plonk,
+    /// UltraHonk. The proof flavor is ignored, Honk always uses its own transcript.
+    UltraHonk,
+}
+
+impl Backend {
+    /// Returns the number of SRS points needed to prove with a subgroup of `subgroup_size`.
+    ///
+    /// Honk prepends a zero row to the execution trace, which can push the circuit into the next
+    /// power of two.
+    fn num_points(self, subgroup_size: u32) -> u32 {
+        match self {
+            Backend::Plonk => subgroup_size + 1,
+            Backend::UltraHonk => 2 * subgroup_size,
+        }
+    }
+}
+
+/// Builder consolidating the configuration used to generate a proof.
+///
+/// Unset options fall back to the same defaults as [`prove`]: the SRS is downloaded from the
+/// ignition transcript or the mirror set in `NOIR_RS_SRS_URL`, the subgroup is sized from the total circuit size and the proof uses a
+/// Keccak transcript.
+///
+/// ```no_run
+/// # use noir_rs::prelude::*;
+/// # let circuit_bytecode = String::new();
+/// let (proof, vk) = ProveOptions::new()
+///     .srs(DevSrs::new(1 << 10))
+///     .recursive(true)
+///     .prove(&circuit_bytecode, &WitnessMap::new())?;
+/// # Ok::<(), noir_rs::NoirRsError>(())
+/// ```
+#[derive(Default)]
+pub struct ProveOptions {
+    srs: Option<Box<dyn Srs>>,
+    sizing: SizingMode,
+    flavor: ProofFlavor,
+    backend: Backend,
+    skip_srs_check: bool,
+    num_threads: Option<usize>,
+    cancellation: Option<CancellationToken>,
+    vk_cache: Option<Arc<VkCache>>,
+    max_memory_bytes: Option<u64>,
+    abi: Option<Abi>,
+    skip_input_validation: bool,
+}
+
+impl ProveOptions {
+    /// Creates a new ProveOptions instance with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the SRS source the points are loaded from.
+    ///
+    /// # Arguments
+    /// * `srs` - The SRS source the points are loaded from.
+    pub fn srs(mut self, srs: impl Srs + 'static) -> Self {
+        self.srs = Some(Box::new(srs));
+        self
+    }
+
+    /// Downloads the SRS from the given URL instead of the default ignition transcript.
+    ///
+    /// # Arguments
+    /// * `url` - URL of a transcript with the same layout as the ignition `transcript00.dat`.
+    pub fn srs_url(self, url: &str) -> Self {
+        self.srs(NetSrs::with_url(url))
+    }
+
+    /// Loads the SRS from the given source.
+    ///
+    /// # Arguments
+    /// * `source` - The source the points are loaded from, e.g. [`SrsSource::from_env`].
+    pub fn srs_source(mut self, source: SrsSource) -> Self {
+        self.srs = Some(source.into_srs());
+        self
+    }
+
+    /// Sets the circuit size basis used to compute the subgroup size.
+    ///
+    /// # Arguments
+    /// * `sizing` - The circuit size basis used to compute the subgroup size.
+    pub fn sizing(mut self, sizing: SizingMode) -> Self {
+        self.sizing = sizing;
+        self
+    }
+
+    /// Selects the proof flavor produced by barretenberg.
+    ///
+    /// # Arguments
+    /// * `flavor` - The transcript the proof is made for.
+    pub fn flavor(mut self, flavor: ProofFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Selects between a proof meant for recursive verification, [`ProofFlavor::Plonk`], and one
+    /// meant for the Solidity verifier, [`ProofFlavor::UltraKeccak`].
+    ///
+    /// A recursive proof of a circuit that itself verifies a proof carries an aggregation object,
+    /// which [`extract_aggregation_object`] reads for the next circuit of the chain.
+    ///
+    /// # Arguments
+    /// * `recursive` - Whether to produce a proof meant for recursive verification.
+    pub fn recursive(self, recursive: bool) -> Self {
+        self.flavor(if recursive { ProofFlavor::Plonk } else { ProofFlavor::UltraKeccak })
+    }
+
+    /// Selects the proving system used to generate the proof.
+    ///
+    /// # Arguments
+    /// * `backend` - The proving system the proof is generated with.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Skips checking that the loaded SRS starts with the bn254 generator and holds a valid G2
+    /// point, for setups using a transcript that deliberately differs from the ignition layout.
+    ///
+    /// # Arguments
+    /// * `skip_srs_check` - Whether to hand the SRS to the backend without checking it.
+    pub fn skip_srs_check(mut self, skip_srs_check: bool) -> Self {
+        self.skip_srs_check = skip_srs_check;
+        self
+    }
+
+    /// Sets the number of threads the backend proves with, overriding the `NOIR_RS_NUM_THREADS`
+    /// environment variable, which itself overrides the `HARDWARE_CONCURRENCY` variable read by
+    /// barretenberg. Every core is used when none is set.
+    ///
+    /// Barretenberg sizes its thread pool on first use, so the count applies to the whole process
+    /// and must be set by its first proof; a later proof asking for another count fails with
+    /// [`NoirRsError::NumThreadsFixed`]. The backend is single threaded, and this has no effect,
+    /// unless the `multithreading` feature is enabled. The count only affects proving time.
+    ///
+    /// # Arguments
+    /// * `num_threads` - Number of threads, at least 1.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Aborts proving with [`NoirRsError::Cancelled`] once the token is cancelled.
+    ///
+    /// Cancellation is cooperative. The token is checked before executing the circuit, before
+    /// loading the SRS and before generating the proof, and the default [`NetSrs`] stops its
+    /// download when the token is cancelled; an SRS source set with [`ProveOptions::srs`] only
+    /// does if it was built with [`NetSrs::with_cancellation`]. Executing the circuit and
+    /// generating the proof in the backend are not interruptible, a token cancelled while they
+    /// run takes effect once they return, and a proof whose generation started is returned.
+    ///
+    /// # Arguments
+    /// * `token` - The token to watch, keep a clone of it to cancel from another thread.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Aborts proving with [`NoirRsError::Cancelled`] once `deadline` passes, with the same
+    /// interruption points as [`ProveOptions::cancellation`].
+    ///
+    /// # Arguments
+    /// * `deadline` - The instant after which proving is abandoned.
+    pub fn deadline(self, deadline: Instant) -> Self {
+        let token = self.cancellation.clone().unwrap_or_default();
+        self.cancellation(token.with_deadline(deadline))
+    }
+
+    /// Reads the verification key from `cache` when it holds the key of the circuit, and stores
+    /// the computed key in it otherwise. Only used with [`Backend::Plonk`].
+    ///
+    /// # Arguments
+    /// * `cache` - The cache to consult, shared with other provers.
+    pub fn vk_cache(mut self, cache: Arc<VkCache>) -> Self {
+        self.vk_cache = Some(cache);
+        self
+    }
+
+    /// Fails with [`NoirRsError::MemoryBudgetExceeded`] before loading the SRS when the peak
+    /// memory of proving the circuit is estimated over `budget`, instead of letting the process
+    /// run out of memory.
+    ///
+    /// The estimate follows [`MemoryEstimate::peak_bytes`] for the subgroup size of the circuit,
+    /// see [`estimate_peak_memory`]. It is an approximation, leave headroom for the rest of the
+    /// process.
+    ///
+    /// # Arguments
+    /// * `budget` - The memory proving may use, in bytes.
+    pub fn max_memory_bytes(mut self, budget: u64) -> Self {
+        self.max_memory_bytes = Some(budget);
+        self
+    }
+
+    /// Sets the ABI of the circuit, whose parameter types [`validate_inputs`] checks the initial
+    /// witness against, on top of the range constraints of the circuit.
+    ///
+    /// # Arguments
+    /// * `abi` - The ABI of the circuit, as found in the artifact written by `nargo compile`.
+    pub fn abi(mut self, abi: Abi) -> Self {
+        self.abi = Some(abi);
+        self
+    }
+
+    /// Skips checking the initial witness with [`validate_inputs`] before executing the circuit,
+    /// e.g. to let the circuit itself report an out of range input.
+    ///
+    /// # Arguments
+    /// * `skip_input_validation` - Whether to execute the circuit without checking its inputs.
+    pub fn skip_input_validation(mut self, skip_input_validation: bool) -> Self {
+        self.skip_input_validation = skip_input_validation;
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<(), NoirRsError> {
+        match &self.cancellation {
+            Some(token) if token.is_cancelled() => Err(NoirRsError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    /// Generates a proof with the configured options.
+    /// # Arguments
+    /// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+    /// * `initial_witness` - Initial witness values for the circuit.
+    /// # Returns
+    /// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+    pub fn prove(
+        self,
+        circuit_bytecode: &str,
+        initial_witness: &WitnessMap,
+    ) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+        let output = self.prove_detailed(circuit_bytecode, initial_witness)?;
+        Ok((output.proof, output.verification_key))
+    }
+
+    /// Generates a proof with the configured options, returning the values of its public inputs
+    /// alongside it.
+    /// # Arguments
+    /// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+    /// * `initial_witness` - Initial witness values for the circuit.
+    /// # Returns
+    /// * `Result<ProveOutput, NoirRsError>` - Returns the proof, the verification key and the public inputs or a NoirRsError.
+    pub fn prove_detailed(
+        mut self,
+        circuit_bytecode: &str,
+        initial_witness: &WitnessMap,
+    ) -> Result<ProveOutput, NoirRsError> {
+        let mut srs = match self.srs.take() {
+            Some(srs) => srs,
+            None => {
+                let srs = NetSrs::from_env()?;
+                Box::new(match &self.cancellation {
+                    Some(token) => srs.with_cancellation(token.clone()),
+                    None => srs,
+                })
+            }
+        };
+        let (output, _) =
+            prove_inner(circuit_bytecode, initial_witness, srs.as_mut(), &self, false)?;
+        Ok(output)
+    }
+}
+
+/// A proof together with the data a verifier needs alongside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProveOutput {
+    /// The proof, with the public inputs prepended for UltraPlonk.
+    pub proof: Vec<u8>,
+    /// The serialized verification key.
+    pub verification_key: Vec<u8>,
+    /// The values of the public inputs, in the order the verifier expects them.
+    ///
+    /// The backend makes each witness of the public parameters and the return values public once,
+    /// in increasing witness order, so a witness in both lists appears once. With the usual
+    /// layout, where return values are allocated after the parameters, this lists the public
+    /// parameters followed by the return values.
+    pub public_inputs: Vec<FieldElement>,
+    /// The versions the proof was produced with.
+    pub backend_info: BackendInfo,
+}
+
+/// A proof and its verification key, with the verdict of the verifier on them, as returned by
+/// [`prove_and_verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProveAndVerifyOutput {
+    /// The proof, with the public inputs prepended.
+    pub proof: Vec<u8>,
+    /// The serialized verification key.
+    pub verification_key: Vec<u8>,
+    /// Whether the proof is valid for the verification key.
+    pub verified: bool,
+}
+
+/// Options controlling proof verification.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyOptions {
+    /// Must match the flavor the proof was generated with.
+    pub flavor: ProofFlavor,
+    /// Must match the backend the proof was generated with.
+    pub backend: Backend,
+    /// Skips checking that the loaded SRS starts with the bn254 generator and holds a valid G2
+    /// point.
+    pub skip_srs_check: bool,
+}
+
+/// The verdict on a proof that could be checked.
+///
+/// Failures to check the proof, such as a malformed verification key or an SRS that cannot be
+/// loaded, are reported as a [`NoirRsError`] instead, so that callers can tell a proof to reject
+/// from an operation to retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// The proof is valid for the verification key.
+    Valid,
+    /// The proof is not valid for the verification key.
+    Invalid {
+        /// Why the proof was rejected, e.g. the error raised while reading a malformed proof.
+        reason: String,
+    },
+}
+
+impl VerifyOutcome {
+    /// Returns whether the proof is valid.
+    pub fn is_valid(&self) -> bool {
+        *self == VerifyOutcome::Valid
+    }
+}
+
+/// Runs the verifier of a composer holding a verification key on a proof.
+///
+/// The key is already loaded, so an error at this point comes from reading the proof and rejects
+/// it.
+pub(crate) fn verify_loaded(
+    acir_composer: &AcirComposer,
+    proof: &[u8],
+    flavor: ProofFlavor,
+) -> VerifyOutcome {
+    match acir_composer.verify_proof(proof, flavor.is_recursive()) {
+        Ok(true) => VerifyOutcome::Valid,
+        Ok(false) => VerifyOutcome::Invalid {
+            reason: String::from("the proof does not satisfy the verifier"),
+        },
+        Err(error) => VerifyOutcome::Invalid { reason: error.to_string() },
+    }
+}
+
+/// Largest subgroup size [`subgroup_size_for`] returns, which needs 4 times the points of the
+/// ignition transcript.
+pub const MAX_SUBGROUP_SIZE: u32 = 1 << 25;
+
+/// Returns the size of the evaluation subgroup a circuit of `total_gates` gates is proven over,
+/// the smallest power of two holding the gates.
+///
+/// Proving with the Plonk backend needs one more SRS point than the subgroup size, which lets
+/// callers predict the SRS a circuit requires from the gate counts of [`get_circuit_sizes`].
+/// Circuits of 0 and 1 gates both have a subgroup of size 1.
+/// # Arguments
+/// * `total_gates` - The number of gates of the circuit.
+/// # Returns
+/// * `Result<u32, SizeError>` - Returns the subgroup size, or `SizeError::TooLarge` if it would
+///   exceed [`MAX_SUBGROUP_SIZE`].
+pub fn subgroup_size_for(total_gates: u32) -> Result<u32, SizeError> {
+    match total_gates.checked_next_power_of_two() {
+        Some(subgroup_size) if subgroup_size <= MAX_SUBGROUP_SIZE => Ok(subgroup_size),
+        _ => Err(SizeError::TooLarge { total_gates, max: MAX_SUBGROUP_SIZE }),
+    }
+}
+
+/// Computes the subgroup size for a circuit using the selected sizing basis.
+///
+/// Returns an error if the resulting subgroup is smaller than the one the composer reports, which
+/// sizes the proving key and would reject the SRS natively.
+fn subgroup_size(circuit_size: &CircuitSizes, sizing: SizingMode) -> Result<u32, NoirRsError> {
+    let basis = match sizing {
+        SizingMode::Exact => circuit_size.exact,
+        SizingMode::Total => circuit_size.total,
+    };
+    let subgroup_size = subgroup_size_for(basis)?;
+    if subgroup_size < circuit_size.subgroup {
+        return Err(NoirRsError::SubgroupTooSmall {
+            subgroup_size,
+            sizing,
+            required: circuit_size.subgroup,
+        });
+    }
+    Ok(subgroup_size)
+}
+
+/// Computes the subgroup size of a circuit and the number of SRS points proving it requires.
+///
+/// Shared by proving and [`estimate_srs_bytes`], so the estimate always matches what is loaded.
+/// # Returns
+/// * `Result<(u32, u32), NoirRsError>` - Returns the subgroup size and the number of points.
+fn srs_points(
+    acir_buffer_uncompressed: &[u8],
+    sizing: SizingMode,
+    backend: Backend,
+) -> Result<(u32, u32), NoirRsError> {
+    let circuit_size = cached_circuit_sizes(acir_buffer_uncompressed)?;
+    let subgroup_size = subgroup_size(&circuit_size, sizing)?;
+    Ok((subgroup_size, backend.num_points(subgroup_size)))
+}
+
+/// Estimates the number of bytes of SRS [`prove`] and [`prove_generic`] load for a circuit,
+/// which is what a [`NetSrs`] downloads, e.g. to warn users on metered connections.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<u64, NoirRsError>` - Returns the size of the G1 points and the G2 point in bytes, or a NoirRsError.
+pub fn estimate_srs_bytes(circuit_bytecode: String) -> Result<u64, NoirRsError> {
+    Ok(num_points_for_circuit(&circuit_bytecode)? as u64 * 64 + 128)
+}
+
+/// Returns the number of SRS points [`prove`] and [`prove_generic`] load for a circuit, the
+/// subgroup size plus one, without loading any SRS.
+///
+/// The sizes of the circuit are cached, so proving it afterwards does not compute them again.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<u32, NoirRsError>` - Returns the number of points passed to [`Srs::load_data`], or a NoirRsError.
+pub fn num_points_for_circuit(circuit_bytecode: &str) -> Result<u32, NoirRsError> {
+    let acir_buffer = decode_bytecode(circuit_bytecode)?;
+    let acir_buffer_uncompressed = decompress_bytecode(&acir_buffer)?;
+
+    let (_, num_points) =
+        srs_points(&acir_buffer_uncompressed, SizingMode::default(), Backend::default())?;
+    Ok(num_points)
+}
+
+/// Makes sure `path` holds an ignition transcript with the points needed to prove circuits up to
+/// `subgroup_size`, downloading it only when the file is missing or too small.
+///
+/// This is meant to provision the SRS before a service starts proving, e.g. from the entrypoint of
+/// a container, and can be called on every start: a file holding enough points is left untouched,
+/// and a smaller one is extended with the missing points only. Concurrent calls for the same path
+/// download the transcript once, waiting on a lock of `<path>.lock`.
+/// # Arguments
+/// * `path` - Path of the transcript, to be read with [`LocalSrs`].
+/// * `url` - URL of the transcript, [`DEFAULT_SRS_URL`] when `None`.
+/// * `subgroup_size` - The largest subgroup size of the circuits to prove, see
+///   [`subgroup_size_for`].
+/// # Returns
+/// * `Result<(), NoirRsError>` - Returns a NoirRsError if the transcript cannot be downloaded or
+///   holds fewer points than needed.
+pub fn ensure_srs(path: &str, url: Option<&str>, subgroup_size: u32) -> Result<(), NoirRsError> {
+    ensure_transcript(url, Path::new(path), Backend::Plonk.num_points(subgroup_size))?;
+    Ok(())
+}
+
+/// Writes an ignition transcript holding only the points needed to prove circuits up to
+/// `subgroup_size`, taken from the ignition transcript at `src_path`.
+///
+/// The 322 MB `transcript00.dat` holds enough points for circuits of 2^22 gates, trimming it lets a
+/// transcript of a few megabytes be shipped along with a small circuit and read with [`LocalSrs`].
+/// # Arguments
+/// * `src_path` - Path of the ignition transcript to trim.
+/// * `dst_path` - Path the trimmed transcript is written to.
+/// * `subgroup_size` - The largest subgroup size of the circuits to prove, see
+///   [`subgroup_size_for`].
+/// # Returns
+/// * `Result<(), NoirRsError>` - Returns a NoirRsError if `src_path` holds fewer points than
+///   needed or `dst_path` cannot be written.
+pub fn trim_srs(src_path: &str, dst_path: &str, subgroup_size: u32) -> Result<(), NoirRsError> {
+    let num_points = Backend::Plonk.num_points(subgroup_size);
+    trim_transcript(Path::new(src_path), Path::new(dst_path), num_points)?;
+    Ok(())
+}
 
-pub use acir::*;
-pub use acvm::*;
+/// Loads `num_points` from the SRS source and initializes the backend with them.
+///
+/// Sources knowing their [`Srs::max_points`] are checked to hold enough points before anything is
+/// loaded, as the backend aborts on an SRS too small for the circuit. Unless `skip_check` is set,
+/// the data is then checked to look like a bn254 transcript.
+pub(crate) fn init_srs<S: Srs + ?Sized>(
+    srs: &mut S,
+    num_points: u32,
+    skip_check: bool,
+) -> Result<(), NoirRsError> {
+    if let Some(available) = srs.max_points() {
+        if num_points > available {
+            return Err(NoirRsError::CircuitTooLargeForSrs { needed: num_points, available });
+        }
+    }
+    // Verifying sizes the thread pool too when it runs first.
+    configure_num_threads(None)?;
+    srs.load_data(num_points)?;
+    if !skip_check {
+        check_transcript(srs.g1_data(), srs.g2_data())?;
+    }
+    srs_init(srs.g1_data(), srs.num_points(), srs.g2_data())?;
+    // The backend copied the points, holding on to them would double the memory used.
+    srs.clear();
+    Ok(())
+}
 
+/// Generates a proof, downloading the SRS from the ignition transcript.
+///
+/// The transcript is downloaded from the mirror set in `NOIR_RS_SRS_URL` when the variable is set
+/// and not empty, and from [`DEFAULT_SRS_URL`] otherwise. Unlike [`prove_auto`], `NOIR_RS_SRS_PATH`
+/// is ignored.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit, with a value for each of the
+///   [`required_inputs`].
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
 pub fn prove(
-    circuit_bytecode: String,
-    initial_witness: WitnessMap,
-) -> Result<(Vec<u8>, Vec<u8>), String> {
-    let acir_buffer =
-        general_purpose::STANDARD.decode(circuit_bytecode).map_err(|e| e.to_string())?;
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    prove_generic(circuit_bytecode, initial_witness, NetSrs::from_env()?)
+}
+
+/// Generates a proof, downloading the SRS like [`prove`], unless `deadline` passes first.
+///
+/// The deadline is checked between the proving phases and stops the SRS download, but executing
+/// the circuit and generating the proof in the backend are not interruptible: a proof whose
+/// generation started before the deadline is returned even if it completes after it. See
+/// [`ProveOptions::cancellation`] to cancel from another thread instead.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `deadline` - The instant after which proving is abandoned.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key, or
+///   `NoirRsError::Cancelled` if the deadline passed before proof generation started.
+pub fn prove_with_deadline(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    deadline: Instant,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    ProveOptions::new().deadline(deadline).prove(circuit_bytecode, initial_witness)
+}
+
+/// Generates a proof, reading the SRS from a local ignition transcript.
+///
+/// The size of the transcript is checked against the circuit before it is executed, see
+/// [`LocalSrs::max_points`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `path` - The path of the transcript file.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key, or
+///   `SrsError::InsufficientPoints` if the transcript holds fewer points than the circuit needs.
+pub fn prove_local_srs(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    path: impl AsRef<Path>,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let srs = LocalSrs::new(&path);
+    let (available, needed) = (srs.max_points()?, num_points_for_circuit(circuit_bytecode)?);
+    if needed > available {
+        return Err(SrsError::InsufficientPoints {
+            available,
+            requested: needed,
+            path: Some(path.as_ref().to_path_buf()),
+        }
+        .into());
+    }
+    prove_generic(circuit_bytecode, initial_witness, srs)
+}
+
+/// Generates a proof, downloading the SRS from a mirror of the ignition transcript.
+///
+/// The mirror is, in order of precedence, `url`, the `NOIR_RS_SRS_URL` environment variable and
+/// [`DEFAULT_SRS_URL`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `url` - The URL of the transcript, or `None` to resolve it as [`prove`] does.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_net_srs(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    url: Option<&str>,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let srs = match url {
+        Some(url) => NetSrs::with_url(url),
+        None => NetSrs::from_env()?,
+    };
+    prove_generic(circuit_bytecode, initial_witness, srs)
+}
+
+/// Generates a proof, loading the SRS from the source configured in the environment.
+///
+/// `NOIR_RS_SRS_PATH` selects a local transcript and takes precedence over `NOIR_RS_SRS_URL`,
+/// which selects a mirror. The default ignition transcript is used when neither is set.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_auto(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    ProveOptions::new().srs_source(SrsSource::from_env()?).prove(circuit_bytecode, initial_witness)
+}
+
+/// Generates a proof, choosing which circuit size determines the amount of SRS to download.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `sizing` - The circuit size basis used to compute the subgroup size.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_with_sizing(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    sizing: SizingMode,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    ProveOptions::new().sizing(sizing).prove(circuit_bytecode, initial_witness)
+}
+
+/// Generates a proof using any SRS source, with the default options.
+///
+/// This is the extension point for environments the bundled sources do not cover: implement
+/// [`Srs`] to serve the points from wherever they live, e.g. an embedded buffer, browser storage
+/// or a fixture in tests, and pass it here. [`prove`], [`prove_local_srs`] and [`prove_net_srs`]
+/// are shorthands for the bundled sources.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `witness` - Initial witness values for the circuit.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_generic(
+    circuit_bytecode: &str,
+    witness: &WitnessMap,
+    mut srs: impl Srs,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let (output, _) =
+        prove_inner(circuit_bytecode, witness, &mut srs, &ProveOptions::default(), false)?;
+    Ok((output.proof, output.verification_key))
+}
+
+/// Generates a proof and checks it with the verifier of the composer that produced it.
+///
+/// The composer keeps the circuit and the verification key computed while proving, so this skips
+/// the circuit decoding, SRS loading and key deserialization a separate call to [`verify`] would
+/// repeat. The proof still goes through the real verifier, which makes this a meaningful sanity
+/// check of the whole flow.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `witness` - Initial witness values for the circuit.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<ProveAndVerifyOutput, NoirRsError>` - Returns the proof, the verification key and the verdict or a NoirRsError.
+pub fn prove_and_verify(
+    circuit_bytecode: &str,
+    witness: &WitnessMap,
+    mut srs: impl Srs,
+) -> Result<ProveAndVerifyOutput, NoirRsError> {
+    let (output, verified) =
+        prove_inner(circuit_bytecode, witness, &mut srs, &ProveOptions::default(), true)?;
+    Ok(ProveAndVerifyOutput {
+        proof: output.proof,
+        verification_key: output.verification_key,
+        verified: verified.unwrap_or(false),
+    })
+}
+
+/// Generates a proof using an SRS source chosen at runtime.
+///
+/// Unlike [`prove_generic`], this is not monomorphized for each source type, so a single call site
+/// serves whichever source a configuration selects. A `Box<dyn Srs>` can also be passed to
+/// [`prove_generic`] directly.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `witness` - Initial witness values for the circuit.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_dyn(
+    circuit_bytecode: &str,
+    witness: &WitnessMap,
+    srs: &mut dyn Srs,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let (output, _) = prove_inner(circuit_bytecode, witness, srs, &ProveOptions::default(), false)?;
+    Ok((output.proof, output.verification_key))
+}
+
+/// Generates a proof using the given SRS source, same as [`prove_generic`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_with_srs<S: Srs>(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    srs: S,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    prove_generic(circuit_bytecode, initial_witness, srs)
+}
+
+/// Generates a proof using the given SRS source, with the inputs of the circuit given in the order
+/// of their witnesses, as in [`witness::from_vec`].
+///
+/// The inputs are checked against the parameters of the circuit first: every parameter must be
+/// given a value, and every input must be a parameter.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `inputs` - The values of the parameters, the first one being `w1`.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a
+///   NoirRsError naming the first parameter without a value.
+pub fn prove_with_inputs<S: Srs>(
+    circuit_bytecode: &str,
+    inputs: Vec<FieldElement>,
+    srs: S,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let DecodedCircuit { circuit, .. } = decode_circuit(circuit_bytecode)?;
+    let parameters = circuit.circuit_arguments();
+    if let Some(missing) = parameters.iter().find(|witness| witness.0 as usize > inputs.len()) {
+        return Err(NoirRsError::MissingInput { witness: missing.0, num_inputs: inputs.len() });
+    }
+    if let Some(witness) =
+        (1..=inputs.len() as u32).find(|&index| !parameters.contains(&Witness(index)))
+    {
+        return Err(NoirRsError::UnexpectedInput { witness });
+    }
+    prove_with_srs(circuit_bytecode, &witness::from_vec(inputs), srs)
+}
+
+/// Lists the witnesses a circuit takes as input, which must all be given a value in the initial
+/// witness, e.g. to prompt for them before proving.
+///
+/// The inputs are the private and public parameters of the circuit, in increasing witness order.
+/// Proving fails with [`NoirRsError::MissingInputs`], listing every input without a value, before
+/// the circuit is executed.
+/// Return values are solved by the circuit, so they are not inputs unless they are also
+/// parameters.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<Vec<Witness>, NoirRsError>` - Returns the input witnesses or a NoirRsError.
+pub fn required_inputs(circuit_bytecode: &str) -> Result<Vec<Witness>, NoirRsError> {
+    let DecodedCircuit { circuit, .. } = decode_circuit(circuit_bytecode)?;
+    Ok(circuit.circuit_arguments().into_iter().collect())
+}
+
+/// Solves a circuit, finding the values of all its witnesses from the initial ones, with the given
+/// black box function solver.
+///
+/// Together with [`prove_from_witness`], this splits [`prove_with_srs`] in two, so that circuits
+/// can be executed with another solver than [`BlackboxSolver`], e.g. one with accelerated Pedersen
+/// hashes. The backend constrains `pedersen_commitment`, `pedersen_hash`,
+/// `fixed_base_scalar_mul` and `schnorr_verify` with its own implementation, so the solver must
+/// return exactly the values barretenberg computes for them, with the same generators and domain
+/// separators, or the proof cannot be generated. The other black box functions are solved by the
+/// ACVM itself.
+///
+/// Unconstrained functions are executed by the Brillig VM of the ACVM. Their foreign calls, such
+/// as `println`, are resolved with an empty result, so an unconstrained function must not depend
+/// on the values returned by an oracle.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `blackbox_solver` - The solver of the black box functions the ACVM does not implement.
+/// # Returns
+/// * `Result<WitnessMap, NoirRsError>` - Returns the solved witness or a NoirRsError.
+pub fn execute<B: BlackBoxFunctionSolver>(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    blackbox_solver: &B,
+) -> Result<WitnessMap, NoirRsError> {
+    let DecodedCircuit { circuit, .. } = decode_circuit(circuit_bytecode)?;
+    // The solver extends the witness it is given, so it works on a copy of the caller's.
+    Ok(execute_circuit(blackbox_solver, circuit, initial_witness.clone())?)
+}
+
+/// Generates a proof from the solved witness of a circuit, as returned by [`execute`], using the
+/// given SRS source.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `solved_witness` - The values of all the witnesses of the circuit.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_from_witness<S: Srs>(
+    circuit_bytecode: &str,
+    solved_witness: &WitnessMap,
+    mut srs: S,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let (output, _) = prove_circuit(
+        circuit_bytecode,
+        |_| Ok(solved_witness.clone()),
+        &mut srs,
+        &ProveOptions::default(),
+        false,
+    )?;
+    Ok((output.proof, output.verification_key))
+}
+
+fn prove_inner<S: Srs + ?Sized>(
+    circuit_bytecode: &str,
+    initial_witness: &WitnessMap,
+    srs: &mut S,
+    options: &ProveOptions,
+    verify: bool,
+) -> Result<(ProveOutput, Option<bool>), NoirRsError> {
+    let solve = |circuit: Circuit| {
+        // Without this check, a missing input only fails the constraint that first reads it.
+        let missing: Vec<u32> = circuit
+            .circuit_arguments()
+            .into_iter()
+            .filter(|input| !initial_witness.contains_key(input))
+            .map(|input| input.0)
+            .collect();
+        if !missing.is_empty() {
+            return Err(NoirRsError::MissingInputs { witnesses: missing });
+        }
+        if !options.skip_input_validation {
+            validate_inputs(&circuit, options.abi.as_ref(), initial_witness)
+                .map_err(NoirRsError::InvalidInputs)?;
+        }
+        let _span = span!("execute");
+        // The solver extends the witness it is given, so it works on a copy of the caller's.
+        Ok(execute_circuit(&BlackboxSolver::new(), circuit, initial_witness.clone())?)
+    };
+    prove_circuit(circuit_bytecode, solve, srs, options, verify)
+}
+
+/// Proves a circuit with the witness returned by `solve` for it.
+fn prove_circuit<S: Srs + ?Sized>(
+    circuit_bytecode: &str,
+    solve: impl FnOnce(Circuit) -> Result<WitnessMap, NoirRsError>,
+    srs: &mut S,
+    options: &ProveOptions,
+    verify: bool,
+) -> Result<(ProveOutput, Option<bool>), NoirRsError> {
+    let prove_span = span!("prove");
+    options.check_cancelled()?;
+    configure_num_threads(options.num_threads)?;
+    let DecodedCircuit { circuit, raw_acir: acir_buffer_uncompressed } =
+        decode_circuit(circuit_bytecode)?;
+    let public_witnesses = circuit.public_inputs();
+
+    let solved_witness = solve(circuit)?;
+    let serialized_solved_witness = bincode::serialize(&solved_witness)?;
+    // The backend reads witnesses missing from the solution, such as unused parameters, as zero.
+    let public_inputs = public_witnesses
+        .indices()
+        .into_iter()
+        .map(|index| solved_witness.get_index(index).copied().unwrap_or_else(FieldElement::zero))
+        .collect();
+
+    options.check_cancelled()?;
+    let (subgroup_size, num_points) =
+        srs_points(&acir_buffer_uncompressed, options.sizing, options.backend)?;
+    prove_span.record("circuit_size", subgroup_size as u64);
+    prove_span.record("num_points", num_points as u64);
+    if let Some(budget) = options.max_memory_bytes {
+        let estimated = estimate_memory(subgroup_size).peak_bytes();
+        if estimated > budget {
+            return Err(NoirRsError::MemoryBudgetExceeded { estimated, budget });
+        }
+    }
+
+    {
+        let srs_span = span!("srs_load");
+        srs_span.record("num_points", num_points as u64);
+        init_srs(srs, num_points, options.skip_srs_check).map_err(|error| match error {
+            NoirRsError::Srs(SrsError::Cancelled) => NoirRsError::Cancelled,
+            error => error,
+        })?;
+    }
+
+    options.check_cancelled()?;
+    let (output, acir_composer) = {
+        let proof_span = span!("create_proof");
+        proof_span.record("circuit_size", subgroup_size as u64);
+        if options.backend == Backend::UltraHonk {
+            let output = ProveOutput {
+                proof: prove_ultra_honk(&acir_buffer_uncompressed, &serialized_solved_witness)?,
+                verification_key: get_ultra_honk_verification_key(&acir_buffer_uncompressed)?,
+                public_inputs,
+                backend_info: backend_info(),
+            };
+            (output, None)
+        } else {
+            let acir_composer = AcirComposer::new(&subgroup_size)?;
+            let output = ProveOutput {
+                proof: acir_composer.create_proof(
+                    &acir_buffer_uncompressed,
+                    &serialized_solved_witness,
+                    options.flavor.is_recursive(),
+                )?,
+                verification_key: match &options.vk_cache {
+                    Some(cache) => cache.get_or_compute(&acir_buffer_uncompressed, || {
+                        Ok(acir_composer.get_verification_key()?)
+                    })?,
+                    None => acir_composer.get_verification_key()?,
+                },
+                public_inputs,
+                backend_info: backend_info(),
+            };
+            if cfg!(debug_assertions) {
+                check_proof_layout(&output.proof, &output.verification_key)?;
+            }
+            (output, Some(acir_composer))
+        }
+    };
 
-    let circuit = Circuit::deserialize_circuit(&acir_buffer).map_err(|e| e.to_string())?;
+    let verified = match (verify, acir_composer) {
+        (false, _) => None,
+        // The composer still holds the circuit and the key it was proven with.
+        (true, Some(acir_composer)) => {
+            Some(acir_composer.verify_proof(&output.proof, options.flavor.is_recursive())?)
+        }
+        (true, None) => Some(verify_ultra_honk(&output.proof, &output.verification_key)?),
+    };
+    Ok((output, verified))
+}
+
+/// Checks that a proof generated by the backend has the length [`proof_layout`] promises.
+fn check_proof_layout(proof: &[u8], verification_key: &[u8]) -> Result<(), NoirRsError> {
+    let layout = proof_layout(verification_key)?;
+    if proof.len() != layout.total_bytes as usize {
+        return Err(NoirRsError::Internal(format!(
+            "the backend generated a proof of {} bytes, but its verification key declares {} \
+             public inputs, for proofs of {} bytes",
+            proof.len(),
+            layout.num_public_inputs,
+            layout.total_bytes
+        )));
+    }
+    Ok(())
+}
 
-    let mut decoder = GzDecoder::new(acir_buffer.as_slice());
-    let mut acir_buffer_uncompressed = Vec::<u8>::new();
-    decoder.read_to_end(&mut acir_buffer_uncompressed).map_err(|e| e.to_string())?;
+/// Computes the verification key of a circuit using the given SRS source, without proving it.
+///
+/// With a `cache`, the key is read from it when it holds the key of the circuit, in which case the
+/// SRS is not loaded.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `srs` - The SRS source the points are loaded from.
+/// * `cache` - The cache to consult and store the key in.
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the verification key or a NoirRsError.
+pub fn get_verification_key<S: Srs>(
+    circuit_bytecode: &str,
+    mut srs: S,
+    cache: Option<&VkCache>,
+) -> Result<Vec<u8>, NoirRsError> {
+    let DecodedCircuit { raw_acir: acir_buffer_uncompressed, .. } =
+        decode_circuit(circuit_bytecode)?;
+    let mut compute = || {
+        let acir_composer = composer_for(&acir_buffer_uncompressed, &mut srs)?;
+        acir_composer.init_proving_key(&acir_buffer_uncompressed)?;
+        Ok(acir_composer.get_verification_key()?)
+    };
+    match cache {
+        Some(cache) => cache.get_or_compute(&acir_buffer_uncompressed, compute),
+        None => compute(),
+    }
+}
 
-    let blackbox_solver = BlackboxSolver::new();
+/// Computes the proving key of a circuit using the given SRS source, to store it with
+/// [`write_pk_file`] and load it with [`Prover::from_artifacts`].
+///
+/// The key holds the polynomials of the circuit, so it is much larger than the circuit itself and
+/// only valid for the exact circuit and SRS it was computed with.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the serialized proving key or a NoirRsError.
+pub fn proving_key_with_srs<S: Srs>(
+    circuit_bytecode: &str,
+    mut srs: S,
+) -> Result<Vec<u8>, NoirRsError> {
+    let DecodedCircuit { raw_acir: acir_buffer_uncompressed, .. } =
+        decode_circuit(circuit_bytecode)?;
+    let acir_composer = composer_for(&acir_buffer_uncompressed, &mut srs)?;
+    Ok(acir_composer.get_proving_key(&acir_buffer_uncompressed)?)
+}
 
-    let solved_witness =
-        execute_circuit(&blackbox_solver, circuit, initial_witness).map_err(|e| e.to_string())?;
-    let serialized_solved_witness =
-        bincode::serialize(&solved_witness).map_err(|e| e.to_string())?;
+/// Computes the proving key of a circuit, downloading the SRS from the same transcript as
+/// [`prove`], see [`proving_key_with_srs`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<Vec<u8>, NoirRsError>` - Returns the serialized proving key or a NoirRsError.
+pub fn proving_key(circuit_bytecode: String) -> Result<Vec<u8>, NoirRsError> {
+    proving_key_with_srs(&circuit_bytecode, NetSrs::from_env()?)
+}
 
-    let circuit_size = get_circuit_sizes(&acir_buffer_uncompressed).map_err(|e| e.to_string())?;
-    let log_value = (circuit_size.total as f64).log2().ceil() as u32;
-    let subgroup_size = 2u32.pow(log_value);
+/// Generates a proof with a proving key computed ahead of time by [`proving_key`], downloading the
+/// SRS from the same transcript as [`prove`].
+///
+/// See [`prove_with_pk_and_srs`] for the requirements on the key.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proving_key` - The serialized proving key of the circuit.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_with_pk(
+    circuit_bytecode: &str,
+    proving_key: &[u8],
+    initial_witness: &WitnessMap,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    prove_with_pk_and_srs(circuit_bytecode, proving_key, initial_witness, NetSrs::from_env()?)
+}
 
-    let srs = NetSrs::new(subgroup_size + 1);
-    srs_init(&srs.data, srs.num_points, &srs.g2_data).map_err(|e| e.to_string())?;
+/// Generates a proof with a proving key computed ahead of time by [`proving_key_with_srs`], using
+/// the given SRS source, which skips computing the key.
+///
+/// The key is only valid for the exact circuit and SRS it was computed with: a key of another
+/// circuit or another SRS yields invalid proofs, and it is not checked here. The SRS is still
+/// loaded on each call, a [`Prover::from_artifacts`] keeps it loaded to prove many times.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proving_key` - The serialized proving key of the circuit.
+/// * `initial_witness` - Initial witness values for the circuit.
+/// * `srs` - The SRS source the key was computed with.
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+pub fn prove_with_pk_and_srs<S: Srs>(
+    circuit_bytecode: &str,
+    proving_key: &[u8],
+    initial_witness: &WitnessMap,
+    mut srs: S,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let DecodedCircuit { circuit, raw_acir: acir_buffer_uncompressed } =
+        decode_circuit(circuit_bytecode)?;
+    let solved_witness = execute_circuit(&BlackboxSolver::new(), circuit, initial_witness.clone())?;
+    let serialized_solved_witness = bincode::serialize(&solved_witness)?;
 
-    let acir_composer = AcirComposer::new(&subgroup_size).map_err(|e| e.to_string())?;
+    let acir_composer = composer_for(&acir_buffer_uncompressed, &mut srs)?;
+    acir_composer.load_proving_key(proving_key)?;
+    let proof = acir_composer.create_proof(
+        &acir_buffer_uncompressed,
+        &serialized_solved_witness,
+        ProofFlavor::default().is_recursive(),
+    )?;
+    Ok((proof, acir_composer.get_verification_key()?))
+}
 
-    Ok((
-        acir_composer
-            .create_proof(&acir_buffer_uncompressed, &serialized_solved_witness, false)
-            .map_err(|e| e.to_string())?,
-        acir_composer.get_verification_key().map_err(|e| e.to_string())?,
-    ))
+/// Loads the SRS a circuit needs for the Plonk backend and returns a composer of its subgroup
+/// size.
+fn composer_for<S: Srs + ?Sized>(
+    acir_buffer_uncompressed: &[u8],
+    srs: &mut S,
+) -> Result<AcirComposer, NoirRsError> {
+    let (subgroup_size, num_points) =
+        srs_points(acir_buffer_uncompressed, SizingMode::default(), Backend::Plonk)?;
+    init_srs(srs, num_points, false)?;
+    Ok(AcirComposer::new(&subgroup_size)?)
 }
 
+/// Verifies a proof, downloading the SRS from the same transcript as [`prove`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
 pub fn verify(
     circuit_bytecode: String,
     proof: Vec<u8>,
     verification_key: Vec<u8>,
-) -> Result<bool, String> {
-    let acir_buffer =
-        general_purpose::STANDARD.decode(circuit_bytecode).map_err(|e| e.to_string())?;
-    let mut decoder = GzDecoder::new(acir_buffer.as_slice());
-    let mut acir_buffer_uncompressed = Vec::<u8>::new();
-    decoder.read_to_end(&mut acir_buffer_uncompressed).map_err(|e| e.to_string())?;
+) -> Result<bool, NoirRsError> {
+    verify_with_srs(circuit_bytecode, proof, verification_key, NetSrs::from_env()?)
+}
+
+/// Verifies a proof using the given SRS source.
+///
+/// The SRS must be the one the proof and the verification key were generated with.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+pub fn verify_with_srs<S: Srs>(
+    circuit_bytecode: String,
+    proof: Vec<u8>,
+    verification_key: Vec<u8>,
+    srs: S,
+) -> Result<bool, NoirRsError> {
+    verify_with_options(circuit_bytecode, proof, verification_key, srs, &VerifyOptions::default())
+}
+
+/// Verifies a proof using the given SRS source and options.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// * `srs` - The SRS source the points are loaded from.
+/// * `options` - Options matching the ones the proof was generated with.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+pub fn verify_with_options<S: Srs>(
+    circuit_bytecode: String,
+    proof: Vec<u8>,
+    verification_key: Vec<u8>,
+    srs: S,
+    options: &VerifyOptions,
+) -> Result<bool, NoirRsError> {
+    Ok(verify_detailed_with_options(circuit_bytecode, proof, verification_key, srs, options)?
+        .is_valid())
+}
+
+/// Verifies a proof without its verification key, which is computed from the circuit, downloading
+/// the SRS from the same transcript as [`prove`].
+///
+/// Computing the key commits to every selector of the circuit and needs the SRS points of the
+/// whole circuit, so this is much slower than [`verify`] with a stored key, which is best cached
+/// with [`VkCache`] when the same circuit is verified repeatedly.
+/// # Arguments
+/// * `proof` - Buffer representing the proof, generated with the default options of [`prove`].
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+pub fn verify_with_circuit(proof: &[u8], circuit_bytecode: String) -> Result<bool, NoirRsError> {
+    verify_with_circuit_and_srs(proof, circuit_bytecode, NetSrs::from_env()?)
+}
+
+/// Verifies a proof without its verification key, which is computed from the circuit using the
+/// given SRS source, see [`verify_with_circuit`].
+/// # Arguments
+/// * `proof` - Buffer representing the proof, generated with the default options of [`prove`].
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `srs` - The SRS source the proof was generated with.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+pub fn verify_with_circuit_and_srs<S: Srs>(
+    proof: &[u8],
+    circuit_bytecode: String,
+    mut srs: S,
+) -> Result<bool, NoirRsError> {
+    let DecodedCircuit { raw_acir: acir_buffer_uncompressed, .. } =
+        decode_circuit(&circuit_bytecode)?;
+    let acir_composer = composer_for(&acir_buffer_uncompressed, &mut srs)?;
+    acir_composer.init_proving_key(&acir_buffer_uncompressed)?;
+    acir_composer.init_verification_key()?;
+    Ok(verify_loaded(&acir_composer, proof, ProofFlavor::default()).is_valid())
+}
+
+/// Verifies a proof, telling an invalid proof apart from a failure to verify it.
+///
+/// The SRS is downloaded from the same transcript as [`prove`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<VerifyOutcome, NoirRsError>` - Returns whether the proof is valid, or a NoirRsError if it could not be checked.
+pub fn verify_detailed(
+    circuit_bytecode: String,
+    proof: Vec<u8>,
+    verification_key: Vec<u8>,
+) -> Result<VerifyOutcome, NoirRsError> {
+    verify_detailed_with_options(
+        circuit_bytecode,
+        proof,
+        verification_key,
+        NetSrs::from_env()?,
+        &VerifyOptions::default(),
+    )
+}
+
+/// Verifies a proof using the given SRS source and options, telling an invalid proof apart from a
+/// failure to verify it.
+///
+/// The UltraHonk verifier reads the key and the proof in a single call, so with
+/// [`Backend::UltraHonk`] a malformed proof is reported as an error.
+///
+/// A proof already verified against the same key and options gets its stored verdict when the
+/// cache of [`set_verify_cache_capacity`] is enabled.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// * `srs` - The SRS source the points are loaded from.
+/// * `options` - Options matching the ones the proof was generated with.
+/// # Returns
+/// * `Result<VerifyOutcome, NoirRsError>` - Returns whether the proof is valid, or a NoirRsError if it could not be checked.
+pub fn verify_detailed_with_options<S: Srs>(
+    circuit_bytecode: String,
+    proof: Vec<u8>,
+    verification_key: Vec<u8>,
+    srs: S,
+    options: &VerifyOptions,
+) -> Result<VerifyOutcome, NoirRsError> {
+    cached_verify(&proof, &verification_key, options, || {
+        verify_uncached(Some(circuit_bytecode), &proof, &verification_key, srs, options)
+    })
+}
+
+/// Checks a proof, sizing the composer from the circuit when its bytecode is given, and from the
+/// circuit size recorded in the verification key otherwise.
+fn verify_uncached<S: Srs>(
+    circuit_bytecode: Option<String>,
+    proof: &[u8],
+    verification_key: &[u8],
+    mut srs: S,
+    options: &VerifyOptions,
+) -> Result<VerifyOutcome, NoirRsError> {
+    let verify_span = span!("verify");
+    if options.backend == Backend::UltraHonk {
+        // The UltraHonk verifier only needs the G2 point of the SRS.
+        init_srs(&mut srs, 1, options.skip_srs_check)?;
+        return Ok(match verify_ultra_honk(proof, verification_key)? {
+            true => VerifyOutcome::Valid,
+            false => VerifyOutcome::Invalid {
+                reason: String::from("the proof does not satisfy the verifier"),
+            },
+        });
+    }
+
+    let (subgroup_size, num_points) = match circuit_bytecode {
+        Some(circuit_bytecode) => {
+            let acir_buffer = decode_bytecode(&circuit_bytecode)?;
+            let acir_buffer_uncompressed = decompress_bytecode(&acir_buffer)?;
+            srs_points(&acir_buffer_uncompressed, SizingMode::Total, Backend::Plonk)?
+        }
+        // Verifying only reads the G2 point, the first G1 point is loaded to initialize the SRS.
+        None => (vk_circuit_size(verification_key)?, 1),
+    };
+    verify_span.record("circuit_size", subgroup_size as u64);
+    verify_span.record("num_points", num_points as u64);
+
+    init_srs(&mut srs, num_points, options.skip_srs_check)?;
 
-    let circuit_size = get_circuit_sizes(&acir_buffer_uncompressed).map_err(|e| e.to_string())?;
-    let log_value = (circuit_size.total as f64).log2().ceil() as u32;
-    let subgroup_size = 2u32.pow(log_value);
+    let acir_composer = AcirComposer::new(&subgroup_size)?;
+    acir_composer.load_verification_key(verification_key)?;
+    Ok(verify_loaded(&acir_composer, proof, options.flavor))
+}
+
+/// Reads the number of public inputs a Plonk verification key expects.
+///
+/// A proof starts with that many 32-byte big-endian field elements holding the public inputs,
+/// followed by the proof body. The key starts with the big-endian `circuit_type`, `circuit_size`
+/// and `num_public_inputs` 32-bit words.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<u32, NoirRsError>` - Returns the number of public inputs or a NoirRsError.
+pub fn num_public_inputs(verification_key: &[u8]) -> Result<u32, NoirRsError> {
+    match verification_key.get(8..12) {
+        Some(bytes) => Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        None => Err(NoirRsError::InvalidVerificationKey(verification_key.len())),
+    }
+}
+
+/// Splits the public inputs off the front of a proof.
+///
+/// Barretenberg prepends the public inputs to the proof as 32-byte big-endian field elements. If
+/// the proof is shorter than `num_public_inputs` fields, every whole field is returned as a public
+/// input and the proof body is empty. A circuit without public inputs yields an empty `Vec` and
+/// the whole proof as the body.
+/// # Arguments
+/// * `proof` - Buffer representing the proof.
+/// * `num_public_inputs` - Number of public inputs of the circuit, see [`num_public_inputs`].
+/// # Returns
+/// * `(Vec<FieldElement>, Vec<u8>)` - Returns the public inputs and the remaining proof body.
+pub fn split_proof(proof: &[u8], num_public_inputs: u32) -> (Vec<FieldElement>, Vec<u8>) {
+    let split = (num_public_inputs as usize * 32).min(proof.len());
+    let (public_inputs, body) = proof.split_at(split - split % 32);
+    let public_inputs = public_inputs.chunks(32).map(FieldElement::from_be_bytes_reduce).collect();
+    (public_inputs, body.to_vec())
+}
+
+/// Splits the public inputs off the front of a proof, reading their number from the verification
+/// key.
+/// # Arguments
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key the proof was generated with.
+/// # Returns
+/// * `Result<(Vec<FieldElement>, Vec<u8>), NoirRsError>` - Returns the public inputs and the
+///   remaining proof body, or a NoirRsError.
+pub fn split_proof_with_vk(
+    proof: &[u8],
+    verification_key: &[u8],
+) -> Result<(Vec<FieldElement>, Vec<u8>), NoirRsError> {
+    Ok(split_proof(proof, num_public_inputs(verification_key)?))
+}
+
+/// Reads the public inputs off the front of a proof, reading their number from the verification
+/// key. A circuit without public inputs yields an empty `Vec`.
+/// # Arguments
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key the proof was generated with.
+/// # Returns
+/// * `Result<Vec<FieldElement>, NoirRsError>` - Returns the public inputs, or a NoirRsError if the
+///   verification key is too short.
+pub fn extract_public_inputs(
+    proof: &[u8],
+    verification_key: &[u8],
+) -> Result<Vec<FieldElement>, NoirRsError> {
+    Ok(split_proof_with_vk(proof, verification_key)?.0)
+}
+
+/// Computes a stable identifier of a circuit, suitable as a cache key for verification keys,
+/// composers or SRS sizes.
+///
+/// The hash is the SHA-256 of the decompressed ACIR, so it depends only on the circuit structure:
+/// it ignores the witness, and how the bytecode was compressed.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<[u8; 32], NoirRsError>` - Returns the hash of the circuit or a NoirRsError.
+pub fn circuit_hash(circuit_bytecode: &str) -> Result<[u8; 32], NoirRsError> {
+    let acir_buffer = decode_bytecode(circuit_bytecode)?;
+    let acir_buffer_uncompressed = decompress_bytecode(&acir_buffer)?;
+
+    Ok(sha256(&acir_buffer_uncompressed)?)
+}
+
+/// Largest proof [`verify_from_reader`] reads, in bytes, which holds the 32-byte values of more
+/// than 30000 public inputs.
+pub const MAX_READER_PROOF_SIZE: usize = 1 << 20;
+
+/// Verifies a proof read from a reader, such as a socket, against a verification key generated
+/// with the ignition transcript.
+///
+/// The proof is read to the end before being verified, rejecting readers yielding more than
+/// [`MAX_READER_PROOF_SIZE`] bytes. Verification only needs the G2 point of the SRS, which is
+/// built in, so no circuit bytecode is required and nothing is downloaded.
+/// # Arguments
+/// * `proof` - Reader yielding the proof bytes.
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+pub fn verify_from_reader<R: Read>(
+    proof: &mut R,
+    verification_key: &[u8],
+) -> Result<bool, NoirRsError> {
+    verify_from_reader_with_options(proof, verification_key, &VerifyOptions::default())
+}
 
-    let srs = NetSrs::new(subgroup_size + 1);
-    srs_init(&srs.data, srs.num_points, &srs.g2_data).map_err(|e| e.to_string())?;
+/// Same as [`verify_from_reader`], with options matching the ones the proof was generated with.
+/// # Arguments
+/// * `proof` - Reader yielding the proof bytes.
+/// * `verification_key` - Buffer representing the verification key.
+/// * `options` - Options matching the ones the proof was generated with.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or
+///   `NoirRsError::ProofTooLarge` if the reader yields more than [`MAX_READER_PROOF_SIZE`] bytes.
+pub fn verify_from_reader_with_options<R: Read>(
+    proof: &mut R,
+    verification_key: &[u8],
+    options: &VerifyOptions,
+) -> Result<bool, NoirRsError> {
+    // Reading at most one byte past the cap is enough to tell it was exceeded.
+    let mut proof_buffer = Vec::<u8>::new();
+    proof.take(MAX_READER_PROOF_SIZE as u64 + 1).read_to_end(&mut proof_buffer)?;
+    if proof_buffer.len() > MAX_READER_PROOF_SIZE {
+        return Err(NoirRsError::ProofTooLarge { limit: MAX_READER_PROOF_SIZE });
+    }
 
-    let acir_composer = AcirComposer::new(&subgroup_size).map_err(|e| e.to_string())?;
-    acir_composer.load_verification_key(&verification_key).map_err(|e| e.to_string())?;
-    acir_composer.verify_proof(&proof, false).map_err(|e| e.to_string())
+    let outcome = cached_verify(&proof_buffer, verification_key, options, || {
+        verify_uncached(None, &proof_buffer, verification_key, IgnitionVerifierSrs::new(), options)
+    })?;
+    Ok(outcome.is_valid())
 }
 
 #[cfg(test)]
 mod tests {
-    use acir::native_types::{Witness, WitnessMap};
-    use acvm::FieldElement;
+    use std::{
+        cell::Cell,
+        collections::BTreeMap,
+        io::{Read, Write},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Instant,
+    };
+
+    use acir::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness, WitnessMap},
+    };
+    use acvm::{
+        blackbox_solver::{sha256, BlackBoxResolutionError},
+        BlackBoxFunctionSolver, FieldElement,
+    };
+    use ark_bn254::{Fq, Fr, G1Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::{BigInteger, PrimeField};
+    use base64::{engine::general_purpose, Engine};
+    use flate2::{
+        bufread::GzDecoder,
+        write::{GzEncoder, ZlibEncoder},
+        Compression,
+    };
+    use noir_rs_barretenberg::{
+        srs::{
+            mock::{MockSrsServer, MockTranscript},
+            SrsError,
+        },
+        BackendError,
+    };
+    use noirc_abi::{AbiParameter, AbiType, AbiVisibility, Sign};
 
-    use crate::{prove, verify};
+    use crate::{
+        backend_info,
+        bytecode::DECOMPRESSIONS,
+        circuit_hash, circuit_stats, decode_bytecode, decode_circuit, decode_from_transport,
+        decompress_bytecode, download_srs, encode_for_transport, estimate_memory,
+        estimate_peak_memory, estimate_srs_bytes, execute, execute_circuit,
+        extract_aggregation_object, extract_public_inputs, gate_report, get_circuit_sizes,
+        get_verification_key, init_srs, inject_aggregation_object, num_points_for_circuit,
+        num_public_inputs, proof_as_fields, proof_layout, prove, prove_and_verify, prove_dyn,
+        prove_from_witness, prove_generic, prove_local_srs, prove_net_srs, prove_with_deadline,
+        prove_with_inputs, prove_with_pk_and_srs, prove_with_srs, proving_key_with_srs,
+        required_inputs,
+        size_cache::SizeCache,
+        solidity_verifier_with_srs, split_proof, split_proof_with_vk, subgroup_size,
+        subgroup_size_for,
+        transport::decode_from_transport_with_limit,
+        trim_srs, validate_aggregation_object, validate_inputs, verifier_stats, verify,
+        verify_cache::{verify_with_cache, VerifyCache},
+        verify_detailed_with_options, verify_from_reader, verify_recursive_with_srs,
+        verify_with_circuit_and_srs, verify_with_options, verify_with_srs, vk_as_fields_with_srs,
+        vk_hash,
+        witness::{self, WitnessDiff},
+        witness_from_json, witness_to_json, write_pk_file, write_vk_file, Abi, Backend,
+        BlackboxSolver, CancellationToken, CircuitExecutor, CircuitSizes, DevSrs, FormatVersion,
+        InputViolation, LocalSrs, NoirRsError, Proof, ProofFlavor, ProofLayout, ProveOptions,
+        Prover, SizeError, SizingMode, Srs, StepResult, VerificationKey, Verifier, VerifyOptions,
+        VerifyOutcome, VkCache, ACIR_VERSION, AGGREGATION_OBJECT_SIZE, MAX_READER_PROOF_SIZE,
+        MAX_SUBGROUP_SIZE, NARGO_VERSION,
+    };
 
     const BYTECODE: &str = "H4sIAAAAAAAA/7VTQQ4DIQjE3bXHvgUWXfHWr9TU/f8TmrY2Ma43cRJCwmEYBrAAYOGKteRHyYyHcznsmZieuMckHp1Ph5CQF//ahTmLkxBTDBjJcabTRz7xB1Nx4RhoUdS16un6cpmOl6bxEsdAmpprvVuJD5bOLdwmzAJNn9a/e6em2nzGcrYJvBb0jn7W3FZ/R1hRXjSP+mBB/5FMpbN+oj/eG6c6pXEFAAA=";
+    const DEV_SRS_POINTS: u32 = 1 << 10;
+    /// Set to prove with downloaded ignition points where tests otherwise use the dev SRS.
+    const DOWNLOAD_SRS_VAR: &str = "NOIR_RS_TEST_DOWNLOAD_SRS";
+
+    /// Returns the path of an ignition transcript holding [`DEV_SRS_POINTS`] points, downloading it
+    /// on first use.
+    fn download_ignition_transcript() -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("noir_rs_ignition_{}.dat", DEV_SRS_POINTS));
+        if !path.exists() {
+            download_srs(None, &path, Some(DEV_SRS_POINTS)).unwrap();
+        }
+        path
+    }
+
+    /// Returns [`download_ignition_transcript`], or `None` if [`DOWNLOAD_SRS_VAR`] is not set.
+    fn ignition_transcript() -> Option<std::path::PathBuf> {
+        std::env::var_os(DOWNLOAD_SRS_VAR)?;
+        Some(download_ignition_transcript())
+    }
+
+    /// Returns a circuit squaring its private input `num_opcodes` times and returning the result,
+    /// with its initial witness.
+    ///
+    /// Each opcode is one gate, which stands in for the circuits of hundreds of thousands of gates
+    /// the size related regressions were found with, too large to be committed as fixtures.
+    fn squaring_chain(num_opcodes: u32) -> (String, WitnessMap) {
+        let opcodes = (1..=num_opcodes)
+            .map(|i| {
+                Opcode::Arithmetic(Expression {
+                    mul_terms: vec![(FieldElement::one(), Witness(i), Witness(i))],
+                    linear_combinations: vec![(-FieldElement::one(), Witness(i + 1))],
+                    q_c: FieldElement::zero(),
+                })
+            })
+            .collect();
+        let circuit = Circuit {
+            current_witness_index: num_opcodes + 1,
+            opcodes,
+            private_parameters: [Witness(1)].into(),
+            return_values: PublicInputs([Witness(num_opcodes + 1)].into()),
+            ..Circuit::default()
+        };
+        let bytecode = general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit));
+        (bytecode, witness::from_vec(vec![FieldElement::from(3u128)]))
+    }
+
+    /// Returns the sizes the composer reports for a circuit.
+    fn circuit_sizes(bytecode: &str) -> CircuitSizes {
+        let acir_buffer = decode_bytecode(bytecode).unwrap();
+        get_circuit_sizes(&decompress_bytecode(&acir_buffer).unwrap()).unwrap()
+    }
+
+    /// Returns [`BYTECODE`] with an opcode asserting that `w1` is zero, a different circuit of the
+    /// same subgroup size.
+    fn bytecode_asserting_w1_is_zero() -> String {
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut circuit = Circuit::deserialize_circuit(&acir_buffer).unwrap();
+        circuit.opcodes.push(Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![(FieldElement::one(), Witness(1))],
+            q_c: FieldElement::zero(),
+        }));
+        general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit))
+    }
+
+    /// Returns a [`squaring_chain`] of exactly `num_gates` gates, accounting for the gates the
+    /// composer adds to every circuit.
+    fn squaring_chain_of_gates(num_gates: u32) -> (String, WitnessMap) {
+        let overhead = circuit_sizes(&squaring_chain(1).0).exact - 1;
+        let chain = squaring_chain(num_gates - overhead);
+        assert_eq!(circuit_sizes(&chain.0).exact, num_gates);
+        chain
+    }
 
     #[test]
     fn test_prove_verify() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) =
+            prove_generic(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let verdict =
+            verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+                .unwrap();
+        assert!(verdict);
+    }
+
+    #[test]
+    fn test_prove_with_inputs() {
+        let inputs = vec![FieldElement::zero(), FieldElement::one()];
+        assert_eq!(witness::from_vec(inputs.clone()).get(&Witness(2)), Some(&FieldElement::one()));
+
+        assert!(matches!(
+            prove_with_inputs(BYTECODE, vec![FieldElement::zero()], DevSrs::new(DEV_SRS_POINTS)),
+            Err(NoirRsError::MissingInput { witness: 2, num_inputs: 1 })
+        ));
+        let too_many = vec![FieldElement::zero(), FieldElement::one(), FieldElement::one()];
+        assert!(matches!(
+            prove_with_inputs(BYTECODE, too_many, DevSrs::new(DEV_SRS_POINTS)),
+            Err(NoirRsError::UnexpectedInput { witness: 3 })
+        ));
+
+        let (proof, vk) = prove_with_inputs(BYTECODE, inputs, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let verdict =
+            verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+                .unwrap();
+        assert!(verdict);
+    }
+
+    #[test]
+    fn test_required_inputs() {
+        assert_eq!(required_inputs(BYTECODE).unwrap(), vec![Witness(1), Witness(2)]);
+        assert!(matches!(required_inputs("not base64"), Err(NoirRsError::Base64Decode(_))));
+
         let mut initial_witness = WitnessMap::new();
         initial_witness.insert(Witness(1), FieldElement::zero());
-        initial_witness.insert(Witness(2), FieldElement::one());
+        assert!(matches!(
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)),
+            Err(NoirRsError::MissingInputs { witnesses }) if witnesses == [2]
+        ));
+        let error =
+            prove_with_srs(BYTECODE, &WitnessMap::new(), DevSrs::new(DEV_SRS_POINTS)).unwrap_err();
+        assert_eq!(error.to_string(), "Circuit inputs w1, w2 have no value in the initial witness");
+        assert!(matches!(error, NoirRsError::MissingInputs { witnesses } if witnesses == [1, 2]));
+    }
 
-        let (proof, vk) = prove(String::from(BYTECODE), initial_witness).unwrap();
-        let verdict = verify(String::from(BYTECODE), proof, vk).unwrap();
-        assert!(verdict);
+    #[test]
+    fn test_validate_inputs() {
+        // `fn main(a: u8, b: bool) -> pub Field { a + b }`, where only `a` is range constrained.
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                    input: FunctionInput { witness: Witness(1), num_bits: 8 },
+                }),
+                Opcode::Arithmetic(Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![
+                        (FieldElement::one(), Witness(1)),
+                        (FieldElement::one(), Witness(2)),
+                        (-FieldElement::one(), Witness(3)),
+                    ],
+                    q_c: FieldElement::zero(),
+                }),
+            ],
+            private_parameters: [Witness(1), Witness(2)].into(),
+            return_values: PublicInputs([Witness(3)].into()),
+            ..Circuit::default()
+        };
+        let parameter = |name: &str, typ| AbiParameter {
+            name: name.to_string(),
+            typ,
+            visibility: AbiVisibility::Private,
+        };
+        let abi = Abi {
+            parameters: vec![
+                parameter("a", AbiType::Integer { sign: Sign::Unsigned, width: 8 }),
+                parameter("b", AbiType::Boolean),
+            ],
+            param_witnesses: BTreeMap::from([
+                (String::from("a"), vec![Witness(1)..Witness(2)]),
+                (String::from("b"), vec![Witness(2)..Witness(3)]),
+            ]),
+            return_type: Some(AbiType::Field),
+            return_witnesses: vec![Witness(3)],
+        };
+        let bytecode = general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit));
+        let initial_witness = witness::from_vec(vec![300u128.into(), 2u128.into()]);
+
+        let a = InputViolation { witness: Witness(1), num_bits: 8, value: 300u128.into() };
+        let b = InputViolation { witness: Witness(2), num_bits: 1, value: 2u128.into() };
+        assert_eq!(validate_inputs(&circuit, None, &initial_witness), Err(vec![a.clone()]));
+        assert_eq!(
+            validate_inputs(&circuit, Some(&abi), &initial_witness),
+            Err(vec![a.clone(), b.clone()])
+        );
+        let valid = witness::from_vec(vec![255u128.into(), FieldElement::one()]);
+        assert_eq!(validate_inputs(&circuit, Some(&abi), &valid), Ok(()));
+
+        // The inputs are checked before the SRS is downloaded.
+        let (url, server) = serve_dev_srs();
+        let error = ProveOptions::new()
+            .srs_url(&url)
+            .abi(abi)
+            .prove(&bytecode, &initial_witness)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Inputs do not fit their declared types: w1 = 300 does not fit in 8 bits, \
+             w2 = 2 does not fit in 1 bits"
+        );
+        assert!(matches!(error, NoirRsError::InvalidInputs(violations) if violations == [a, b]));
+        let unchecked = ProveOptions::new()
+            .srs_url(&url)
+            .skip_input_validation(true)
+            .prove(&bytecode, &initial_witness);
+        assert!(matches!(unchecked, Err(NoirRsError::Execution(_))));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn test_prove_dyn_with_runtime_source() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let source = || -> Box<dyn Srs> {
+            match ignition_transcript() {
+                Some(path) => Box::new(LocalSrs::new(path)),
+                None => Box::new(DevSrs::new(DEV_SRS_POINTS)),
+            }
+        };
+        let (proof, vk) = prove_dyn(BYTECODE, &initial_witness, source().as_mut()).unwrap();
+        assert!(verify_with_srs(String::from(BYTECODE), proof, vk, source()).unwrap());
+
+        let (proof, vk) = prove_generic(BYTECODE, &initial_witness, source()).unwrap();
+        assert!(verify_with_srs(String::from(BYTECODE), proof, vk, source()).unwrap());
+    }
+
+    #[test]
+    fn test_decode_bytecode_variants() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let encodings = [
+            general_purpose::STANDARD.encode(&acir_buffer),
+            general_purpose::STANDARD_NO_PAD.encode(&acir_buffer),
+            general_purpose::URL_SAFE.encode(&acir_buffer),
+            general_purpose::URL_SAFE_NO_PAD.encode(&acir_buffer),
+        ];
+        // The URL-safe encodings must differ from the standard ones for the test to be meaningful.
+        assert_ne!(encodings[0], encodings[2]);
+        for encoding in encodings {
+            let wrapped = encoding
+                .as_bytes()
+                .chunks(76)
+                .map(|line| std::str::from_utf8(line).unwrap())
+                .collect::<Vec<_>>()
+                .join("\r\n");
+            for bytecode in [encoding.clone(), format!(" {}\n", wrapped)] {
+                assert_eq!(decode_bytecode(&bytecode).unwrap(), acir_buffer);
+                prove_with_srs(&bytecode, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+            }
+        }
+
+        // A character of the standard alphabet in URL-safe input is reported.
+        let mut mixed = general_purpose::URL_SAFE.encode(&acir_buffer);
+        let position = mixed.rfind('_').unwrap();
+        mixed.replace_range(position..=position, "/");
+        assert!(matches!(
+            decode_bytecode(&mixed),
+            Err(NoirRsError::InvalidBytecodeCharacter { position: p, .. }) if p == position
+        ));
+        assert!(matches!(
+            decode_bytecode("H4sI\nAA*A"),
+            Err(NoirRsError::InvalidBytecodeCharacter { position: 7, character: '*' })
+        ));
+        assert!(matches!(decode_bytecode("H4sIA"), Err(NoirRsError::Base64Decode(_))));
+    }
+
+    #[test]
+    fn test_decode_circuit_outputs_agree() {
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let decoded = decode_circuit(BYTECODE).unwrap();
+
+        assert_eq!(decoded.circuit, Circuit::deserialize_circuit(&acir_buffer).unwrap());
+        assert_eq!(decoded.raw_acir, decompress_bytecode(&acir_buffer).unwrap());
+        assert_eq!(bincode::serialize(&decoded.circuit).unwrap(), decoded.raw_acir);
+    }
+
+    #[test]
+    #[ignore = "proves a circuit of 365461 gates, which takes minutes and gigabytes of memory"]
+    fn test_prove_verify_large_circuit() {
+        let (bytecode, initial_witness) = squaring_chain(365_461);
+        let acir_buffer = decode_bytecode(&bytecode).unwrap();
+        let decoded = decode_circuit(&bytecode).unwrap();
+        assert_eq!(decoded.circuit.opcodes.len(), 365_461);
+        assert_eq!(decoded.raw_acir, decompress_bytecode(&acir_buffer).unwrap());
+        assert_eq!(bincode::serialize(&decoded.circuit).unwrap(), decoded.raw_acir);
+
+        let srs_points = num_points_for_circuit(&bytecode).unwrap();
+        DECOMPRESSIONS.with(|count| count.set(0));
+        let (proof, vk) =
+            prove_generic(&bytecode, &initial_witness, DevSrs::new(srs_points)).unwrap();
+        // The ACVM and the composer share a single decompression of the bytecode.
+        assert_eq!(DECOMPRESSIONS.with(Cell::get), 1);
+        assert!(verify_with_srs(bytecode, proof, vk, DevSrs::new(srs_points)).unwrap());
+    }
+
+    #[test]
+    fn test_prove_cancelled_before_proof_generation() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let result = prove_with_deadline(BYTECODE, &initial_witness, Instant::now());
+        assert!(matches!(result, Err(NoirRsError::Cancelled)));
+
+        let token = CancellationToken::new();
+        let options =
+            ProveOptions::new().srs(DevSrs::new(DEV_SRS_POINTS)).cancellation(token.clone());
+        token.cancel();
+        assert!(matches!(options.prove(BYTECODE, &initial_witness), Err(NoirRsError::Cancelled)));
+    }
+
+    #[test]
+    fn test_prover_pools_composers_by_subgroup_size() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let other_bytecode = bytecode_asserting_w1_is_zero();
+        assert_ne!(circuit_hash(BYTECODE).unwrap(), circuit_hash(&other_bytecode).unwrap());
+        assert_eq!(circuit_sizes(BYTECODE).subgroup, circuit_sizes(&other_bytecode).subgroup);
+
+        let mut prover = Prover::new(DevSrs::new(DEV_SRS_POINTS));
+        let mut proofs = Vec::new();
+        for bytecode in [BYTECODE, &other_bytecode, BYTECODE] {
+            proofs.push((bytecode, prover.prove(bytecode, &initial_witness).unwrap()));
+        }
+        assert_eq!(prover.subgroup_sizes().len(), 1);
+
+        for (bytecode, (proof, vk)) in proofs {
+            let verified =
+                verify_with_srs(String::from(bytecode), proof, vk, DevSrs::new(DEV_SRS_POINTS));
+            assert!(verified.unwrap());
+        }
+    }
+
+    #[test]
+    fn test_prover_from_artifacts() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let dir = std::env::temp_dir().join(format!("noir_rs_artifacts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (pk_path, vk_path) = (dir.join("pk"), dir.join("vk"));
+        write_pk_file(
+            &pk_path,
+            &proving_key_with_srs(BYTECODE, DevSrs::new(DEV_SRS_POINTS)).unwrap(),
+        )
+        .unwrap();
+        let vk = get_verification_key(BYTECODE, DevSrs::new(DEV_SRS_POINTS), None).unwrap();
+        write_vk_file(&vk_path, &vk).unwrap();
+
+        let mut prover =
+            Prover::from_artifacts(BYTECODE, &pk_path, &vk_path, DevSrs::new(DEV_SRS_POINTS), true)
+                .unwrap();
+        let (proof, proven_vk) = prover.prove(BYTECODE, &initial_witness).unwrap();
+
+        assert_eq!(proven_vk, vk);
+        assert!(verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+            .unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prove_with_pk() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let proving_key = proving_key_with_srs(BYTECODE, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+
+        for _ in 0..2 {
+            let (proof, vk) = prove_with_pk_and_srs(
+                BYTECODE,
+                &proving_key,
+                &initial_witness,
+                DevSrs::new(DEV_SRS_POINTS),
+            )
+            .unwrap();
+            let verdict =
+                verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+                    .unwrap();
+            assert!(verdict);
+        }
+    }
+
+    #[test]
+    fn test_prover_from_artifacts_rejects_mismatched_vk() {
+        let other_bytecode = bytecode_asserting_w1_is_zero();
+
+        let dir = std::env::temp_dir().join(format!("noir_rs_mismatch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (pk_path, vk_path) = (dir.join("pk"), dir.join("vk"));
+        write_pk_file(
+            &pk_path,
+            &proving_key_with_srs(BYTECODE, DevSrs::new(DEV_SRS_POINTS)).unwrap(),
+        )
+        .unwrap();
+        let other_vk =
+            get_verification_key(&other_bytecode, DevSrs::new(DEV_SRS_POINTS), None).unwrap();
+        write_vk_file(&vk_path, &other_vk).unwrap();
+
+        let result =
+            Prover::from_artifacts(BYTECODE, &pk_path, &vk_path, DevSrs::new(DEV_SRS_POINTS), true);
+        match result {
+            Err(NoirRsError::ArtifactMismatch { artifact, path, expected, found }) => {
+                assert_eq!(artifact, "verification key");
+                assert_eq!(path, vk_path);
+                assert_eq!(found, hex::encode(sha256(&other_vk).unwrap()));
+                assert_ne!(expected, found);
+            }
+            Err(error) => panic!("unexpected error {:?}", error),
+            Ok(_) => panic!("mismatched verification key was accepted"),
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Delegates to [`BlackboxSolver`], counting the calls.
+    #[derive(Default)]
+    struct CountingSolver {
+        calls: AtomicUsize,
+    }
+
+    impl BlackBoxFunctionSolver for CountingSolver {
+        fn schnorr_verify(
+            &self,
+            public_key_x: &FieldElement,
+            public_key_y: &FieldElement,
+            signature: &[u8],
+            message: &[u8],
+        ) -> Result<bool, BlackBoxResolutionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            BlackboxSolver::new().schnorr_verify(public_key_x, public_key_y, signature, message)
+        }
+
+        fn pedersen_commitment(
+            &self,
+            inputs: &[FieldElement],
+            domain_separator: u32,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            BlackboxSolver::new().pedersen_commitment(inputs, domain_separator)
+        }
+
+        fn pedersen_hash(
+            &self,
+            inputs: &[FieldElement],
+            domain_separator: u32,
+        ) -> Result<FieldElement, BlackBoxResolutionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            BlackboxSolver::new().pedersen_hash(inputs, domain_separator)
+        }
+
+        fn fixed_base_scalar_mul(
+            &self,
+            low: &FieldElement,
+            high: &FieldElement,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            BlackboxSolver::new().fixed_base_scalar_mul(low, high)
+        }
+    }
+
+    #[test]
+    fn test_prove_with_custom_blackbox_solver() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        // Hashes `w1` and `w2` into the next witness.
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut circuit = Circuit::deserialize_circuit(&acir_buffer).unwrap();
+        let output = Witness(circuit.current_witness_index + 1);
+        circuit.current_witness_index = output.witness_index();
+        circuit.opcodes.push(Opcode::BlackBoxFuncCall(BlackBoxFuncCall::PedersenHash {
+            inputs: vec![
+                FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() },
+                FunctionInput { witness: Witness(2), num_bits: FieldElement::max_num_bits() },
+            ],
+            domain_separator: 0,
+            output,
+        }));
+        let bytecode = general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit));
+
+        let solver = CountingSolver::default();
+        let solved_witness = execute(&bytecode, &initial_witness, &solver).unwrap();
+        assert_eq!(solver.calls.load(Ordering::SeqCst), 1);
+        let expected = BlackboxSolver::new()
+            .pedersen_hash(&[FieldElement::zero(), FieldElement::one()], 0)
+            .unwrap();
+        assert_eq!(solved_witness.get(&output), Some(&expected));
+
+        let (proof, vk) =
+            prove_from_witness(&bytecode, &solved_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        assert!(verify_with_srs(bytecode, proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap());
+    }
+
+    /// Delegates to a [`DevSrs`], counting the loads.
+    struct CountingSrs {
+        srs: DevSrs,
+        loads: Arc<AtomicUsize>,
+    }
+
+    impl Srs for CountingSrs {
+        fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+            self.loads.fetch_add(1, Ordering::SeqCst);
+            self.srs.load_data(num_points)
+        }
+
+        fn g1_data(&self) -> &[u8] {
+            self.srs.g1_data()
+        }
+
+        fn g2_data(&self) -> &[u8] {
+            self.srs.g2_data()
+        }
+
+        fn num_points(&self) -> u32 {
+            self.srs.num_points()
+        }
+
+        fn clear(&mut self) {
+            self.srs.clear()
+        }
+
+        fn max_points(&self) -> Option<u32> {
+            self.srs.max_points()
+        }
+    }
+
+    #[test]
+    fn test_prove_and_verify_shares_setup() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let loads = Arc::new(AtomicUsize::new(0));
+        let srs = CountingSrs { srs: DevSrs::new(DEV_SRS_POINTS), loads: loads.clone() };
+
+        let output = prove_and_verify(BYTECODE, &initial_witness, srs).unwrap();
+        assert!(output.verified);
+        // The verifier runs on the points loaded to prove.
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        // The proof is checked by the real verifier, so it holds up on its own too.
+        let verdict = verify_with_srs(
+            String::from(BYTECODE),
+            output.proof,
+            output.verification_key,
+            DevSrs::new(DEV_SRS_POINTS),
+        )
+        .unwrap();
+        assert!(verdict);
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture to print its measurements"]
+    fn bench_prove_and_verify() {
+        let (bytecode, initial_witness) = squaring_chain_of_gates(111_662);
+        let srs_points = (1 << 17) + 1;
+
+        let start = Instant::now();
+        let (proof, vk) =
+            prove_generic(&bytecode, &initial_witness, DevSrs::new(srs_points)).unwrap();
+        assert!(verify_with_srs(bytecode.clone(), proof, vk, DevSrs::new(srs_points)).unwrap());
+        let separate = start.elapsed();
+
+        let start = Instant::now();
+        let output =
+            prove_and_verify(&bytecode, &initial_witness, DevSrs::new(srs_points)).unwrap();
+        let shared = start.elapsed();
+        assert!(output.verified);
+
+        eprintln!("prove then verify: {:?}, prove_and_verify: {:?}", separate, shared);
+    }
+
+    #[test]
+    #[ignore = "downloads the ignition transcript"]
+    fn test_prove_verify_net_srs() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) = prove(BYTECODE, &initial_witness).unwrap();
+        // Verified from the reader first, with the built-in G2 point, before the verdict of
+        // `verify` is cached.
+        let verdict = verify_from_reader(&mut proof.as_slice(), &vk).unwrap();
+        assert!(verdict);
+        let verdict = verify(String::from(BYTECODE), proof, vk).unwrap();
+        assert!(verdict);
+    }
+
+    #[test]
+    fn test_verify_from_reader_rejects_long_proofs() {
+        // Rejected before the key is read.
+        let mut proof = std::io::repeat(0).take(MAX_READER_PROOF_SIZE as u64 + 1);
+        let error = verify_from_reader(&mut proof, &[]).unwrap_err();
+        assert!(matches!(error, NoirRsError::ProofTooLarge { limit: MAX_READER_PROOF_SIZE }));
+        assert_eq!(error.code(), "proof_too_large");
+
+        // An endless reader is not read past the cap.
+        let error = verify_from_reader(&mut std::io::repeat(0), &[]).unwrap_err();
+        assert!(matches!(error, NoirRsError::ProofTooLarge { .. }));
+    }
+
+    #[test]
+    #[ignore = "downloads the ignition transcript"]
+    fn test_prove_verify_downloaded_srs() {
+        let path = download_ignition_transcript();
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) = prove_local_srs(BYTECODE, &initial_witness, &path).unwrap();
+        let verdict =
+            verify_with_srs(String::from(BYTECODE), proof, vk, LocalSrs::new(&path)).unwrap();
+        assert!(verdict);
+    }
+
+    /// Serves the dev SRS with the layout of the ignition transcript, where the G2 point follows
+    /// 5040001 G1 points.
+    fn serve_dev_srs() -> (String, MockSrsServer) {
+        let mut dev_srs = DevSrs::new(2 * DEV_SRS_POINTS);
+        dev_srs.load_data(2 * DEV_SRS_POINTS).unwrap();
+        MockSrsServer::serve(MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data()))
+    }
+
+    #[test]
+    fn test_estimate_srs_bytes_matches_download() {
+        let (url, server) = serve_dev_srs();
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let estimate = estimate_srs_bytes(String::from(BYTECODE)).unwrap();
+        let acir_buffer = decode_bytecode(BYTECODE).unwrap();
+        let circuit_size = get_circuit_sizes(&decompress_bytecode(&acir_buffer).unwrap()).unwrap();
+        let subgroup_size = subgroup_size(&circuit_size, SizingMode::default()).unwrap();
+        let memory = estimate_memory(subgroup_size);
+        assert_eq!(memory.g1_bytes + 128, estimate);
+        let num_points = num_points_for_circuit(BYTECODE).unwrap();
+        assert_eq!(num_points, subgroup_size + 1);
+        assert_eq!(num_points as u64 * 64 + 128, estimate);
+        prove_net_srs(BYTECODE, &initial_witness, Some(&url)).unwrap();
+        let served: u64 = server
+            .requests()
+            .iter()
+            .map(|request| match request.range {
+                Some((start, Some(end))) => end - start + 1,
+                range => panic!("unexpected range {:?}", range),
+            })
+            .sum();
+        assert_eq!(served, estimate);
+    }
+
+    #[test]
+    fn test_prove_verify_sizing_modes() {
+        // The gates the composer reserves keep the total size within the same subgroup of 2^16.
+        let (bytecode, initial_witness) = squaring_chain_of_gates(54_830);
+        let sizes = circuit_sizes(&bytecode);
+        assert!(sizes.exact < sizes.total);
+        assert_eq!(sizes.subgroup, 1 << 16);
+
+        // Both modes report the points they load before reading any, the same for this circuit.
+        let points: Vec<u32> = [SizingMode::Exact, SizingMode::Total]
+            .into_iter()
+            .map(|sizing| {
+                match ProveOptions::new()
+                    .srs(UnreadSrs(0))
+                    .sizing(sizing)
+                    .prove(&bytecode, &initial_witness)
+                {
+                    Err(NoirRsError::CircuitTooLargeForSrs { needed, .. }) => needed,
+                    other => panic!("expected CircuitTooLargeForSrs, got {:?}", other),
+                }
+            })
+            .collect();
+        assert_eq!(points, [(1 << 16) + 1, (1 << 16) + 1]);
+
+        for sizing in [SizingMode::Exact, SizingMode::Total] {
+            let (proof, vk) = ProveOptions::new()
+                .srs(DevSrs::new(points[0]))
+                .sizing(sizing)
+                .prove(&bytecode, &initial_witness)
+                .unwrap();
+            let verdict =
+                verify_with_srs(bytecode.clone(), proof, vk, DevSrs::new(points[0])).unwrap();
+            assert!(verdict);
+        }
+    }
+
+    #[test]
+    fn test_prove_verify_recursive() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .recursive(true)
+            .prove(BYTECODE, &initial_witness)
+            .unwrap();
+        let verdict = verify_with_options(
+            String::from(BYTECODE),
+            proof,
+            vk,
+            DevSrs::new(DEV_SRS_POINTS),
+            &VerifyOptions { flavor: ProofFlavor::Plonk, ..Default::default() },
+        )
+        .unwrap();
+        assert!(verdict);
+    }
+
+    #[test]
+    fn test_prove_verify_flavors() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        for (flavor, other) in [
+            (ProofFlavor::Plonk, ProofFlavor::UltraKeccak),
+            (ProofFlavor::UltraKeccak, ProofFlavor::Plonk),
+        ] {
+            let (proof, vk) = ProveOptions::new()
+                .srs(DevSrs::new(DEV_SRS_POINTS))
+                .flavor(flavor)
+                .prove(BYTECODE, &initial_witness)
+                .unwrap();
+            let verify = |flavor| {
+                verify_with_options(
+                    String::from(BYTECODE),
+                    proof.clone(),
+                    vk.clone(),
+                    DevSrs::new(DEV_SRS_POINTS),
+                    &VerifyOptions { flavor, ..Default::default() },
+                )
+            };
+            assert!(verify(flavor).unwrap());
+            assert!(!matches!(verify(other), Ok(true)));
+        }
+    }
+
+    #[test]
+    fn test_prove_verify_backends() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        for backend in [Backend::Plonk, Backend::UltraHonk] {
+            let (proof, vk) = ProveOptions::new()
+                .srs(DevSrs::new(DEV_SRS_POINTS))
+                .backend(backend)
+                .prove(BYTECODE, &initial_witness)
+                .unwrap();
+            let verdict = verify_with_options(
+                String::from(BYTECODE),
+                proof.clone(),
+                vk.clone(),
+                DevSrs::new(DEV_SRS_POINTS),
+                &VerifyOptions { backend, ..Default::default() },
+            )
+            .unwrap();
+            assert!(verdict);
+
+            let mut tampered = proof;
+            let last = tampered.len() - 1;
+            tampered[last] ^= 1;
+            let verdict = verify_with_options(
+                String::from(BYTECODE),
+                tampered,
+                vk,
+                DevSrs::new(DEV_SRS_POINTS),
+                &VerifyOptions { backend, ..Default::default() },
+            );
+            assert!(!matches!(verdict, Ok(true)));
+        }
+    }
+
+    #[test]
+    fn test_num_public_inputs() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        // The circuit exposes Witness(2) as its only public input.
+        let (proof, vk) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        assert_eq!(num_public_inputs(&vk).unwrap(), 1);
+        assert_eq!(FieldElement::from_be_bytes_reduce(&proof[..32]), FieldElement::one());
+        assert_eq!(proof.len(), 2144 + 32);
+
+        assert!(matches!(
+            num_public_inputs(&vk[..11]),
+            Err(NoirRsError::InvalidVerificationKey(11))
+        ));
+    }
+
+    #[test]
+    fn test_verifier_matches_one_shot_verify() {
+        let proofs: Vec<(Vec<u8>, Vec<u8>)> = (1..=5u128)
+            .map(|y| {
+                let mut initial_witness = WitnessMap::new();
+                initial_witness.insert(Witness(1), FieldElement::zero());
+                initial_witness.insert(Witness(2), FieldElement::from(y));
+                prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap()
+            })
+            .collect();
+        // The verification key only depends on the circuit.
+        let vk = proofs[0].1.clone();
+        assert!(proofs.iter().all(|(_, proof_vk)| *proof_vk == vk));
+
+        let verifier = Verifier::from_vk_with_srs(&vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        for i in 0..50 {
+            let mut proof = proofs[i % proofs.len()].0.clone();
+            if i % 7 == 0 {
+                proof[100 + i] ^= 1;
+            }
+            let one_shot = verify_with_srs(
+                String::from(BYTECODE),
+                proof.clone(),
+                vk.clone(),
+                DevSrs::new(DEV_SRS_POINTS),
+            );
+            let reused = verifier.verify(&proof);
+            assert_eq!(reused.as_ref().ok(), one_shot.as_ref().ok());
+            assert_eq!(matches!(reused, Ok(true)), i % 7 != 0);
+        }
+
+        // A key read from a file is checked against the ignition G2 point, which a proof made
+        // with the development SRS does not match.
+        let path = std::env::temp_dir().join(format!("noir_rs_vk_{}", std::process::id()));
+        std::fs::write(&path, &vk).unwrap();
+        let verifier = Verifier::from_vk_file(&path).unwrap();
+        assert!(!matches!(verifier.verify(&proofs[0].0), Ok(true)));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with --ignored --nocapture to print its measurements"]
+    fn bench_verifier_from_vk() {
+        const RUNS: u32 = 100;
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let (proof, vk) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+
+        let start = Instant::now();
+        for _ in 0..RUNS {
+            let verdict = verify_with_srs(
+                String::from(BYTECODE),
+                proof.clone(),
+                vk.clone(),
+                DevSrs::new(DEV_SRS_POINTS),
+            );
+            assert!(verdict.unwrap());
+        }
+        let one_shot = start.elapsed() / RUNS;
+
+        let start = Instant::now();
+        let verifier = Verifier::from_vk_with_srs(&vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let load = start.elapsed();
+        let start = Instant::now();
+        for _ in 0..RUNS {
+            assert!(verifier.verify(&proof).unwrap());
+        }
+        let reused = start.elapsed() / RUNS;
+
+        eprintln!(
+            "verify_with_srs: {:?} per proof; Verifier: {:?} to load the key, {:?} per proof",
+            one_shot, load, reused
+        );
+    }
+
+    #[test]
+    fn test_verify_detailed() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let verify = |proof: &[u8], vk: &[u8]| {
+            verify_detailed_with_options(
+                String::from(BYTECODE),
+                proof.to_vec(),
+                vk.to_vec(),
+                DevSrs::new(DEV_SRS_POINTS),
+                &VerifyOptions::default(),
+            )
+        };
+        assert_eq!(verify(&proof, &vk).unwrap(), VerifyOutcome::Valid);
+
+        // A tampered or truncated proof is rejected, whether the verifier fails or errors on it.
+        let mut tampered = proof.clone();
+        tampered[100] ^= 1;
+        for proof in [&tampered[..], &proof[..proof.len() / 2]] {
+            assert!(matches!(verify(proof, &vk).unwrap(), VerifyOutcome::Invalid { .. }));
+        }
+
+        // Without an SRS the proof could not be checked.
+        let unchecked = verify_detailed_with_options(
+            String::from(BYTECODE),
+            proof,
+            vk,
+            LocalSrs::new(std::env::temp_dir().join("noir_rs_missing_transcript.dat")),
+            &VerifyOptions::default(),
+        );
+        assert!(matches!(unchecked, Err(NoirRsError::Srs(_))));
+    }
+
+    #[test]
+    fn test_split_proof() {
+        let mut initial_witness = WitnessMap::new();
+        initial_witness.insert(Witness(1), FieldElement::zero());
+        initial_witness.insert(Witness(2), FieldElement::from(7u128));
+
+        let (proof, vk) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let (public_inputs, body) = split_proof_with_vk(&proof, &vk).unwrap();
+        assert_eq!(public_inputs, vec![FieldElement::from(7u128)]);
+        assert_eq!(body.len(), 2144);
+        let layout = proof_layout(&vk).unwrap();
+        assert_eq!(
+            layout,
+            ProofLayout {
+                num_public_inputs: 1,
+                proof_bytes_without_inputs: 2144,
+                total_bytes: 2176
+            }
+        );
+        assert_eq!(proof.len(), layout.total_bytes as usize);
+        assert!(matches!(proof_layout(&vk[..11]), Err(NoirRsError::InvalidVerificationKey(11))));
+
+        let mut reconstructed: Vec<u8> =
+            public_inputs.into_iter().flat_map(FieldElement::to_be_bytes).collect();
+        reconstructed.extend_from_slice(&body);
+        assert_eq!(reconstructed, proof);
+
+        assert_eq!(split_proof(&proof, 0), (Vec::new(), proof.clone()));
+        assert_eq!(split_proof(&proof[..40], 2).0.len(), 1);
+    }
+
+    #[test]
+    fn test_extract_public_input_counts() {
+        // A key header claiming `num_public_inputs`, followed by a proof body of fake bytes.
+        let vk = |num_public_inputs: u32| {
+            let mut vk = vec![0u8; 8];
+            vk.extend_from_slice(&num_public_inputs.to_be_bytes());
+            vk
+        };
+        let body: Vec<u8> = (0..2144).map(|byte| byte as u8).collect();
+
+        assert_eq!(extract_public_inputs(&body, &vk(0)).unwrap(), Vec::new());
+        assert_eq!(split_proof(&body, 0), (Vec::new(), body.clone()));
+        assert_eq!(split_proof(&[], 0), (Vec::new(), Vec::new()));
+
+        for num_public_inputs in [1u32, 3, 8] {
+            let public_inputs: Vec<FieldElement> =
+                (0..num_public_inputs).map(|input| FieldElement::from(input as u128 + 1)).collect();
+            let mut proof: Vec<u8> =
+                public_inputs.iter().flat_map(|input| input.to_be_bytes()).collect();
+            proof.extend_from_slice(&body);
+
+            assert_eq!(
+                extract_public_inputs(&proof, &vk(num_public_inputs)).unwrap(),
+                public_inputs
+            );
+            assert_eq!(split_proof(&proof, num_public_inputs), (public_inputs, body.clone()));
+        }
+        assert!(matches!(
+            extract_public_inputs(&body, &vk(0)[..11]),
+            Err(NoirRsError::InvalidVerificationKey(11))
+        ));
+    }
+
+    #[test]
+    fn test_prove_detailed_public_inputs() {
+        // Returns `w3 = w1 + w2` along with the public parameter `w1`, which is also returned.
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Arithmetic(Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: vec![
+                    (FieldElement::one(), Witness(1)),
+                    (FieldElement::one(), Witness(2)),
+                    (-FieldElement::one(), Witness(3)),
+                ],
+                q_c: FieldElement::zero(),
+            })],
+            private_parameters: [Witness(2)].into(),
+            public_parameters: PublicInputs([Witness(1)].into()),
+            return_values: PublicInputs([Witness(1), Witness(3)].into()),
+            ..Circuit::default()
+        };
+        let bytecode = general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit));
+        let mut initial_witness = WitnessMap::new();
+        initial_witness.insert(Witness(1), FieldElement::from(3u128));
+        initial_witness.insert(Witness(2), FieldElement::from(4u128));
+
+        let output = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .prove_detailed(&bytecode, &initial_witness)
+            .unwrap();
+        assert_eq!(
+            output.public_inputs,
+            vec![FieldElement::from(3u128), FieldElement::from(7u128)]
+        );
+        assert_eq!(num_public_inputs(&output.verification_key).unwrap(), 2);
+        assert_eq!(split_proof(&output.proof, 2).0, output.public_inputs);
+    }
+
+    #[test]
+    fn test_proof_and_vk_as_fields() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .recursive(true)
+            .prove(BYTECODE, &initial_witness)
+            .unwrap();
+
+        let proof_fields = proof_as_fields(&proof, &vk).unwrap();
+        assert!(!proof_fields.is_empty());
+
+        let (vk_fields, vk_hash) = vk_as_fields_with_srs(&vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let num_public_inputs = u32::from_be_bytes([vk[8], vk[9], vk[10], vk[11]]);
+        assert_eq!(vk_fields[4], FieldElement::from(num_public_inputs as u128));
+        assert_ne!(vk_hash, FieldElement::zero());
+    }
+
+    /// Builds a circuit verifying a proof of `num_public_inputs` public inputs, taking the key,
+    /// key hash, proof and public inputs as parameters in that order, then the input aggregation
+    /// object if `aggregate` is set, and returning the output aggregation object.
+    fn recursion_circuit(
+        num_key_fields: usize,
+        num_proof_fields: usize,
+        num_public_inputs: usize,
+        aggregate: bool,
+    ) -> (String, Option<[Witness; AGGREGATION_OBJECT_SIZE]>) {
+        let mut next_witness = 1..;
+        let mut inputs = |count: usize| -> Vec<FunctionInput> {
+            next_witness
+                .by_ref()
+                .take(count)
+                .map(|index| FunctionInput {
+                    witness: Witness(index),
+                    num_bits: FieldElement::max_num_bits(),
+                })
+                .collect()
+        };
+        let verification_key = inputs(num_key_fields);
+        let key_hash = inputs(1)[0];
+        let proof = inputs(num_proof_fields);
+        let public_inputs = inputs(num_public_inputs);
+        let input_aggregation_object = aggregate.then(|| inputs(AGGREGATION_OBJECT_SIZE));
+        let num_parameters = next_witness.start - 1;
+        let output_aggregation_object: Vec<Witness> =
+            next_witness.by_ref().take(AGGREGATION_OBJECT_SIZE).map(Witness).collect();
+
+        let input_witnesses = input_aggregation_object.as_ref().map(|inputs| {
+            let mut witnesses = [Witness::default(); AGGREGATION_OBJECT_SIZE];
+            for (witness, input) in witnesses.iter_mut().zip(inputs) {
+                *witness = input.witness;
+            }
+            witnesses
+        });
+        let circuit = Circuit {
+            current_witness_index: next_witness.start - 1,
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RecursiveAggregation {
+                verification_key,
+                proof,
+                public_inputs,
+                key_hash,
+                input_aggregation_object,
+                output_aggregation_object: output_aggregation_object.clone(),
+            })],
+            private_parameters: (1..=num_parameters).map(Witness).collect(),
+            return_values: PublicInputs(output_aggregation_object.into_iter().collect()),
+            ..Circuit::default()
+        };
+        (general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit)), input_witnesses)
+    }
+
+    #[test]
+    fn test_aggregation_object_two_level_recursion() {
+        // Verifying a proof recursively takes a few hundred thousand gates.
+        const RECURSION_SRS_POINTS: u32 = 1 << 19;
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (inner_proof, inner_vk) = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .recursive(true)
+            .prove(BYTECODE, &initial_witness)
+            .unwrap();
+        assert!(matches!(
+            extract_aggregation_object(&inner_proof, &inner_vk),
+            Err(NoirRsError::NotRecursiveProof)
+        ));
+        let proof_fields = proof_as_fields(&inner_proof, &inner_vk).unwrap();
+        let (key_fields, key_hash) =
+            vk_as_fields_with_srs(&inner_vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let (public_inputs, _) = split_proof_with_vk(&inner_proof, &inner_vk).unwrap();
+        let mut recursion_inputs = key_fields.clone();
+        recursion_inputs.push(key_hash);
+        recursion_inputs.extend(proof_fields.iter().copied());
+        recursion_inputs.extend(public_inputs.iter().copied());
+
+        // The first level starts the aggregation.
+        let (first_bytecode, _) =
+            recursion_circuit(key_fields.len(), proof_fields.len(), public_inputs.len(), false);
+        let (first_proof, first_vk) = ProveOptions::new()
+            .srs(DevSrs::new(RECURSION_SRS_POINTS))
+            .recursive(true)
+            .prove(&first_bytecode, &witness::from_vec(recursion_inputs.clone()))
+            .unwrap();
+        let first_object = extract_aggregation_object(&first_proof, &first_vk).unwrap();
+        assert_eq!(first_object.len(), AGGREGATION_OBJECT_SIZE);
+        assert_ne!(first_object, [FieldElement::zero(); AGGREGATION_OBJECT_SIZE]);
+
+        // The second level carries on the aggregation object of the first.
+        let (second_bytecode, input_witnesses) =
+            recursion_circuit(key_fields.len(), proof_fields.len(), public_inputs.len(), true);
+        let mut second_witness = witness::from_vec(recursion_inputs);
+        inject_aggregation_object(&mut second_witness, &input_witnesses.unwrap(), &first_object);
+        let (second_proof, second_vk) = ProveOptions::new()
+            .srs(DevSrs::new(RECURSION_SRS_POINTS))
+            .recursive(true)
+            .prove(&second_bytecode, &second_witness)
+            .unwrap();
+        let verdict = verify_recursive_with_srs(
+            second_bytecode,
+            second_proof.clone(),
+            second_vk.clone(),
+            DevSrs::new(RECURSION_SRS_POINTS),
+        )
+        .unwrap();
+        assert!(verdict);
+        let second_object = extract_aggregation_object(&second_proof, &second_vk).unwrap();
+        assert_ne!(second_object, first_object);
+        validate_aggregation_object(&first_object).unwrap();
+        validate_aggregation_object(&second_object).unwrap();
+
+        // Keys of circuits verifying proofs carry the recursive proof indices in their hash.
+        assert_eq!(hex::encode(vk_hash(&second_vk).unwrap()), solidity_vk_hash(&second_vk));
+    }
+
+    /// Splits a coordinate into the four 68-bit limbs of an aggregation object.
+    fn aggregation_limbs(coordinate: Fq) -> [FieldElement; 4] {
+        let bits = coordinate.into_bigint().to_bits_le();
+        let mut limbs = [FieldElement::zero(); 4];
+        for (limb, bits) in limbs.iter_mut().zip(bits.chunks(68)) {
+            let value = bits.iter().rev().fold(0u128, |value, bit| value << 1 | *bit as u128);
+            *limb = FieldElement::from(value);
+        }
+        limbs
+    }
+
+    #[test]
+    fn test_validate_aggregation_object() {
+        let first = G1Affine::generator();
+        let second = (G1Affine::generator() * Fr::from(5u64)).into_affine();
+        let object = |coordinates: [Fq; 4]| {
+            let mut object = [FieldElement::zero(); AGGREGATION_OBJECT_SIZE];
+            for (limbs, coordinate) in object.chunks_mut(4).zip(coordinates) {
+                limbs.copy_from_slice(&aggregation_limbs(coordinate));
+            }
+            object
+        };
+        validate_aggregation_object(&object([first.x, first.y, second.x, second.y])).unwrap();
+
+        let off_curve = object([first.x, first.y, second.x, second.y + Fq::from(1u64)]);
+        assert!(matches!(
+            validate_aggregation_object(&off_curve),
+            Err(NoirRsError::InvalidAggregationObject(reason)) if reason.contains("point 1")
+        ));
+        let mut wide_limb = object([first.x, first.y, second.x, second.y]);
+        wide_limb[5] = FieldElement::from(1u128 << 68);
+        assert!(matches!(
+            validate_aggregation_object(&wide_limb),
+            Err(NoirRsError::InvalidAggregationObject(reason)) if reason.contains("limb 1")
+        ));
+        // The modulus itself is a coordinate of 0 that is not reduced.
+        let mut unreduced = object([first.x, first.y, second.x, second.y]);
+        let modulus_bits = Fq::MODULUS.to_bits_le();
+        for (limb, bits) in unreduced[..4].iter_mut().zip(modulus_bits.chunks(68)) {
+            let value = bits.iter().rev().fold(0u128, |value, bit| value << 1 | *bit as u128);
+            *limb = FieldElement::from(value);
+        }
+        assert!(matches!(
+            validate_aggregation_object(&unreduced),
+            Err(NoirRsError::InvalidAggregationObject(reason)) if reason.contains("coordinate 0")
+        ));
+    }
+
+    #[test]
+    fn test_verify_recursive_rejects_proofs_without_aggregation_object() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let (proof, vk) = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .recursive(true)
+            .prove(BYTECODE, &initial_witness)
+            .unwrap();
+
+        let verify = |proof: Vec<u8>| {
+            verify_recursive_with_srs(
+                String::from(BYTECODE),
+                proof,
+                vk.clone(),
+                DevSrs::new(DEV_SRS_POINTS),
+            )
+        };
+        assert!(matches!(
+            verify(proof[..proof.len() - 32].to_vec()),
+            Err(NoirRsError::ProofLengthMismatch { expected, actual }) if expected == actual + 32
+        ));
+        assert!(matches!(verify(proof), Err(NoirRsError::NotRecursiveProof)));
+    }
+
+    /// Returns the hash barretenberg computes for a key, as written in its Solidity verifier.
+    fn solidity_vk_hash(vk: &[u8]) -> String {
+        let contract = solidity_verifier_with_srs(vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let vk_hash = contract
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("// Verification Key Hash: "))
+            .unwrap();
+        String::from(vk_hash)
+    }
+
+    #[test]
+    fn test_solidity_verifier() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (_, vk) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let contract = solidity_verifier_with_srs(&vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+
+        let vk_hash = solidity_vk_hash(&vk);
+        assert_eq!(vk_hash.len(), 64);
+        assert!(contract.contains(&format!("return 0x{};", vk_hash)));
+        assert!(contract.contains("library UltraVerificationKey"));
+
+        // The hash read from the key matches the contract, and does not change when the key of the
+        // circuit is computed again.
+        assert_eq!(hex::encode(crate::vk_hash(&vk).unwrap()), vk_hash);
+        let recomputed_vk =
+            get_verification_key(BYTECODE, DevSrs::new(DEV_SRS_POINTS), None).unwrap();
+        assert_eq!(hex::encode(crate::vk_hash(&recomputed_vk).unwrap()), vk_hash);
+    }
+
+    #[test]
+    fn test_verifier_stats() {
+        // The fixture circuit has one public input, `tests/fixtures/no_public_inputs.b64` none.
+        let circuits = [
+            (BYTECODE, vec![FieldElement::zero(), FieldElement::one()]),
+            (
+                include_str!("../tests/fixtures/no_public_inputs.b64").trim(),
+                vec![FieldElement::from(3u128), FieldElement::from(4u128)],
+            ),
+        ];
+        for (bytecode, inputs) in circuits {
+            let (proof, vk) =
+                prove_with_srs(bytecode, &witness::from_vec(inputs), DevSrs::new(DEV_SRS_POINTS))
+                    .unwrap();
+            let stats = verifier_stats(&vk).unwrap();
+            let (public_inputs, body) = split_proof_with_vk(&proof, &vk).unwrap();
+            assert_eq!(stats.num_public_inputs as usize, public_inputs.len());
+            assert_eq!(stats.proof_size as usize, body.len());
+            assert_eq!(
+                stats.estimated_calldata_bytes_per_proof as usize,
+                4 + 4 * 32 + body.len() + public_inputs.len() * 32
+            );
+        }
+        assert!(matches!(verifier_stats(&[0; 8]), Err(NoirRsError::InvalidVerificationKey(8))));
+    }
+
+    #[test]
+    fn test_vk_hash() {
+        // Two commitments, a recursive proof flag and one recursive proof public input index.
+        let mut vk = Vec::new();
+        for word in [2u32, 16, 1, 2] {
+            vk.extend_from_slice(&word.to_be_bytes());
+        }
+        for (name, x, y) in [("Q_1", 1u8, 2u8), ("SIGMA_1", 3, 4)] {
+            vk.extend_from_slice(&(name.len() as u32).to_be_bytes());
+            vk.extend_from_slice(name.as_bytes());
+            vk.extend_from_slice(&[[0; 31].as_slice(), &[x], &[0; 31], &[y]].concat());
+        }
+        vk.push(1);
+        vk.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 7]);
+        assert_eq!(
+            hex::encode(vk_hash(&vk).unwrap()),
+            "a0cdfd7616948dc561ed32035c9dd9b404642292a97f0da3f8fdf5047590f4ea"
+        );
+        assert!(matches!(
+            vk_hash(&vk[..vk.len() - 1]),
+            Err(NoirRsError::InvalidVerificationKey(_))
+        ));
+
+        // The hash matches barretenberg's for keys of circuits differing in size and public
+        // inputs.
+        let circuits = [
+            String::from(BYTECODE),
+            String::from(include_str!("../tests/fixtures/no_public_inputs.b64").trim()),
+            squaring_chain(1000).0,
+        ];
+        for bytecode in circuits {
+            let vk =
+                get_verification_key(&bytecode, DevSrs::new(4 * DEV_SRS_POINTS), None).unwrap();
+            assert_eq!(hex::encode(vk_hash(&vk).unwrap()), solidity_vk_hash(&vk));
+        }
+    }
+
+    #[test]
+    fn test_prove_verify_local_srs_formats() {
+        let dir = std::env::temp_dir().join(format!("noir_rs_local_srs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut dev_srs = DevSrs::new(DEV_SRS_POINTS);
+        dev_srs.load_data(DEV_SRS_POINTS).unwrap();
+
+        let mut transcript = Vec::new();
+        for word in [0, 1, DEV_SRS_POINTS, 1, DEV_SRS_POINTS, 1, 0] {
+            transcript.extend_from_slice(&u32::to_be_bytes(word));
+        }
+        transcript.extend_from_slice(dev_srs.g1_data());
+        transcript.extend_from_slice(dev_srs.g2_data());
+        std::fs::write(dir.join("transcript00.dat"), transcript).unwrap();
+        std::fs::write(dir.join("bn254_g1.dat"), dev_srs.g1_data()).unwrap();
+        std::fs::write(dir.join("bn254_g2.dat"), dev_srs.g2_data()).unwrap();
+
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        for path in [dir.join("transcript00.dat"), dir.clone()] {
+            let (proof, vk) =
+                prove_with_srs(BYTECODE, &initial_witness, LocalSrs::new(&path)).unwrap();
+            let verdict =
+                verify_with_srs(String::from(BYTECODE), proof, vk, LocalSrs::new(&path)).unwrap();
+            assert!(verdict);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_trim_srs() {
+        let dir = std::env::temp_dir().join(format!("noir_rs_trim_srs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut dev_srs = DevSrs::new(DEV_SRS_POINTS);
+        dev_srs.load_data(DEV_SRS_POINTS).unwrap();
+
+        // Like the ignition transcripts, the source holds two G2 points.
+        let mut transcript = Vec::new();
+        for word in [0, 1, DEV_SRS_POINTS, 2, DEV_SRS_POINTS, 1, 0] {
+            transcript.extend_from_slice(&u32::to_be_bytes(word));
+        }
+        transcript.extend_from_slice(dev_srs.g1_data());
+        transcript.extend_from_slice(dev_srs.g2_data());
+        transcript.extend_from_slice(dev_srs.g2_data());
+        let (src, dst) = (dir.join("transcript00.dat"), dir.join("trimmed.dat"));
+        std::fs::write(&src, transcript).unwrap();
+
+        let num_points = num_points_for_circuit(BYTECODE).unwrap();
+        trim_srs(src.to_str().unwrap(), dst.to_str().unwrap(), num_points - 1).unwrap();
+        assert_eq!(std::fs::metadata(&dst).unwrap().len(), 28 + num_points as u64 * 64 + 128);
+
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let (proof, vk) = prove_with_srs(BYTECODE, &initial_witness, LocalSrs::new(&dst)).unwrap();
+        let verdict =
+            verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+                .unwrap();
+        assert!(verdict);
+
+        assert!(matches!(
+            LocalSrs::new(&dst).load_data(num_points + 1),
+            Err(SrsError::InsufficientPoints { .. })
+        ));
+        assert_eq!(LocalSrs::new(&dst).max_points().unwrap(), num_points);
+
+        // A transcript for the next smaller circuits fails before the circuit is executed.
+        trim_srs(src.to_str().unwrap(), dst.to_str().unwrap(), (num_points - 1) / 2).unwrap();
+        assert!(matches!(
+            prove_local_srs(BYTECODE, &WitnessMap::new(), &dst),
+            Err(NoirRsError::Srs(SrsError::InsufficientPoints { available, requested, .. }))
+                if available == (num_points - 1) / 2 + 1 && requested == num_points
+        ));
+        assert!(matches!(
+            trim_srs(src.to_str().unwrap(), dst.to_str().unwrap(), DEV_SRS_POINTS),
+            Err(NoirRsError::Srs(SrsError::InsufficientPoints { .. }))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// A source whose points must not be read.
+    struct UnreadSrs(u32);
+
+    impl Srs for UnreadSrs {
+        fn load_data(&mut self, _num_points: u32) -> Result<(), SrsError> {
+            panic!("the SRS was read");
+        }
+
+        fn g1_data(&self) -> &[u8] {
+            &[]
+        }
+
+        fn g2_data(&self) -> &[u8] {
+            &[]
+        }
+
+        fn num_points(&self) -> u32 {
+            0
+        }
+
+        fn clear(&mut self) {}
+
+        fn max_points(&self) -> Option<u32> {
+            Some(self.0)
+        }
+    }
+
+    /// A source reporting more points, or a longer G2 point, than the data it returns.
+    struct LyingSrs {
+        srs: DevSrs,
+        claimed_points: u32,
+        g2_len: usize,
+    }
+
+    impl Srs for LyingSrs {
+        fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+            self.srs.load_data(num_points)
+        }
+
+        fn g1_data(&self) -> &[u8] {
+            self.srs.g1_data()
+        }
+
+        fn g2_data(&self) -> &[u8] {
+            &self.srs.g2_data()[..self.g2_len]
+        }
+
+        fn num_points(&self) -> u32 {
+            self.claimed_points
+        }
+
+        fn clear(&mut self) {
+            self.srs.clear()
+        }
+    }
+
+    #[test]
+    fn test_init_srs_rejects_lying_srs() {
+        // Without the transcript check, only the lengths keep the backend within the buffers.
+        for (claimed_points, g2_len) in [(17, 128), (u32::MAX, 128), (0, 128), (16, 64), (16, 0)] {
+            let mut srs = LyingSrs { srs: DevSrs::new(16), claimed_points, g2_len };
+            let error = init_srs(&mut srs, 16, true).unwrap_err();
+            assert!(
+                matches!(error, NoirRsError::Backend(BackendError::BindingCallError(_))),
+                "{:?}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_circuit_too_large_for_srs() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let num_points = num_points_for_circuit(BYTECODE).unwrap();
+
+        let error =
+            prove_with_srs(BYTECODE, &initial_witness, UnreadSrs(num_points - 1)).unwrap_err();
+        assert!(matches!(
+            error,
+            NoirRsError::CircuitTooLargeForSrs { needed, available }
+                if needed == num_points && available == num_points - 1
+        ));
+        assert_eq!(error.code(), "circuit_too_large_for_srs");
+
+        // A truncated transcript is rejected from its size.
+        let dir = std::env::temp_dir().join(format!("noir_rs_too_large_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write_transcript = |num_points: u32| {
+            let mut dev_srs = DevSrs::new(num_points);
+            dev_srs.load_data(num_points).unwrap();
+            let mut transcript = Vec::new();
+            for word in [0, 1, num_points, 1, num_points, 1, 0] {
+                transcript.extend_from_slice(&u32::to_be_bytes(word));
+            }
+            transcript.extend_from_slice(dev_srs.g1_data());
+            transcript.extend_from_slice(dev_srs.g2_data());
+            let path = dir.join(format!("transcript_{}.dat", num_points));
+            std::fs::write(&path, transcript).unwrap();
+            path
+        };
+        let path = write_transcript(16);
+        assert!(matches!(
+            prove_with_srs(BYTECODE, &initial_witness, LocalSrs::new(&path)),
+            Err(NoirRsError::CircuitTooLargeForSrs { needed, available: 16 }) if needed == num_points
+        ));
+
+        // A large circuit against the points a transcript on disk reports, rejected before any
+        // of them are read.
+        let (bytecode, initial_witness) = squaring_chain(365_461);
+        let needed_points = num_points_for_circuit(&bytecode).unwrap();
+        assert_eq!(needed_points, subgroup_size_for(circuit_sizes(&bytecode).total).unwrap() + 1);
+        let srs = LocalSrs::new(write_transcript(DEV_SRS_POINTS));
+        let available_points = srs.max_points().unwrap();
+        assert_eq!(available_points, DEV_SRS_POINTS);
+        assert!(matches!(
+            prove_with_srs(&bytecode, &initial_witness, srs),
+            Err(NoirRsError::CircuitTooLargeForSrs { needed, available })
+                if needed == needed_points && available == available_points
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prove_with_g2_sidecar() {
+        let dir = std::env::temp_dir().join(format!("noir_rs_g2_sidecar_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut dev_srs = DevSrs::new(DEV_SRS_POINTS);
+        dev_srs.load_data(DEV_SRS_POINTS).unwrap();
+
+        // The G1 points in the ignition layout, and the G2 point of the ceremony on its own.
+        let mut transcript = Vec::new();
+        for word in [0, 1, DEV_SRS_POINTS, 1, DEV_SRS_POINTS, 1, 0] {
+            transcript.extend_from_slice(&u32::to_be_bytes(word));
+        }
+        transcript.extend_from_slice(dev_srs.g1_data());
+        let (g1_path, g2_path) = (dir.join("g1.dat"), dir.join("g2.dat"));
+        std::fs::write(&g1_path, transcript).unwrap();
+        std::fs::write(&g2_path, dev_srs.g2_data()).unwrap();
+
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let srs = LocalSrs::new(&g1_path).with_g2_path(&g2_path);
+        let (proof, vk) = prove_with_srs(BYTECODE, &initial_witness, srs).unwrap();
+        let verdict =
+            verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+                .unwrap();
+        assert!(verdict);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_versioned_bytes() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (proof, vk) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let stored_proof = Proof(proof.clone()).to_versioned_bytes();
+        let stored_vk = VerificationKey(vk.clone()).to_versioned_bytes();
+
+        let read_proof = Proof::from_versioned_bytes(&stored_proof).unwrap();
+        let read_vk = VerificationKey::from_versioned_bytes(&stored_vk).unwrap();
+        assert_eq!(read_proof.0, proof);
+        assert_eq!(read_vk.0, vk);
+        let verdict = verify_with_srs(
+            String::from(BYTECODE),
+            read_proof.0,
+            read_vk.0,
+            DevSrs::new(DEV_SRS_POINTS),
+        )
+        .unwrap();
+        assert!(verdict);
+
+        let mut tampered = stored_proof.clone();
+        tampered[4] += 1;
+        match Proof::from_versioned_bytes(&tampered) {
+            Err(NoirRsError::IncompatibleProofVersion { stored, current }) => {
+                assert_eq!(current, FormatVersion::current());
+                assert_eq!(stored.format, current.format + 1);
+                assert_eq!(stored.backend, current.backend);
+            }
+            other => panic!("expected IncompatibleProofVersion, got {:?}", other),
+        }
+
+        assert!(matches!(
+            VerificationKey::from_versioned_bytes(&stored_proof),
+            Err(NoirRsError::InvalidEnvelope(_))
+        ));
+        assert!(matches!(
+            Proof::from_versioned_bytes(&proof),
+            Err(NoirRsError::InvalidEnvelope(_))
+        ));
+    }
+
+    #[test]
+    fn test_circuit_hash() {
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut decoder = GzDecoder::new(acir_buffer.as_slice());
+        let mut acir_buffer_uncompressed = Vec::<u8>::new();
+        decoder.read_to_end(&mut acir_buffer_uncompressed).unwrap();
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&acir_buffer_uncompressed).unwrap();
+        let recompressed = general_purpose::STANDARD.encode(encoder.finish().unwrap());
+        assert_ne!(recompressed, BYTECODE);
+
+        let hash = circuit_hash(BYTECODE).unwrap();
+        assert_eq!(hash, circuit_hash(BYTECODE).unwrap());
+        assert_eq!(hash, circuit_hash(&recompressed).unwrap());
+        assert!(circuit_hash("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_transport_round_trip() {
+        let proof: Vec<u8> = (0..2144u32).map(|i| (i % 251) as u8).collect();
+        let vk: Vec<u8> = (0..1512u32).map(|i| (i % 13) as u8).collect();
+
+        let encoded = encode_for_transport(&proof, &vk);
+        assert!(encoded.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+        assert_eq!(decode_from_transport(&encoded).unwrap(), (proof.clone(), vk.clone()));
+        assert_eq!(
+            decode_from_transport(&encode_for_transport(&[], &vk)).unwrap(),
+            (Vec::new(), vk.clone())
+        );
+
+        let truncated = &encoded[..encoded.len() / 2];
+        assert!(matches!(decode_from_transport(truncated), Err(NoirRsError::InvalidTransport(_))));
+        assert!(decode_from_transport("not base64!").is_err());
+
+        let decoded_size = 13 + proof.len() + vk.len();
+        assert!(decode_from_transport_with_limit(&encoded, decoded_size).is_ok());
+        assert!(matches!(
+            decode_from_transport_with_limit(&encoded, decoded_size - 1),
+            Err(NoirRsError::TransportTooLarge { limit }) if limit == decoded_size - 1
+        ));
+    }
+
+    #[test]
+    fn test_bytecode_compression() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut raw = Vec::new();
+        GzDecoder::new(acir_buffer.as_slice()).read_to_end(&mut raw).unwrap();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&raw).unwrap();
+        let zlib = general_purpose::STANDARD.encode(encoder.finish().unwrap());
+
+        assert_eq!(decompress_bytecode(&acir_buffer).unwrap(), raw);
+        assert_eq!(
+            decode_bytecode(&zlib).and_then(|zlib| decompress_bytecode(&zlib)).unwrap(),
+            raw
+        );
+        assert_eq!(gate_report(&zlib).unwrap(), gate_report(BYTECODE).unwrap());
+        assert_eq!(circuit_hash(&zlib).unwrap(), circuit_hash(BYTECODE).unwrap());
+        prove_with_srs(&zlib, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+
+        let raw_bytecode = general_purpose::STANDARD.encode(&raw);
+        assert!(matches!(
+            gate_report(&raw_bytecode),
+            Err(NoirRsError::UnknownBytecodeCompression { prefix }) if prefix == raw[..4]
+        ));
+        assert!(matches!(
+            decompress_bytecode(&[]),
+            Err(NoirRsError::UnknownBytecodeCompression { prefix }) if prefix.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_backend_info() {
+        let info = backend_info();
+        assert!(!info.backend_commit.is_empty());
+        assert!(!info.acir_version.is_empty());
+        assert!(!info.crate_version.is_empty());
+        assert_eq!(backend_info(), info);
+
+        // The acir version is written by hand, it must follow the vendored crate.
+        let acir_manifest = include_str!("../../../acvm-repo/acir/Cargo.toml");
+        assert!(
+            acir_manifest.contains(&format!("\nversion = \"{}\"", ACIR_VERSION)),
+            "ACIR_VERSION {} is not the version of the vendored acir crate",
+            ACIR_VERSION
+        );
+
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let output = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .prove_detailed(BYTECODE, &initial_witness)
+            .unwrap();
+        assert_eq!(output.backend_info, info);
+    }
+
+    #[test]
+    fn test_newer_acir_version_is_reported() {
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut raw = Vec::new();
+        GzDecoder::new(acir_buffer.as_slice()).read_to_end(&mut raw).unwrap();
+        let encode = |bytes: &[u8]| {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes).unwrap();
+            general_purpose::STANDARD.encode(encoder.finish().unwrap())
+        };
+
+        // Lay the circuit out as later acir versions do: an `ExpressionWidth::Bounded { width: 3 }`
+        // after the witness index, wrapped in a `Program` holding one function.
+        let mut circuit = raw[..4].to_vec();
+        circuit.extend_from_slice(&1u32.to_le_bytes());
+        circuit.extend_from_slice(&3u64.to_le_bytes());
+        circuit.extend_from_slice(&raw[4..]);
+        let mut program = 1u64.to_le_bytes().to_vec();
+        program.extend_from_slice(&circuit);
+
+        for (bytes, detected) in [
+            (circuit, "a bincode Circuit with an expression width"),
+            (program, "a bincode Program"),
+            (vec![0x83, 0xa9], "a msgpack Program"),
+        ] {
+            let bytecode = encode(&bytes);
+            assert!(Circuit::deserialize_circuit(
+                &general_purpose::STANDARD.decode(&bytecode).unwrap()
+            )
+            .is_err());
+            let error = gate_report(&bytecode).unwrap_err();
+            assert!(
+                matches!(&error, NoirRsError::UnsupportedAcirVersion { detected: found, .. } if found == detected),
+                "{:?}",
+                error
+            );
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "Circuit bytecode is {}, but this build reads a bincode Circuit from acir \
+                     0.35.0: recompile the circuit with nargo {}",
+                    detected, NARGO_VERSION
+                )
+            );
+        }
+
+        assert!(matches!(gate_report(&encode(&[0xff; 3])), Err(NoirRsError::Io(_))));
+    }
+
+    #[test]
+    fn test_circuit_executor_steps_match_execute() {
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let circuit = Circuit::deserialize_circuit(&acir_buffer).unwrap();
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let blackbox_solver = BlackboxSolver::new();
+        let solved_witness =
+            execute_circuit(&blackbox_solver, circuit.clone(), initial_witness.clone()).unwrap();
+
+        let mut executor = CircuitExecutor::new(&blackbox_solver, &circuit, initial_witness);
+        let mut assigned_witnesses = vec![Witness(1), Witness(2)];
+        for expected_index in 0..circuit.opcodes.len() {
+            let (opcode_index, assigned) = match executor.step().unwrap() {
+                StepResult::Solved { opcode_index, assigned } => (opcode_index, assigned),
+                StepResult::Finished => panic!("finished before opcode {}", expected_index),
+            };
+            assert_eq!(opcode_index, expected_index);
+            for (witness, value) in assigned {
+                assert_eq!(solved_witness.get(&witness), Some(&value));
+                assert_eq!(executor.witness(witness), Some(value));
+                assigned_witnesses.push(witness);
+            }
+        }
+        assert_eq!(executor.step().unwrap(), StepResult::Finished);
+
+        let solved_witnesses: Vec<Witness> =
+            solved_witness.clone().into_iter().map(|(witness, _)| witness).collect();
+        assigned_witnesses.sort();
+        assert_eq!(assigned_witnesses, solved_witnesses);
+        assert_eq!(executor.run_to_completion().unwrap(), solved_witness);
+    }
+
+    #[test]
+    fn test_gate_report() {
+        let report = gate_report(BYTECODE).unwrap();
+        assert_eq!((report.arithmetic, report.brillig, report.total()), (4, 1, 5));
+        assert!(report.black_box.is_empty());
+
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut circuit = Circuit::deserialize_circuit(&acir_buffer).unwrap();
+        let input = FunctionInput { witness: Witness(1), num_bits: 8 };
+        circuit.opcodes.extend([
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 {
+                inputs: vec![input],
+                outputs: (3..35).map(Witness).collect(),
+            }),
+        ]);
+        let bytecode = general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit));
+
+        let report = gate_report(&bytecode).unwrap();
+        assert_eq!(report.black_box.get("range"), Some(&2));
+        assert_eq!(report.black_box.get("sha256"), Some(&1));
+        assert_eq!((report.black_box_total(), report.total()), (3, 8));
+    }
+
+    #[test]
+    fn test_circuit_stats() {
+        let stats = circuit_stats(BYTECODE).unwrap();
+        assert_eq!(stats.opcodes.get("Arithmetic"), Some(&4));
+        assert_eq!(stats.opcodes.get("Brillig"), Some(&1));
+        assert_eq!((stats.opcodes.len(), stats.total_opcodes()), (2, 5));
+        assert_eq!(stats.total_witnesses, 6);
+        assert_eq!((stats.range_constraints(), stats.largest_memory_block()), (0, None));
+
+        let acir_buffer = general_purpose::STANDARD.decode(BYTECODE).unwrap();
+        let mut circuit = Circuit::deserialize_circuit(&acir_buffer).unwrap();
+        let input = FunctionInput { witness: Witness(1), num_bits: 8 };
+        circuit.opcodes.extend([
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 {
+                inputs: vec![input],
+                outputs: (3..35).map(Witness).collect(),
+            }),
+            Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+            Opcode::MemoryInit { block_id: BlockId(1), init: vec![Witness(1); 3] },
+        ]);
+        circuit.current_witness_index = 34;
+        let bytecode = general_purpose::STANDARD.encode(Circuit::serialize_circuit(&circuit));
+
+        let stats = circuit_stats(&bytecode).unwrap();
+        assert_eq!(stats.opcodes.get("BlackBoxFuncCall"), Some(&3));
+        assert_eq!(stats.opcodes.get("MemoryInit"), Some(&2));
+        assert_eq!(stats.black_box.get("sha256"), Some(&1));
+        assert_eq!((stats.range_constraints(), stats.total_opcodes()), (2, 10));
+        assert_eq!(stats.largest_memory_block(), Some((1, 3)));
+        assert_eq!(stats.total_witnesses, 35);
+        assert_eq!(
+            stats.to_string(),
+            "\
+Arithmetic                      4
+BlackBoxFuncCall                3
+  range                         2
+  sha256                        1
+Brillig                         1
+MemoryInit                      2
+Total opcodes                  10
+Witnesses                      35
+Memory blocks                   2
+Largest memory block  3 (block 1)
+"
+        );
+    }
+
+    #[test]
+    fn test_size_cache_evicts_least_recently_used() {
+        let sizes = |exact| CircuitSizes { exact, total: exact, subgroup: exact };
+        let mut cache = SizeCache::new(2);
+        cache.insert([1; 32], sizes(1));
+        cache.insert([2; 32], sizes(2));
+        assert_eq!(cache.get(&[1; 32]), Some(sizes(1)));
+
+        cache.insert([3; 32], sizes(3));
+        assert_eq!(cache.get(&[2; 32]), None);
+        assert_eq!(cache.get(&[1; 32]), Some(sizes(1)));
+        assert_eq!(cache.get(&[3; 32]), Some(sizes(3)));
+
+        cache.clear();
+        assert_eq!(cache.get(&[1; 32]), None);
+    }
+
+    #[test]
+    fn test_verify_cache_reuses_verdicts() {
+        let mut cache = VerifyCache::new(0);
+        cache.insert([1; 32], VerifyOutcome::Valid);
+        assert_eq!(cache.get(&[1; 32]), None);
+        cache.set_capacity(1);
+        cache.insert([1; 32], VerifyOutcome::Valid);
+        cache.insert([2; 32], VerifyOutcome::Invalid { reason: String::from("bad proof") });
+        assert_eq!(cache.get(&[1; 32]), None);
+        assert!(!cache.get(&[2; 32]).unwrap().is_valid());
+
+        let verifications = AtomicUsize::new(0);
+        let verify = || {
+            verifications.fetch_add(1, Ordering::SeqCst);
+            Ok(VerifyOutcome::Valid)
+        };
+        // The cache shared by the process is exercised by `tests/verify_cache.rs`.
+        let cache = Mutex::new(VerifyCache::new(16));
+        let options = VerifyOptions::default();
+        let (proof, vk) = (b"proof", b"vk");
+        assert!(verify_with_cache(&cache, proof, vk, &options, verify).unwrap().is_valid());
+        assert!(verify_with_cache(&cache, proof, vk, &options, verify).unwrap().is_valid());
+        assert_eq!(verifications.load(Ordering::SeqCst), 1);
+        // The flavor is part of the key, and so is the boundary between the proof and the key.
+        let recursive = VerifyOptions { flavor: ProofFlavor::Plonk, ..Default::default() };
+        verify_with_cache(&cache, proof, vk, &recursive, verify).unwrap();
+        verify_with_cache(&cache, &proof[..proof.len() - 1], b"fvk", &options, verify).unwrap();
+        assert_eq!(verifications.load(Ordering::SeqCst), 3);
+        // Verdicts computed with an unchecked SRS are neither cached nor served from the cache.
+        let unchecked = VerifyOptions { skip_srs_check: true, ..Default::default() };
+        for _ in 0..2 {
+            verify_with_cache(&cache, proof, vk, &unchecked, verify).unwrap();
+        }
+        assert_eq!(verifications.load(Ordering::SeqCst), 5);
+        // Errors are not cached.
+        let failures = AtomicUsize::new(0);
+        for _ in 0..2 {
+            verify_with_cache(&cache, b"failure", vk, &options, || {
+                failures.fetch_add(1, Ordering::SeqCst);
+                Err(NoirRsError::Cancelled)
+            })
+            .unwrap_err();
+        }
+        assert_eq!(failures.load(Ordering::SeqCst), 2);
+
+        cache.lock().unwrap().clear();
+        verify_with_cache(&cache, proof, vk, &options, verify).unwrap();
+        assert_eq!(verifications.load(Ordering::SeqCst), 6);
+        cache.lock().unwrap().set_capacity(0);
+        verify_with_cache(&cache, proof, vk, &options, verify).unwrap();
+        assert_eq!(verifications.load(Ordering::SeqCst), 7);
+    }
+
+    #[test]
+    fn test_vk_cache_hits_by_circuit_hash() {
+        let computations = AtomicUsize::new(0);
+        let compute = |verification_key: &[u8]| {
+            computations.fetch_add(1, Ordering::SeqCst);
+            Ok(verification_key.to_vec())
+        };
+        let dir = std::env::temp_dir().join(format!("noir_rs_vk_cache_{}", std::process::id()));
+        let cache = VkCache::new(4).with_dir(&dir);
+
+        assert_eq!(cache.get_or_compute(b"acir", || compute(b"vk")).unwrap(), b"vk");
+        assert_eq!(cache.get_or_compute(b"acir", || compute(b"other vk")).unwrap(), b"vk");
+        assert_eq!(computations.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            cache.get_or_compute(b"other acir", || compute(b"other vk")).unwrap(),
+            b"other vk"
+        );
+        assert_eq!(computations.load(Ordering::SeqCst), 2);
+
+        // A new cache reads the keys stored on disk.
+        let reopened = VkCache::new(4).with_dir(&dir);
+        assert_eq!(reopened.get_or_compute(b"acir", || compute(b"other vk")).unwrap(), b"vk");
+        assert_eq!(computations.load(Ordering::SeqCst), 2);
+
+        // A key stored by another barretenberg version is computed again.
+        let entries: Vec<_> =
+            std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        assert_eq!(entries.len(), 2);
+        for path in &entries {
+            let mut stored = std::fs::read(path).unwrap();
+            stored[6] ^= 1;
+            std::fs::write(path, stored).unwrap();
+        }
+        let upgraded = VkCache::new(4).with_dir(&dir);
+        assert_eq!(upgraded.get_or_compute(b"acir", || compute(b"new vk")).unwrap(), b"new vk");
+        assert_eq!(computations.load(Ordering::SeqCst), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_verification_key_with_vk_cache() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let cache = Arc::new(VkCache::default());
+        let vk = get_verification_key(BYTECODE, DevSrs::new(DEV_SRS_POINTS), Some(&cache)).unwrap();
+        let (proof, proven_vk) = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .vk_cache(cache.clone())
+            .prove(BYTECODE, &initial_witness)
+            .unwrap();
+        assert_eq!(proven_vk, vk);
+        assert!(verify_with_srs(String::from(BYTECODE), proof, vk, DevSrs::new(DEV_SRS_POINTS))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_verify_with_circuit() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+        let (mut proof, _) =
+            prove_with_srs(BYTECODE, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+        let verdict = verify_with_circuit_and_srs(
+            &proof,
+            String::from(BYTECODE),
+            DevSrs::new(DEV_SRS_POINTS),
+        )
+        .unwrap();
+        assert!(verdict);
+
+        let last = proof.len() - 1;
+        proof[last] ^= 1;
+        let verdict = verify_with_circuit_and_srs(
+            &proof,
+            String::from(BYTECODE),
+            DevSrs::new(DEV_SRS_POINTS),
+        )
+        .unwrap();
+        assert!(!verdict);
+    }
+
+    #[test]
+    fn test_witness_diff_and_dump() {
+        let field = |value: u128| FieldElement::from(value);
+        let first = WitnessMap::from(BTreeMap::from([
+            (Witness(1), field(1)),
+            (Witness(2), field(2)),
+            (Witness(4), field(4)),
+            (Witness(5), field(5)),
+        ]));
+        let second = WitnessMap::from(BTreeMap::from([
+            (Witness(2), field(2)),
+            (Witness(3), field(3)),
+            (Witness(4), field(40)),
+            (Witness(6), field(6)),
+        ]));
+
+        assert_eq!(
+            witness::diff(&first, &second),
+            vec![
+                WitnessDiff::OnlyInFirst { witness: Witness(1), value: field(1) },
+                WitnessDiff::OnlyInSecond { witness: Witness(3), value: field(3) },
+                WitnessDiff::Changed { witness: Witness(4), first: field(4), second: field(40) },
+                WitnessDiff::OnlyInFirst { witness: Witness(5), value: field(5) },
+                WitnessDiff::OnlyInSecond { witness: Witness(6), value: field(6) },
+            ]
+        );
+        assert!(witness::diff(&first, &first).is_empty());
+        assert_eq!(witness::diff(&WitnessMap::new(), &first).len(), 4);
+
+        let mut output = Vec::new();
+        witness::dump(&second, &mut output, 2).unwrap();
+        let zeros = "0".repeat(63);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!("2: 0x{zeros}2\n3: 0x{zeros}3\n... 2 more witnesses\n")
+        );
+        let mut output = Vec::new();
+        witness::dump(&first, &mut output, 10).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), 4);
+    }
+
+    #[test]
+    fn test_witness_json_round_trip() {
+        let witness_map = WitnessMap::from(BTreeMap::from([
+            (Witness(2), FieldElement::from(0xabu128)),
+            (Witness(10), -FieldElement::one()),
+        ]));
+        let json = witness_to_json(&witness_map);
+        let minus_one = (-FieldElement::one()).to_hex();
+        assert_eq!(
+            json,
+            format!("{{\"2\":\"0x{}ab\",\"10\":\"0x{}\"}}", "0".repeat(62), minus_one)
+        );
+        assert_eq!(witness_from_json(&json).unwrap(), witness_map);
+        assert_eq!(witness_to_json(&WitnessMap::new()), "{}");
+
+        let written_by_hand = witness_from_json(r#"{"10": "1", "2": 171}"#).unwrap();
+        assert_eq!(written_by_hand[&Witness(2)], FieldElement::from(0xabu128));
+        assert_eq!(written_by_hand[&Witness(10)], FieldElement::one());
+        for invalid in ["[1, 2]", r#"{"a": "0x01"}"#, r#"{"1": "0xzz"}"#, r#"{"1": -1}"#, "{"] {
+            assert!(matches!(witness_from_json(invalid), Err(NoirRsError::InvalidWitnessJson(_))));
+        }
+    }
+
+    #[test]
+    fn test_estimate_memory() {
+        let memory = estimate_memory(1 << 19);
+        assert_eq!(memory.num_points, (1 << 19) + 1);
+        assert_eq!(memory.g1_bytes, ((1 << 19) + 1) * 64);
+        assert_eq!(memory.witness_bytes, (1 << 19) * 4 * 32);
+        assert_eq!(memory.peak_bytes(), 2 * memory.g1_bytes + memory.witness_bytes * 40);
+        // Around 2.7 GB, in line with what the backend uses for such circuits.
+        assert!((2_500_000_000..3_000_000_000).contains(&memory.peak_bytes()));
+    }
+
+    #[test]
+    fn test_estimate_peak_memory_grows_with_circuits() {
+        let fixtures = [
+            include_str!("../tests/fixtures/constant.b64"),
+            include_str!("../tests/fixtures/no_public_inputs.b64"),
+            include_str!("../tests/fixtures/circuit.b64"),
+            include_str!("../tests/fixtures/schnorr.b64"),
+        ];
+        let mut estimates: Vec<(u32, u64)> = fixtures
+            .iter()
+            .map(|bytecode| {
+                let bytecode = bytecode.trim();
+                let subgroup_size = num_points_for_circuit(bytecode).unwrap() - 1;
+                (subgroup_size, estimate_peak_memory(bytecode).unwrap())
+            })
+            .collect();
+        estimates.sort();
+        for pair in estimates.windows(2) {
+            let ((smaller_subgroup, smaller), (larger_subgroup, larger)) = (pair[0], pair[1]);
+            assert!(smaller <= larger, "{:?}", estimates);
+            assert_eq!(smaller_subgroup < larger_subgroup, smaller < larger, "{:?}", estimates);
+        }
+        // The schnorr verification is far larger than the arithmetic circuits.
+        assert!(estimates[0].0 < estimates[3].0, "{:?}", estimates);
+    }
+
+    #[test]
+    fn test_prove_within_memory_budget() {
+        let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+        let estimated = estimate_peak_memory(BYTECODE).unwrap();
+
+        let result = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .max_memory_bytes(1024)
+            .prove(BYTECODE, &initial_witness);
+        assert!(matches!(
+            result,
+            Err(NoirRsError::MemoryBudgetExceeded { estimated: reported, budget: 1024 })
+                if reported == estimated
+        ));
+
+        ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .max_memory_bytes(estimated)
+            .prove(BYTECODE, &initial_witness)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_subgroup_size_for() {
+        let float_subgroup_size = |total_gates: u32| {
+            let log_value = (total_gates as f64).log2().ceil() as u32;
+            2u32.pow(log_value)
+        };
+        for total_gates in 0..=1 << 20 {
+            assert_eq!(subgroup_size_for(total_gates), Ok(float_subgroup_size(total_gates)));
+        }
+
+        for (total_gates, expected) in [(0, 1), (1, 1), (2, 2), (3, 4), (1024, 1024), (1025, 2048)]
+        {
+            assert_eq!(subgroup_size_for(total_gates), Ok(expected), "{} gates", total_gates);
+        }
+        // Exact powers of two are not rounded up.
+        for log in 1..25 {
+            assert_eq!(subgroup_size_for(1 << log), Ok(1 << log));
+            assert_eq!(subgroup_size_for((1 << log) + 1), Ok(2 << log));
+        }
+
+        assert_eq!(subgroup_size_for(MAX_SUBGROUP_SIZE), Ok(MAX_SUBGROUP_SIZE));
+        for total_gates in [MAX_SUBGROUP_SIZE + 1, 1 << 31, (1 << 31) + 1, u32::MAX] {
+            assert_eq!(
+                subgroup_size_for(total_gates),
+                Err(SizeError::TooLarge { total_gates, max: MAX_SUBGROUP_SIZE })
+            );
+        }
+    }
+
+    #[test]
+    fn test_subgroup_size_checks_the_composer_subgroup() {
+        let sizes = CircuitSizes { exact: 1 << 16, total: (1 << 16) + 8, subgroup: 1 << 17 };
+        assert!(matches!(
+            subgroup_size(&sizes, SizingMode::Exact),
+            Err(NoirRsError::SubgroupTooSmall {
+                subgroup_size: 65_536,
+                sizing: SizingMode::Exact,
+                required: 131_072,
+            })
+        ));
+        assert_eq!(subgroup_size(&sizes, SizingMode::Total).unwrap(), 1 << 17);
+
+        let sizes = CircuitSizes { exact: 54_830, total: 54_840, subgroup: 1 << 16 };
+        assert_eq!(subgroup_size(&sizes, SizingMode::Exact).unwrap(), 1 << 16);
+        assert_eq!(subgroup_size(&sizes, SizingMode::Total).unwrap(), 1 << 16);
+    }
+
+    #[test]
+    fn test_sizing_modes_srs_points() {
+        // A subgroup of 2^16 holds the gates, but not the gates the composer reserves on top.
+        let (bytecode, initial_witness) = squaring_chain_of_gates(1 << 16);
+        let sizes = circuit_sizes(&bytecode);
+        assert!(sizes.total > 1 << 16);
+        assert_eq!(sizes.subgroup, 1 << 17);
+
+        // Exact sizing is rejected before any point is read, instead of failing natively.
+        let error = ProveOptions::new()
+            .srs(UnreadSrs(0))
+            .sizing(SizingMode::Exact)
+            .prove(&bytecode, &initial_witness)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            NoirRsError::SubgroupTooSmall {
+                subgroup_size: 65_536,
+                sizing: SizingMode::Exact,
+                required: 131_072,
+            }
+        ));
+        assert_eq!(error.code(), "subgroup_too_small");
+
+        let error = ProveOptions::new()
+            .srs(UnreadSrs(0))
+            .sizing(SizingMode::Total)
+            .prove(&bytecode, &initial_witness)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            NoirRsError::CircuitTooLargeForSrs { needed, available: 0 } if needed == (1 << 17) + 1
+        ));
     }
 }
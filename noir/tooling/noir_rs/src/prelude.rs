@@ -0,0 +1,31 @@
+//! The types needed to call the public API, for a glob import:
+//!
+//! ```
+//! use noir_rs::prelude::*;
+//!
+//! let mut initial_witness = WitnessMap::new();
+//! initial_witness.insert(Witness(1), FieldElement::one());
+//! ```
+//!
+//! Functions are called from the crate root, e.g. [`noir_rs::prove`](crate::prove). The rest of
+//! the ACIR and ACVM types are found under [`noir_rs::acir`](crate::acir) and
+//! [`noir_rs::acvm`](crate::acvm).
+
+pub use acir::{
+    circuit::Circuit,
+    native_types::{Witness, WitnessMap},
+    FieldElement,
+};
+pub use acvm::BlackBoxFunctionSolver;
+pub use noir_rs_barretenberg::srs::{
+    cached_srs::CachedSrs, dev_srs::DevSrs, grumpkin_srs::GrumpkinSrs, local_srs::LocalSrs,
+    netsrs::NetSrs, source::SrsSource, Srs, SrsError,
+};
+pub use noir_rs_blackbox_solver::BlackboxSolver;
+pub use noirc_abi::Abi;
+
+pub use crate::{
+    errors::{NoirRsError, SizeError},
+    Backend, ProofFlavor, ProveOptions, ProveOutput, Prover, SizingMode, Verifier, VerifyOptions,
+    VerifyOutcome,
+};
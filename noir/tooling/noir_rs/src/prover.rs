@@ -0,0 +1,210 @@
+use std::{collections::BTreeMap, path::Path};
+
+use acir::native_types::WitnessMap;
+use acvm::blackbox_solver::sha256;
+use noir_rs_acir_composer::AcirComposer;
+use noir_rs_acvm_runtime::execute::execute_circuit;
+use noir_rs_barretenberg::srs::Srs;
+use noir_rs_blackbox_solver::BlackboxSolver;
+
+use crate::{
+    decode_circuit, errors::NoirRsError, init_srs, read_pk_file, read_vk_file, srs_points, Backend,
+    DecodedCircuit, ProofFlavor, SizingMode,
+};
+
+/// A composer kept by a [`Prover`], with the hash of the circuit whose proving key it holds and
+/// its verification key once computed.
+struct PooledComposer {
+    acir_composer: AcirComposer,
+    circuit_hash: [u8; 32],
+    verification_key: Option<Vec<u8>>,
+}
+
+/// Generates proofs for several circuits, reusing the composers and the SRS loaded for earlier
+/// ones.
+///
+/// One composer is kept per subgroup size. Proving a circuit with a subgroup size seen before
+/// picks that composer, which keeps its proving key when the circuit is the same and is reset
+/// otherwise, instead of allocating a new one. The SRS is only reloaded when a circuit needs more
+/// points than were loaded so far.
+///
+/// The backend holds a single SRS for the whole process, so other proving functions called
+/// meanwhile may replace the one loaded by a `Prover`. It then reloads it only for circuits larger
+/// than any it proved before.
+///
+/// ```no_run
+/// # use noir_rs::prelude::*;
+/// # let circuits: Vec<(String, WitnessMap)> = Vec::new();
+/// let mut prover = Prover::new(NetSrs::from_env()?);
+/// for (bytecode, witness) in &circuits {
+///     let (proof, vk) = prover.prove(bytecode, witness)?;
+/// }
+/// # Ok::<(), noir_rs::NoirRsError>(())
+/// ```
+pub struct Prover {
+    srs: Box<dyn Srs>,
+    loaded_points: u32,
+    flavor: ProofFlavor,
+    composers: BTreeMap<u32, PooledComposer>,
+}
+
+impl Prover {
+    /// Creates a prover loading its SRS from the given source, once a circuit is proven.
+    /// # Arguments
+    /// * `srs` - The SRS source used to initialize the backend.
+    pub fn new(srs: impl Srs + 'static) -> Self {
+        Prover {
+            srs: Box::new(srs),
+            loaded_points: 0,
+            flavor: ProofFlavor::default(),
+            composers: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a prover for a circuit whose proving and verification keys were computed ahead of
+    /// time, e.g. with [`proving_key_with_srs`](crate::proving_key_with_srs) and
+    /// [`get_verification_key`](crate::get_verification_key), so that proving it computes
+    /// neither.
+    ///
+    /// The SRS the circuit needs is loaded right away, and must be the one the keys were computed
+    /// with. With `verify_artifacts`, the keys are computed again and their hashes compared with
+    /// the ones of the files, which costs as much as proving without them but catches files
+    /// computed for another version of the circuit.
+    /// # Arguments
+    /// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+    /// * `pk_path` - The proving key file, written by [`write_pk_file`](crate::write_pk_file).
+    /// * `vk_path` - The verification key file, written by [`write_vk_file`](crate::write_vk_file).
+    /// * `srs` - The SRS source used to initialize the backend.
+    /// * `verify_artifacts` - Whether to check that the keys belong to the circuit.
+    /// # Returns
+    /// * `Result<Prover, NoirRsError>` - Returns the prover, or `NoirRsError::ArtifactMismatch`
+    ///   with both hashes if a checked key does not belong to the circuit.
+    pub fn from_artifacts(
+        circuit_bytecode: &str,
+        pk_path: impl AsRef<Path>,
+        vk_path: impl AsRef<Path>,
+        srs: impl Srs + 'static,
+        verify_artifacts: bool,
+    ) -> Result<Self, NoirRsError> {
+        let (pk_path, vk_path) = (pk_path.as_ref(), vk_path.as_ref());
+        let mut prover = Prover::new(srs);
+        let DecodedCircuit { raw_acir: acir_buffer_uncompressed, .. } =
+            decode_circuit(circuit_bytecode)?;
+        let subgroup_size = prover.load_srs(&acir_buffer_uncompressed)?;
+        let proving_key = read_pk_file(pk_path)?;
+        let verification_key = read_vk_file(vk_path)?;
+
+        if verify_artifacts {
+            let acir_composer = AcirComposer::new(&subgroup_size)?;
+            let expected = acir_composer.get_proving_key(&acir_buffer_uncompressed)?;
+            check_artifact("proving key", pk_path, &expected, &proving_key)?;
+            let expected = acir_composer.get_verification_key()?;
+            check_artifact("verification key", vk_path, &expected, &verification_key)?;
+        }
+
+        let acir_composer = AcirComposer::new(&subgroup_size)?;
+        acir_composer.load_proving_key(&proving_key)?;
+        acir_composer.load_verification_key(&verification_key)?;
+        let pooled = PooledComposer {
+            acir_composer,
+            circuit_hash: sha256(&acir_buffer_uncompressed)?,
+            verification_key: Some(verification_key),
+        };
+        prover.composers.insert(subgroup_size, pooled);
+        Ok(prover)
+    }
+
+    /// Selects the flavor of the proofs to generate, [`ProofFlavor::UltraKeccak`] by default.
+    ///
+    /// # Arguments
+    /// * `flavor` - The flavor of the proofs to generate.
+    pub fn flavor(mut self, flavor: ProofFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Returns the subgroup sizes the prover holds a composer for, in increasing order.
+    pub fn subgroup_sizes(&self) -> Vec<u32> {
+        self.composers.keys().copied().collect()
+    }
+
+    /// Generates a proof with a composer of the circuit's subgroup size.
+    /// # Arguments
+    /// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+    /// * `initial_witness` - Initial witness values for the circuit.
+    /// # Returns
+    /// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key or a NoirRsError.
+    pub fn prove(
+        &mut self,
+        circuit_bytecode: &str,
+        initial_witness: &WitnessMap,
+    ) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+        let DecodedCircuit { circuit, raw_acir: acir_buffer_uncompressed } =
+            decode_circuit(circuit_bytecode)?;
+        let solved_witness =
+            execute_circuit(&BlackboxSolver::new(), circuit, initial_witness.clone())?;
+        let serialized_solved_witness = bincode::serialize(&solved_witness)?;
+
+        let subgroup_size = self.load_srs(&acir_buffer_uncompressed)?;
+
+        let circuit_hash = sha256(&acir_buffer_uncompressed)?;
+        let mut pooled = match self.composers.remove(&subgroup_size) {
+            Some(mut pooled) if pooled.circuit_hash != circuit_hash => {
+                pooled.acir_composer.reset()?;
+                pooled.circuit_hash = circuit_hash;
+                pooled.verification_key = None;
+                pooled
+            }
+            Some(pooled) => pooled,
+            None => PooledComposer {
+                acir_composer: AcirComposer::new(&subgroup_size)?,
+                circuit_hash,
+                verification_key: None,
+            },
+        };
+        let proof = pooled.acir_composer.create_proof(
+            &acir_buffer_uncompressed,
+            &serialized_solved_witness,
+            self.flavor.is_recursive(),
+        )?;
+        let verification_key = match &pooled.verification_key {
+            Some(verification_key) => verification_key.clone(),
+            None => pooled.acir_composer.get_verification_key()?,
+        };
+        // A composer that failed may hold a partial circuit, so it is only kept on success.
+        pooled.verification_key = Some(verification_key.clone());
+        self.composers.insert(subgroup_size, pooled);
+        Ok((proof, verification_key))
+    }
+
+    /// Loads the SRS a circuit needs unless enough points were loaded already, and returns the
+    /// subgroup size of the circuit.
+    fn load_srs(&mut self, acir_buffer_uncompressed: &[u8]) -> Result<u32, NoirRsError> {
+        let (subgroup_size, num_points) =
+            srs_points(acir_buffer_uncompressed, SizingMode::default(), Backend::Plonk)?;
+        if num_points > self.loaded_points {
+            init_srs(self.srs.as_mut(), num_points, false)?;
+            self.loaded_points = num_points;
+        }
+        Ok(subgroup_size)
+    }
+}
+
+/// Compares the hash of a key read from `path` with the one of the key computed for the circuit.
+fn check_artifact(
+    artifact: &'static str,
+    path: &Path,
+    expected: &[u8],
+    found: &[u8],
+) -> Result<(), NoirRsError> {
+    let (expected, found) = (hex::encode(sha256(expected)?), hex::encode(sha256(found)?));
+    if expected != found {
+        return Err(NoirRsError::ArtifactMismatch {
+            artifact,
+            path: path.to_path_buf(),
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
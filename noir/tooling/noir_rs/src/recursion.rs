@@ -0,0 +1,260 @@
+use acir::native_types::{Witness, WitnessMap};
+use acvm::FieldElement;
+use ark_bn254::{Fq, G1Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInt, PrimeField};
+use noir_rs_acir_composer::AcirComposer;
+use noir_rs_barretenberg::srs::{netsrs::NetSrs, Srs};
+
+use crate::{
+    errors::NoirRsError,
+    init_srs, num_public_inputs,
+    solidity::{proof_layout, take, take_u32},
+    split_proof, verify_with_options, ProofFlavor, VerifyOptions,
+};
+
+/// Number of field elements in an aggregation object: the two accumulated pairing points, each
+/// coordinate being a non-native field split into four 68-bit limbs.
+pub const AGGREGATION_OBJECT_SIZE: usize = 16;
+
+/// Number of bits of each limb of an aggregation object coordinate.
+const AGGREGATION_LIMB_BITS: u32 = 68;
+
+/// Splits a buffer of consecutive 32-byte big-endian field elements.
+fn bytes_to_fields(buffer: &[u8]) -> Vec<FieldElement> {
+    buffer.chunks(32).map(FieldElement::from_be_bytes_reduce).collect()
+}
+
+/// Converts a proof into the field elements consumed by the recursion opcode of a verifier circuit.
+///
+/// Only [`ProofFlavor::Plonk`](crate::ProofFlavor::Plonk) proofs can be converted, since the
+/// conversion replays the Pedersen/Blake3s transcript. The fields follow the order of the proof
+/// transcript: the public inputs first, then every commitment as four limbs (`x_lo`, `x_hi`,
+/// `y_lo`, `y_hi`) and every evaluation as a single field, round by round.
+/// # Arguments
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key the proof was generated with.
+/// # Returns
+/// * `Result<Vec<FieldElement>, NoirRsError>` - Returns the proof as field elements or a NoirRsError.
+pub fn proof_as_fields(
+    proof: &[u8],
+    verification_key: &[u8],
+) -> Result<Vec<FieldElement>, NoirRsError> {
+    let num_public_inputs = num_public_inputs(verification_key)?;
+    let acir_composer = AcirComposer::new(&0)?;
+    let fields = acir_composer.serialize_proof_into_fields(proof, num_public_inputs)?;
+    Ok(bytes_to_fields(&fields))
+}
+
+/// Converts a verification key into the field elements consumed by the recursion opcode of a
+/// verifier circuit, downloading the G2 point of the SRS to load the key.
+///
+/// See [`vk_as_fields_with_srs`] for the field ordering.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<(Vec<FieldElement>, FieldElement), NoirRsError>` - Returns the key as field elements
+///   and the key hash, or a NoirRsError.
+pub fn vk_as_fields(
+    verification_key: &[u8],
+) -> Result<(Vec<FieldElement>, FieldElement), NoirRsError> {
+    vk_as_fields_with_srs(verification_key, NetSrs::from_env()?)
+}
+
+/// Converts a verification key into the field elements consumed by the recursion opcode of a
+/// verifier circuit, loading the key with the given SRS source.
+///
+/// The fields are the evaluation domain root, domain size and generator, the circuit size, the
+/// number of public inputs, the recursive proof flag, the 16 public input indices of the
+/// aggregation object (zero if absent) and then every selector and permutation commitment as four
+/// limbs (`x_lo`, `x_hi`, `y_lo`, `y_hi`). The key hash is returned separately.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// * `srs` - The SRS source the G2 point is loaded from.
+/// # Returns
+/// * `Result<(Vec<FieldElement>, FieldElement), NoirRsError>` - Returns the key as field elements
+///   and the key hash, or a NoirRsError.
+pub fn vk_as_fields_with_srs<S: Srs>(
+    verification_key: &[u8],
+    mut srs: S,
+) -> Result<(Vec<FieldElement>, FieldElement), NoirRsError> {
+    init_srs(&mut srs, 1, false)?;
+
+    let acir_composer = AcirComposer::new(&0)?;
+    acir_composer.load_verification_key(verification_key)?;
+    let (fields, key_hash) = acir_composer.serialize_verification_key_into_fields()?;
+    Ok((bytes_to_fields(&fields), FieldElement::from_be_bytes_reduce(&key_hash)))
+}
+
+/// Reads the aggregation object off the public inputs of a proof of a circuit verifying a proof
+/// recursively, generated with [`ProveOptions::recursive`](crate::ProveOptions::recursive).
+///
+/// The last recursion opcode of such a circuit outputs the accumulated pairing points, which its
+/// verification key records as public inputs. A circuit verifying the proof in turn passes them
+/// as the `input_aggregation_object` of its own recursion opcode, see
+/// [`inject_aggregation_object`].
+/// # Arguments
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key the proof was generated with.
+/// # Returns
+/// * `Result<[FieldElement; 16], NoirRsError>` - Returns the aggregation object, or a NoirRsError
+///   if the circuit does not verify a proof recursively or the proof is too short.
+pub fn extract_aggregation_object(
+    proof: &[u8],
+    verification_key: &[u8],
+) -> Result<[FieldElement; AGGREGATION_OBJECT_SIZE], NoirRsError> {
+    let indices =
+        recursive_proof_indices(verification_key)?.ok_or(NoirRsError::NotRecursiveProof)?;
+    let (public_inputs, _) = split_proof(proof, num_public_inputs(verification_key)?);
+
+    let mut aggregation_object = [FieldElement::zero(); AGGREGATION_OBJECT_SIZE];
+    for (field, index) in aggregation_object.iter_mut().zip(indices) {
+        *field = *public_inputs
+            .get(index as usize)
+            .ok_or(NoirRsError::ProofTooShort { num_public_inputs: public_inputs.len(), index })?;
+    }
+    Ok(aggregation_object)
+}
+
+/// Assigns an aggregation object, as read by [`extract_aggregation_object`], to the witnesses of
+/// the `input_aggregation_object` of a recursion opcode.
+/// # Arguments
+/// * `witness_map` - The initial witness of the circuit verifying the proof.
+/// * `witnesses` - The witnesses of the `input_aggregation_object`, in order.
+/// * `aggregation_object` - The aggregation object of the proof being verified.
+pub fn inject_aggregation_object(
+    witness_map: &mut WitnessMap,
+    witnesses: &[Witness; AGGREGATION_OBJECT_SIZE],
+    aggregation_object: &[FieldElement; AGGREGATION_OBJECT_SIZE],
+) {
+    for (witness, field) in witnesses.iter().zip(aggregation_object) {
+        witness_map.insert(*witness, *field);
+    }
+}
+
+/// Reads the public input indices of the aggregation object recorded by a verification key, or
+/// `None` if its circuit does not verify a proof recursively.
+fn recursive_proof_indices(verification_key: &[u8]) -> Result<Option<Vec<u32>>, NoirRsError> {
+    let invalid = || NoirRsError::InvalidVerificationKey(verification_key.len());
+    // Skip the circuit type, circuit size and number of public inputs.
+    let mut reader = verification_key.get(12..).ok_or_else(invalid)?;
+    let num_commitments = take_u32(&mut reader).ok_or_else(invalid)?;
+    for _ in 0..num_commitments {
+        let name_len = take_u32(&mut reader).ok_or_else(invalid)?;
+        take(&mut reader, name_len as usize + 64).ok_or_else(invalid)?;
+    }
+    let contains_recursive_proof = take(&mut reader, 1).ok_or_else(invalid)?[0] != 0;
+    let num_indices = take_u32(&mut reader).ok_or_else(invalid)?;
+    let indices = (0..num_indices)
+        .map(|_| take_u32(&mut reader).ok_or_else(invalid))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !contains_recursive_proof {
+        return Ok(None);
+    }
+    if indices.len() != AGGREGATION_OBJECT_SIZE {
+        return Err(invalid());
+    }
+    Ok(Some(indices))
+}
+
+/// Checks that an aggregation object, as read by [`extract_aggregation_object`], holds two points
+/// of the bn254 curve.
+///
+/// The object lists the `x` and `y` coordinates of the first point, then those of the second, each
+/// as four limbs of 68 bits from the least significant one. Every limb must fit in 68 bits and
+/// every coordinate must be reduced, as the recursion opcode of the next circuit constrains them.
+/// # Arguments
+/// * `aggregation_object` - The aggregation object to check.
+/// # Returns
+/// * `Result<(), NoirRsError>` - Returns an empty result if the object is well-formed, or a
+///   `NoirRsError::InvalidAggregationObject` naming the first problem.
+pub fn validate_aggregation_object(
+    aggregation_object: &[FieldElement; AGGREGATION_OBJECT_SIZE],
+) -> Result<(), NoirRsError> {
+    let mut coordinates = aggregation_object.chunks(4).enumerate().map(|(index, limbs)| {
+        let invalid = |reason: &str| {
+            NoirRsError::InvalidAggregationObject(format!("coordinate {}: {}", index, reason))
+        };
+        let mut words = [0u64; 6];
+        for (position, limb) in limbs.iter().enumerate() {
+            if limb.num_bits() > AGGREGATION_LIMB_BITS {
+                return Err(invalid(&format!("limb {} exceeds 68 bits", position)));
+            }
+            let offset = position as u32 * AGGREGATION_LIMB_BITS;
+            let (word, shift) = ((offset / 64) as usize, offset % 64);
+            let value = limb.to_u128();
+            words[word] |= (value << shift) as u64;
+            words[word + 1] |= (value >> (64 - shift)) as u64;
+            if shift > 0 {
+                words[word + 2] |= (value >> (128 - shift)) as u64;
+            }
+        }
+        match words[4..] {
+            [0, 0] => Fq::from_bigint(BigInt::new([words[0], words[1], words[2], words[3]]))
+                .ok_or_else(|| invalid("is not reduced modulo the base field")),
+            _ => Err(invalid("is not reduced modulo the base field")),
+        }
+    });
+
+    for point in 0..2 {
+        let x = coordinates.next().unwrap()?;
+        let y = coordinates.next().unwrap()?;
+        let point_affine = G1Affine::new_unchecked(x, y);
+        // The bn254 G1 cofactor is 1, so every point of the curve is in the subgroup.
+        if !point_affine.is_on_curve() || point_affine.is_zero() {
+            return Err(NoirRsError::InvalidAggregationObject(format!(
+                "point {} is not on the bn254 curve",
+                point
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Verifies a recursive proof of a circuit verifying a proof recursively, downloading the SRS from
+/// the same transcript as [`prove`](crate::prove).
+///
+/// See [`verify_recursive_with_srs`].
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+pub fn verify_recursive(
+    circuit_bytecode: String,
+    proof: Vec<u8>,
+    verification_key: Vec<u8>,
+) -> Result<bool, NoirRsError> {
+    verify_recursive_with_srs(circuit_bytecode, proof, verification_key, NetSrs::from_env()?)
+}
+
+/// Verifies a [`ProofFlavor::Plonk`] proof of a circuit verifying a proof recursively, using the
+/// given SRS source.
+///
+/// Before the pairing check, the proof must have the length the key expects and its aggregation
+/// object must pass [`validate_aggregation_object`], so that a proof the next circuit of the chain
+/// cannot consume is reported as an error rather than verified.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// * `srs` - The SRS source the points are loaded from.
+/// # Returns
+/// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise,
+///   or a NoirRsError if the key does not record an aggregation object, the proof has the wrong
+///   length or its aggregation object is malformed.
+pub fn verify_recursive_with_srs<S: Srs>(
+    circuit_bytecode: String,
+    proof: Vec<u8>,
+    verification_key: Vec<u8>,
+    srs: S,
+) -> Result<bool, NoirRsError> {
+    let expected = proof_layout(&verification_key)?.total_bytes as usize;
+    if proof.len() != expected {
+        return Err(NoirRsError::ProofLengthMismatch { expected, actual: proof.len() });
+    }
+    validate_aggregation_object(&extract_aggregation_object(&proof, &verification_key)?)?;
+    let options = VerifyOptions { flavor: ProofFlavor::Plonk, ..Default::default() };
+    verify_with_options(circuit_bytecode, proof, verification_key, srs, &options)
+}
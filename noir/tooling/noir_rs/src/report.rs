@@ -0,0 +1,274 @@
+use std::{collections::BTreeMap, fmt};
+
+use acir::circuit::{Circuit, Opcode};
+
+use crate::{
+    bytecode::{decode_bytecode, decode_circuit, decompress_bytecode},
+    errors::NoirRsError,
+    srs_points, Backend, SizingMode,
+};
+
+/// Number of opcodes of each kind in a circuit.
+///
+/// Opcodes do not translate one to one into gates: an arithmetic opcode is a single gate while a
+/// black box call such as `keccak256` expands to thousands of them. The backend gate count of the
+/// whole circuit is given by [`get_circuit_sizes`](crate::get_circuit_sizes).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GateReport {
+    /// Arithmetic opcodes, each constraining a polynomial expression of witnesses.
+    pub arithmetic: usize,
+    /// Black box function calls, keyed by the function name such as `sha256` or `range`.
+    pub black_box: BTreeMap<String, usize>,
+    /// Memory reads, writes and block initializations.
+    pub memory: usize,
+    /// Unconstrained Brillig calls, which add no gates but are executed by the solver.
+    pub brillig: usize,
+    /// Directives, which are solver hints and add no gates.
+    pub directive: usize,
+}
+
+impl GateReport {
+    /// Counts the opcodes of a deserialized circuit.
+    pub fn from_circuit(circuit: &Circuit) -> Self {
+        let mut report = GateReport::default();
+        for opcode in &circuit.opcodes {
+            match opcode {
+                Opcode::Arithmetic(_) => report.arithmetic += 1,
+                Opcode::BlackBoxFuncCall(call) => {
+                    *report.black_box.entry(call.name().to_string()).or_default() += 1
+                }
+                Opcode::MemoryOp { .. } | Opcode::MemoryInit { .. } => report.memory += 1,
+                Opcode::Brillig(_) => report.brillig += 1,
+                Opcode::Directive(_) => report.directive += 1,
+            }
+        }
+        report
+    }
+
+    /// Returns the total number of black box function calls.
+    pub fn black_box_total(&self) -> usize {
+        self.black_box.values().sum()
+    }
+
+    /// Returns the total number of opcodes.
+    pub fn total(&self) -> usize {
+        self.arithmetic + self.black_box_total() + self.memory + self.brillig + self.directive
+    }
+}
+
+/// Counts the opcodes of each kind in a circuit, without executing or proving it.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<GateReport, NoirRsError>` - Returns the opcode counts or a NoirRsError.
+pub fn gate_report(circuit_bytecode: &str) -> Result<GateReport, NoirRsError> {
+    let circuit = decode_circuit(circuit_bytecode)?.circuit;
+    Ok(GateReport::from_circuit(&circuit))
+}
+
+/// Statistics on the opcodes, witnesses and memory blocks of a circuit.
+///
+/// Unlike [`GateReport`], opcodes are counted per ACIR `Opcode` variant, and the witnesses and
+/// memory blocks are reported too. `Display` prints an aligned table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CircuitStats {
+    /// Opcodes, keyed by the name of their `Opcode` variant such as `Arithmetic` or `MemoryOp`.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_opcode_counts"))]
+    pub opcodes: BTreeMap<&'static str, usize>,
+    /// Black box function calls, keyed by the function name such as `sha256` or `range`.
+    pub black_box: BTreeMap<String, usize>,
+    /// Number of witnesses the circuit allocates, from witness 0 to its highest witness index.
+    pub total_witnesses: u32,
+    /// Number of elements of each memory block, keyed by block id.
+    pub memory_blocks: BTreeMap<u32, usize>,
+}
+
+/// Names of the ACIR `Opcode` variants, the keys of [`CircuitStats::opcodes`].
+#[cfg(feature = "serde")]
+const OPCODE_VARIANTS: [&str; 6] =
+    ["Arithmetic", "BlackBoxFuncCall", "Directive", "Brillig", "MemoryOp", "MemoryInit"];
+
+/// Reads opcode counts keyed by variant name back into static names, rejecting unknown variants.
+#[cfg(feature = "serde")]
+fn deserialize_opcode_counts<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<&'static str, usize>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::{de::Error, Deserialize};
+
+    let counts = BTreeMap::<String, usize>::deserialize(deserializer)?;
+    counts
+        .into_iter()
+        .map(|(name, count)| {
+            let variant = OPCODE_VARIANTS.iter().find(|&&variant| variant == name);
+            variant
+                .map(|&variant| (variant, count))
+                .ok_or_else(|| D::Error::unknown_variant(&name, &OPCODE_VARIANTS))
+        })
+        .collect()
+}
+
+impl CircuitStats {
+    /// Walks the opcodes of a deserialized circuit.
+    pub fn from_circuit(circuit: &Circuit) -> Self {
+        let mut stats = CircuitStats {
+            total_witnesses: circuit.current_witness_index + 1,
+            ..CircuitStats::default()
+        };
+        for opcode in &circuit.opcodes {
+            let variant = match opcode {
+                Opcode::Arithmetic(_) => "Arithmetic",
+                Opcode::BlackBoxFuncCall(call) => {
+                    *stats.black_box.entry(call.name().to_string()).or_default() += 1;
+                    "BlackBoxFuncCall"
+                }
+                Opcode::Directive(_) => "Directive",
+                Opcode::Brillig(_) => "Brillig",
+                Opcode::MemoryOp { .. } => "MemoryOp",
+                Opcode::MemoryInit { block_id, init } => {
+                    stats.memory_blocks.insert(block_id.0, init.len());
+                    "MemoryInit"
+                }
+            };
+            *stats.opcodes.entry(variant).or_default() += 1;
+        }
+        stats
+    }
+
+    /// Returns the total number of opcodes.
+    pub fn total_opcodes(&self) -> usize {
+        self.opcodes.values().sum()
+    }
+
+    /// Returns the number of range constraints.
+    pub fn range_constraints(&self) -> usize {
+        self.black_box.get("range").copied().unwrap_or_default()
+    }
+
+    /// Returns the id and the number of elements of the largest memory block, the first one for
+    /// blocks of equal size.
+    pub fn largest_memory_block(&self) -> Option<(u32, usize)> {
+        self.memory_blocks.iter().map(|(&block_id, &len)| (block_id, len)).fold(
+            None,
+            |largest, block| match largest {
+                Some((_, len)) if len >= block.1 => largest,
+                _ => Some(block),
+            },
+        )
+    }
+}
+
+impl fmt::Display for CircuitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = Vec::new();
+        for (&variant, count) in &self.opcodes {
+            rows.push((variant.to_string(), count.to_string()));
+            if variant == "BlackBoxFuncCall" {
+                for (name, count) in &self.black_box {
+                    rows.push((format!("  {}", name), count.to_string()));
+                }
+            }
+        }
+        rows.push((String::from("Total opcodes"), self.total_opcodes().to_string()));
+        rows.push((String::from("Witnesses"), self.total_witnesses.to_string()));
+        rows.push((String::from("Memory blocks"), self.memory_blocks.len().to_string()));
+        let largest = match self.largest_memory_block() {
+            Some((block_id, len)) => format!("{} (block {})", len, block_id),
+            None => String::from("-"),
+        };
+        rows.push((String::from("Largest memory block"), largest));
+
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or_default();
+        let value_width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or_default();
+        for (label, value) in rows {
+            writeln!(f, "{:<label_width$}  {:>value_width$}", label, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes statistics on the opcodes, witnesses and memory blocks of a circuit, without executing
+/// or proving it.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<CircuitStats, NoirRsError>` - Returns the statistics or a NoirRsError.
+pub fn circuit_stats(circuit_bytecode: &str) -> Result<CircuitStats, NoirRsError> {
+    let circuit = decode_circuit(circuit_bytecode)?.circuit;
+    Ok(CircuitStats::from_circuit(&circuit))
+}
+
+/// Bytes of a field element in the backend.
+const FIELD_BYTES: u64 = 32;
+/// Wires of an UltraPlonk gate, each holding one witness value per gate.
+const NUM_WIRES: u64 = 4;
+/// Bytes held while proving per byte of witness, an approximation of the proving key
+/// polynomials, which are kept both in monomial form and evaluated over a 4x larger coset.
+pub const PROVING_OVERHEAD_MULTIPLIER: u64 = 40;
+
+/// Approximate memory needed to prove a circuit of a given subgroup size with UltraPlonk.
+///
+/// The figures are a rough guide for picking a machine, not a bound: the actual peak depends on
+/// the circuit, such as the size of its lookup tables, and on the number of threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryEstimate {
+    /// Number of SRS points loaded for the subgroup.
+    pub num_points: u32,
+    /// Bytes of the G1 points, as loaded by the SRS sources.
+    pub g1_bytes: u64,
+    /// Bytes of the witness values placed on the wires of the gates.
+    pub witness_bytes: u64,
+    /// Bytes held while proving per byte of witness, [`PROVING_OVERHEAD_MULTIPLIER`].
+    pub overhead_multiplier: u64,
+}
+
+impl MemoryEstimate {
+    /// Returns the approximate peak memory of proving, in bytes.
+    ///
+    /// The SRS loaded by a source is handed to the backend and freed, but both copies coexist
+    /// while it is copied.
+    pub fn peak_bytes(&self) -> u64 {
+        2 * self.g1_bytes + self.witness_bytes * self.overhead_multiplier
+    }
+}
+
+/// Estimates the memory needed to prove a circuit, from its subgroup size as returned by
+/// [`subgroup_size_for`](crate::subgroup_size_for).
+/// # Arguments
+/// * `subgroup_size` - The subgroup size of the circuit.
+/// # Returns
+/// * `MemoryEstimate` - Returns the approximate sizes of the SRS, the witness and the proving
+///   overhead.
+pub fn estimate_memory(subgroup_size: u32) -> MemoryEstimate {
+    let num_points = Backend::Plonk.num_points(subgroup_size);
+    MemoryEstimate {
+        num_points,
+        g1_bytes: num_points as u64 * 64,
+        witness_bytes: subgroup_size as u64 * NUM_WIRES * FIELD_BYTES,
+        overhead_multiplier: PROVING_OVERHEAD_MULTIPLIER,
+    }
+}
+
+/// Estimates the peak memory of proving a circuit with the default options, following the model
+/// of [`MemoryEstimate::peak_bytes`]: twice the G1 points of the SRS, plus the witness placed on
+/// the wires multiplied by [`PROVING_OVERHEAD_MULTIPLIER`] for the proving key.
+///
+/// This is the estimate checked against [`ProveOptions::max_memory_bytes`](crate::ProveOptions::max_memory_bytes).
+/// The sizes of the circuit are cached, so proving it afterwards does not compute them again.
+/// # Arguments
+/// * `circuit_bytecode` - Base64 encoded, gzipped ACIR bytecode.
+/// # Returns
+/// * `Result<u64, NoirRsError>` - Returns the estimated peak memory in bytes, or a NoirRsError.
+pub fn estimate_peak_memory(circuit_bytecode: &str) -> Result<u64, NoirRsError> {
+    let acir_buffer = decode_bytecode(circuit_bytecode)?;
+    let acir_buffer_uncompressed = decompress_bytecode(&acir_buffer)?;
+
+    let (subgroup_size, _) =
+        srs_points(&acir_buffer_uncompressed, SizingMode::default(), Backend::default())?;
+    Ok(estimate_memory(subgroup_size).peak_bytes())
+}
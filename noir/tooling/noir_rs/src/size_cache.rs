@@ -0,0 +1,81 @@
+use std::sync::Mutex;
+
+use acvm::blackbox_solver::sha256;
+use noir_rs_barretenberg::circuit::circuit_size::{get_circuit_sizes, CircuitSizes};
+
+use crate::errors::NoirRsError;
+
+/// Number of circuits whose sizes are kept, enough for a server proving a handful of circuits.
+const SIZE_CACHE_CAPACITY: usize = 32;
+
+static SIZE_CACHE: Mutex<SizeCache> = Mutex::new(SizeCache::new(SIZE_CACHE_CAPACITY));
+
+/// Least recently used cache of circuit sizes, keyed by [`circuit_hash`](crate::circuit_hash).
+///
+/// Entries are ordered from the most to the least recently used.
+#[derive(Debug)]
+pub(crate) struct SizeCache {
+    entries: Vec<([u8; 32], CircuitSizes)>,
+    capacity: usize,
+}
+
+impl SizeCache {
+    pub(crate) const fn new(capacity: usize) -> Self {
+        SizeCache { entries: Vec::new(), capacity }
+    }
+
+    /// Returns the sizes stored for `hash` and marks them as most recently used.
+    pub(crate) fn get(&mut self, hash: &[u8; 32]) -> Option<CircuitSizes> {
+        let position = self.entries.iter().position(|(key, _)| key == hash)?;
+        let entry = self.entries.remove(position);
+        self.entries.insert(0, entry);
+        Some(entry.1)
+    }
+
+    /// Stores the sizes for `hash`, evicting the least recently used entry when full.
+    pub(crate) fn insert(&mut self, hash: [u8; 32], sizes: CircuitSizes) {
+        self.entries.retain(|(key, _)| *key != hash);
+        if self.entries.len() >= self.capacity {
+            self.entries.pop();
+        }
+        self.entries.insert(0, (hash, sizes));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Returns the sizes of a circuit, computing them with the backend only on the first call for a
+/// given circuit.
+/// # Arguments
+/// * `acir_buffer_uncompressed` - The decompressed ACIR of the circuit.
+/// # Returns
+/// * `Result<CircuitSizes, NoirRsError>` - Returns the circuit sizes or a NoirRsError.
+pub(crate) fn cached_circuit_sizes(
+    acir_buffer_uncompressed: &[u8],
+) -> Result<CircuitSizes, NoirRsError> {
+    let hash = sha256(acir_buffer_uncompressed)?;
+    if let Some(sizes) = lock().get(&hash) {
+        return Ok(sizes);
+    }
+
+    // The lock is not held while the backend builds the circuit, so concurrent calls may compute
+    // the sizes of the same circuit twice, which is harmless.
+    let sizes = get_circuit_sizes(acir_buffer_uncompressed)?;
+    lock().insert(hash, sizes);
+    Ok(sizes)
+}
+
+/// Empties the cache of circuit sizes consulted by the proving functions.
+///
+/// The cache holds at most 32 circuits, call this to release it sooner, e.g. after retiring a
+/// circuit.
+pub fn clear_size_cache() {
+    lock().clear();
+}
+
+fn lock() -> std::sync::MutexGuard<'static, SizeCache> {
+    // The cache holds no invariant a panic could break, so a poisoned lock is still usable.
+    SIZE_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,178 @@
+use acvm::blackbox_solver::sha256;
+use noir_rs_acir_composer::AcirComposer;
+use noir_rs_barretenberg::srs::{netsrs::NetSrs, Srs};
+
+use crate::{errors::NoirRsError, init_srs, num_public_inputs};
+
+/// Size of an UltraPlonk proof without its public inputs, which is the same for every circuit.
+pub(crate) const PROOF_BODY_BYTES: u32 = 2144;
+
+/// Size of the calldata of a call to `verify(bytes _proof, bytes32[] _publicInputs)` besides the
+/// proof and the public inputs: the function selector, the offsets of both arguments and their
+/// lengths.
+const VERIFY_CALL_OVERHEAD_BYTES: u32 = 4 + 4 * 32;
+
+/// Sizes of the proofs checked by the Solidity verifier of a verification key, see
+/// [`verifier_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierStats {
+    /// Number of public inputs of the circuit, passed to `verify` as `bytes32` each.
+    pub num_public_inputs: u32,
+    /// Size of the calldata of a `verify` call, for a proof split with
+    /// [`split_proof`](crate::split_proof) into the proof body and the public inputs.
+    pub estimated_calldata_bytes_per_proof: u32,
+    /// Size of the proof body, without the public inputs.
+    pub proof_size: u32,
+}
+
+/// Sizes of the UltraPlonk proofs of a circuit, see [`proof_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofLayout {
+    /// Number of public inputs prepended to the proof, as 32-byte big-endian field elements.
+    pub num_public_inputs: u32,
+    /// Size of the proof body following the public inputs, the same for every circuit.
+    pub proof_bytes_without_inputs: u32,
+    /// Size of a proof, with its public inputs.
+    pub total_bytes: u32,
+}
+
+/// Generates the Solidity verification key contract for a verification key, downloading the G2
+/// point of the SRS to load the key.
+///
+/// See [`solidity_verifier_with_srs`] for the contents of the contract.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<String, NoirRsError>` - Returns the contract source or a NoirRsError.
+pub fn solidity_verifier(verification_key: &[u8]) -> Result<String, NoirRsError> {
+    solidity_verifier_with_srs(verification_key, NetSrs::from_env()?)
+}
+
+/// Generates the Solidity verification key contract for a verification key, loading the key with
+/// the given SRS source.
+///
+/// The contract is the `UltraVerificationKey` library that `bb contract` emits. Its first line is a
+/// `// Verification Key Hash: <hex>` comment, and the same SHA-256 hash is returned on-chain by
+/// `verificationKeyHash()`, so a deployment can be checked against the key it was generated from.
+/// Only [`ProofFlavor::UltraKeccak`](crate::ProofFlavor::UltraKeccak) proofs can be verified
+/// on-chain.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// * `srs` - The SRS source the G2 point is loaded from. It must match the SRS the key was
+///   generated with.
+/// # Returns
+/// * `Result<String, NoirRsError>` - Returns the contract source or a NoirRsError.
+pub fn solidity_verifier_with_srs<S: Srs>(
+    verification_key: &[u8],
+    mut srs: S,
+) -> Result<String, NoirRsError> {
+    init_srs(&mut srs, 1, false)?;
+
+    let acir_composer = AcirComposer::new(&0)?;
+    acir_composer.load_verification_key(verification_key)?;
+    Ok(acir_composer.get_solidity_verifier()?)
+}
+
+/// Computes the sizes of the proofs checked by the Solidity verifier of a Plonk verification key,
+/// e.g. to estimate the cost of verifying them on-chain before deploying the verifier.
+///
+/// The sizes only depend on the number of public inputs the key declares, as an UltraPlonk proof
+/// body always takes 2144 bytes. The calldata of a `verify` call is the ABI encoding of the proof
+/// body and the public inputs, each as a 32-byte word, behind the function selector.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<VerifierStats, NoirRsError>` - Returns the sizes, or a NoirRsError if the key is too
+///   short.
+pub fn verifier_stats(verification_key: &[u8]) -> Result<VerifierStats, NoirRsError> {
+    let num_public_inputs = num_public_inputs(verification_key)?;
+    Ok(VerifierStats {
+        num_public_inputs,
+        estimated_calldata_bytes_per_proof: VERIFY_CALL_OVERHEAD_BYTES
+            + PROOF_BODY_BYTES
+            + num_public_inputs * 32,
+        proof_size: PROOF_BODY_BYTES,
+    })
+}
+
+/// Computes the size of every proof of a circuit from its Plonk verification key, e.g. to allocate
+/// the calldata of a contract before the proof is generated.
+///
+/// An UltraPlonk proof, of either [`ProofFlavor`](crate::ProofFlavor), is laid out as its public
+/// inputs, 32 bytes each, followed by a 2144-byte body, so its length only depends on the number of
+/// public inputs the key declares. Proving checks the proofs it returns against this layout in
+/// debug builds.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<ProofLayout, NoirRsError>` - Returns the sizes, or a NoirRsError if the key is too
+///   short.
+pub fn proof_layout(verification_key: &[u8]) -> Result<ProofLayout, NoirRsError> {
+    let num_public_inputs = num_public_inputs(verification_key)?;
+    Ok(ProofLayout {
+        num_public_inputs,
+        proof_bytes_without_inputs: PROOF_BODY_BYTES,
+        total_bytes: num_public_inputs * 32 + PROOF_BODY_BYTES,
+    })
+}
+
+/// Computes the hash of a verification key that the Solidity verifier of the key returns from
+/// `verificationKeyHash()`, e.g. to register the key with a contract.
+///
+/// This is barretenberg's SHA-256 of the key fields, each as a 32-byte big-endian word: the circuit
+/// type, circuit size and number of public inputs, the coordinates of each commitment, the
+/// recursive proof flag and the recursive proof public input indices. The hash is not reduced to
+/// a field element, as it is returned on-chain as a `bytes32`, and it is read from the key alone,
+/// so no SRS is needed.
+/// # Arguments
+/// * `verification_key` - Buffer representing the verification key.
+/// # Returns
+/// * `Result<[u8; 32], NoirRsError>` - Returns the hash of the key, or a NoirRsError if the key
+///   cannot be read.
+pub fn vk_hash(verification_key: &[u8]) -> Result<[u8; 32], NoirRsError> {
+    let invalid = || NoirRsError::InvalidVerificationKey(verification_key.len());
+    let mut reader = verification_key;
+    let mut words: Vec<&[u8]> = Vec::new();
+
+    // The circuit type, circuit size and number of public inputs.
+    for _ in 0..3 {
+        words.push(take(&mut reader, 4).ok_or_else(invalid)?);
+    }
+    let num_commitments = take_u32(&mut reader).ok_or_else(invalid)?;
+    for _ in 0..num_commitments {
+        let name_len = take_u32(&mut reader).ok_or_else(invalid)?;
+        take(&mut reader, name_len as usize).ok_or_else(invalid)?;
+        words.push(take(&mut reader, 32).ok_or_else(invalid)?);
+        words.push(take(&mut reader, 32).ok_or_else(invalid)?);
+    }
+    words.push(take(&mut reader, 1).ok_or_else(invalid)?);
+    let num_indices = take_u32(&mut reader).ok_or_else(invalid)?;
+    for _ in 0..num_indices {
+        words.push(take(&mut reader, 4).ok_or_else(invalid)?);
+    }
+    if !reader.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut buffer = (words.len() as u32).to_be_bytes().to_vec();
+    for word in words {
+        buffer.resize(buffer.len() + 32 - word.len(), 0);
+        buffer.extend_from_slice(word);
+    }
+    Ok(sha256(&buffer)?)
+}
+
+/// Takes the next `len` bytes off the front of `reader`.
+pub(crate) fn take<'a>(reader: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if reader.len() < len {
+        return None;
+    }
+    let (bytes, rest) = reader.split_at(len);
+    *reader = rest;
+    Some(bytes)
+}
+
+/// Takes a big-endian `u32` off the front of `reader`.
+pub(crate) fn take_u32(reader: &mut &[u8]) -> Option<u32> {
+    take(reader, 4).map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
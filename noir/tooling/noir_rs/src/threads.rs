@@ -0,0 +1,57 @@
+use std::{env, sync::Mutex};
+
+use noir_rs_barretenberg::HARDWARE_CONCURRENCY_VAR;
+
+use crate::errors::NoirRsError;
+
+/// Environment variable holding the number of threads the backend proves with, see
+/// [`ProveOptions::num_threads`](crate::ProveOptions::num_threads).
+pub const NUM_THREADS_VAR: &str = "NOIR_RS_NUM_THREADS";
+
+/// Whether the backend was used yet and, if so, the thread count requested when it was first used.
+static CONFIGURED: Mutex<Option<Option<usize>>> = Mutex::new(None);
+
+/// Sets the number of backend threads before its first use in the process.
+///
+/// `requested` takes precedence over [`NUM_THREADS_VAR`], which takes precedence over the
+/// `HARDWARE_CONCURRENCY` variable read by barretenberg itself. When none is set the backend uses
+/// every core.
+///
+/// Barretenberg sizes its thread pool once, so once the backend was used a different count cannot
+/// take effect and fails with `NoirRsError::NumThreadsFixed`.
+pub(crate) fn configure_num_threads(requested: Option<usize>) -> Result<(), NoirRsError> {
+    let requested = match requested {
+        Some(num_threads) => Some(num_threads),
+        None => num_threads_from_env()?,
+    };
+    if requested == Some(0) {
+        return Err(NoirRsError::InvalidNumThreads(String::from("0")));
+    }
+
+    // No state a panic could break is guarded, so a poisoned lock is still usable.
+    let mut configured = CONFIGURED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    match (*configured, requested) {
+        (None, _) => {
+            if let Some(num_threads) = requested {
+                env::set_var(HARDWARE_CONCURRENCY_VAR, num_threads.to_string());
+            }
+            *configured = Some(requested);
+            Ok(())
+        }
+        (Some(configured), Some(requested)) if configured != Some(requested) => {
+            Err(NoirRsError::NumThreadsFixed { configured, requested })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn num_threads_from_env() -> Result<Option<usize>, NoirRsError> {
+    match env::var(NUM_THREADS_VAR) {
+        Ok(value) if value.is_empty() => Ok(None),
+        Ok(value) => value.parse().map(Some).map_err(|_| NoirRsError::InvalidNumThreads(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(env::VarError::NotUnicode(value)) => {
+            Err(NoirRsError::InvalidNumThreads(value.to_string_lossy().into_owned()))
+        }
+    }
+}
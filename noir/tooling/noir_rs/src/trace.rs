@@ -0,0 +1,63 @@
+/// A span entered around a proving or verification step.
+///
+/// Every span carries the `circuit_size`, `num_points` and `elapsed_ms` fields, the latter recorded
+/// when the span is dropped. Without the `tracing` feature this is a zero-sized type and recording
+/// compiles to nothing.
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl Span {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn enter(span: tracing::Span) -> Self {
+        Span { span: span.entered(), start: std::time::Instant::now() }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    pub(crate) fn enter() -> Self {
+        Span {}
+    }
+
+    /// Records a field declared by [`span!`].
+    #[cfg(feature = "tracing")]
+    pub(crate) fn record(&self, field: &'static str, value: u64) {
+        self.span.record(field, value);
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    pub(crate) fn record(&self, _field: &'static str, _value: u64) {}
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Span {
+    fn drop(&mut self) {
+        self.span.record("elapsed_ms", self.start.elapsed().as_millis() as u64);
+    }
+}
+
+/// Enters an info level span named `$name`, see [`Span`].
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($name:literal) => {
+        $crate::trace::Span::enter(tracing::info_span!(
+            $name,
+            circuit_size = tracing::field::Empty,
+            num_points = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        ))
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! span {
+    ($name:literal) => {
+        $crate::trace::Span::enter()
+    };
+}
+
+pub(crate) use span;
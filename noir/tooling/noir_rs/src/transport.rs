@@ -0,0 +1,108 @@
+use std::io::{Read, Write};
+
+use base64::{engine::general_purpose, Engine};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::errors::NoirRsError;
+
+/// Version of the layout written by [`encode_for_transport`].
+pub const TRANSPORT_VERSION: u8 = 1;
+
+/// Largest decompressed size [`decode_from_transport`] accepts, in bytes.
+pub const DEFAULT_MAX_DECODED_SIZE: usize = 1 << 20;
+
+const TRANSPORT_MAGIC: &[u8; 4] = b"NRST";
+/// Magic, version, and the big-endian `u32` lengths of the proof and the verification key.
+const HEADER_SIZE: usize = 4 + 1 + 4 + 4;
+
+/// Packs a proof and its verification key into a single URL-safe string, for sending both from a
+/// client to a server in one field.
+///
+/// The pair is prefixed with a header holding the length of each section, gzipped and encoded as
+/// unpadded base64url.
+///
+/// # Arguments
+/// * `proof` - The proof, as returned by [`prove`](crate::prove).
+/// * `vk` - The verification key of the circuit.
+///
+/// # Returns
+/// * `String` - The encoded pair, to be read back with [`decode_from_transport`].
+pub fn encode_for_transport(proof: &[u8], vk: &[u8]) -> String {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    // Writing to a vector cannot fail.
+    encoder.write_all(TRANSPORT_MAGIC).unwrap();
+    encoder.write_all(&[TRANSPORT_VERSION]).unwrap();
+    encoder.write_all(&(proof.len() as u32).to_be_bytes()).unwrap();
+    encoder.write_all(&(vk.len() as u32).to_be_bytes()).unwrap();
+    encoder.write_all(proof).unwrap();
+    encoder.write_all(vk).unwrap();
+    general_purpose::URL_SAFE_NO_PAD.encode(encoder.finish().unwrap())
+}
+
+/// Reads a proof and verification key written by [`encode_for_transport`], rejecting pairs whose
+/// decompressed size exceeds [`DEFAULT_MAX_DECODED_SIZE`].
+///
+/// # Arguments
+/// * `encoded` - The string returned by [`encode_for_transport`].
+///
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key, or a
+///   NoirRsError if the string is malformed, truncated or too large.
+pub fn decode_from_transport(encoded: &str) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    decode_from_transport_with_limit(encoded, DEFAULT_MAX_DECODED_SIZE)
+}
+
+/// Same as [`decode_from_transport`], with a custom cap on the decompressed size.
+///
+/// # Arguments
+/// * `encoded` - The string returned by [`encode_for_transport`].
+/// * `max_decoded_size` - The largest decompressed size to accept, header included, in bytes.
+///
+/// # Returns
+/// * `Result<(Vec<u8>, Vec<u8>), NoirRsError>` - Returns the proof and the verification key, or
+///   `NoirRsError::TransportTooLarge` if they decompress to more than `max_decoded_size` bytes.
+pub fn decode_from_transport_with_limit(
+    encoded: &str,
+    max_decoded_size: usize,
+) -> Result<(Vec<u8>, Vec<u8>), NoirRsError> {
+    let compressed = general_purpose::URL_SAFE_NO_PAD.decode(encoded.trim())?;
+
+    // Decompressing at most one byte past the cap is enough to tell it was exceeded, without
+    // letting a small input inflate into an arbitrarily large buffer.
+    let mut bytes = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+        .take(max_decoded_size as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|error| NoirRsError::InvalidTransport(format!("cannot decompress: {}", error)))?;
+    if bytes.len() > max_decoded_size {
+        return Err(NoirRsError::TransportTooLarge { limit: max_decoded_size });
+    }
+
+    if bytes.len() < HEADER_SIZE || !bytes.starts_with(TRANSPORT_MAGIC) {
+        return Err(NoirRsError::InvalidTransport(format!(
+            "missing the {} header",
+            String::from_utf8_lossy(TRANSPORT_MAGIC)
+        )));
+    }
+    let version = bytes[4];
+    if version != TRANSPORT_VERSION {
+        return Err(NoirRsError::InvalidTransport(format!(
+            "unsupported version {}, expected {}",
+            version, TRANSPORT_VERSION
+        )));
+    }
+    let proof_len = u32::from_be_bytes(bytes[5..9].try_into().unwrap()) as usize;
+    let vk_len = u32::from_be_bytes(bytes[9..13].try_into().unwrap()) as usize;
+    if HEADER_SIZE as u64 + proof_len as u64 + vk_len as u64 != bytes.len() as u64 {
+        return Err(NoirRsError::InvalidTransport(format!(
+            "header announces {} proof and {} key bytes, but {} follow",
+            proof_len,
+            vk_len,
+            bytes.len() - HEADER_SIZE
+        )));
+    }
+
+    let vk = bytes.split_off(HEADER_SIZE + proof_len);
+    let proof = bytes.split_off(HEADER_SIZE);
+    Ok((proof, vk))
+}
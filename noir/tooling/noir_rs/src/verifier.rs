@@ -0,0 +1,156 @@
+use std::{fs, path::Path};
+
+use noir_rs_acir_composer::AcirComposer;
+use noir_rs_barretenberg::srs::{ignition_verifier_data, Srs, SrsError};
+
+use crate::{errors::NoirRsError, init_srs, verify_loaded, ProofFlavor, VerifyOutcome};
+
+/// The first G1 point and the G2 point of the ignition transcript, which are built in, so
+/// verifying keys generated with the transcript downloads nothing.
+pub(crate) struct IgnitionVerifierSrs {
+    g1_data: Vec<u8>,
+    g2_data: Vec<u8>,
+}
+
+impl IgnitionVerifierSrs {
+    pub(crate) fn new() -> Self {
+        let (g1_data, g2_data) = ignition_verifier_data();
+        IgnitionVerifierSrs { g1_data, g2_data }
+    }
+}
+
+impl Srs for IgnitionVerifierSrs {
+    fn load_data(&mut self, num_points: u32) -> Result<(), SrsError> {
+        if num_points > 1 {
+            return Err(SrsError::InsufficientPoints {
+                available: 1,
+                requested: num_points,
+                path: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn g1_data(&self) -> &[u8] {
+        &self.g1_data
+    }
+
+    fn g2_data(&self) -> &[u8] {
+        &self.g2_data
+    }
+
+    fn num_points(&self) -> u32 {
+        1
+    }
+
+    // The data is built in, so it is kept to be loaded again.
+    fn clear(&mut self) {}
+
+    fn max_points(&self) -> Option<u32> {
+        Some(1)
+    }
+}
+
+/// Reads the circuit size, which is the subgroup size the key was computed for, from a serialized
+/// verification key.
+pub(crate) fn vk_circuit_size(verification_key: &[u8]) -> Result<u32, NoirRsError> {
+    match verification_key.get(4..8) {
+        Some(bytes) => Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        None => Err(NoirRsError::InvalidVerificationKey(verification_key.len())),
+    }
+}
+
+/// Verifies proofs against a verification key that is loaded once.
+///
+/// Loading the key deserializes its commitments and precomputes the pairing lines of the G2
+/// point, which dominates the cost of a one-shot [`verify`](crate::verify). A `Verifier` pays it
+/// once in its constructor, [`Verifier::verify`] then only runs the verifier on the proof.
+///
+/// ```no_run
+/// # use noir_rs::Verifier;
+/// # let proofs: Vec<Vec<u8>> = Vec::new();
+/// let verifier = Verifier::from_vk_file("target/vk")?;
+/// for proof in &proofs {
+///     assert!(verifier.verify(proof)?);
+/// }
+/// # Ok::<(), noir_rs::NoirRsError>(())
+/// ```
+pub struct Verifier {
+    acir_composer: AcirComposer,
+    flavor: ProofFlavor,
+}
+
+impl Verifier {
+    /// Loads a verification key generated with the ignition transcript.
+    ///
+    /// The G2 point of the transcript is built in, so nothing is downloaded.
+    /// # Arguments
+    /// * `verification_key` - Buffer representing the verification key.
+    /// # Returns
+    /// * `Result<Verifier, NoirRsError>` - Returns the verifier or a NoirRsError.
+    pub fn from_vk(verification_key: &[u8]) -> Result<Self, NoirRsError> {
+        Self::from_vk_with_srs(verification_key, IgnitionVerifierSrs::new())
+    }
+
+    /// Loads a verification key generated with the given SRS source, of which only the G2 point
+    /// is read.
+    /// # Arguments
+    /// * `verification_key` - Buffer representing the verification key.
+    /// * `srs` - The SRS source the key was generated with.
+    /// # Returns
+    /// * `Result<Verifier, NoirRsError>` - Returns the verifier or a NoirRsError.
+    pub fn from_vk_with_srs<S: Srs>(
+        verification_key: &[u8],
+        mut srs: S,
+    ) -> Result<Self, NoirRsError> {
+        init_srs(&mut srs, 1, false)?;
+        Self::load(verification_key)
+    }
+
+    /// Reads a verification key generated with the ignition transcript from a file, such as the
+    /// `vk` written by `bb write_vk`.
+    /// # Arguments
+    /// * `path` - Path of the verification key file.
+    /// # Returns
+    /// * `Result<Verifier, NoirRsError>` - Returns the verifier or a NoirRsError.
+    pub fn from_vk_file(path: impl AsRef<Path>) -> Result<Self, NoirRsError> {
+        Self::from_vk(&fs::read(path)?)
+    }
+
+    /// Selects the flavor of the proofs to verify, [`ProofFlavor::UltraKeccak`] by default.
+    ///
+    /// # Arguments
+    /// * `flavor` - Must match the flavor the proofs were generated with.
+    pub fn flavor(mut self, flavor: ProofFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Verifies a proof against the loaded verification key.
+    /// # Arguments
+    /// * `proof` - Buffer representing the proof.
+    /// # Returns
+    /// * `Result<bool, NoirRsError>` - Returns `true` if the verification succeeds, `false` otherwise, or a NoirRsError.
+    pub fn verify(&self, proof: &[u8]) -> Result<bool, NoirRsError> {
+        Ok(self.verify_detailed(proof).is_valid())
+    }
+
+    /// Verifies a proof against the loaded verification key, with the reason it was rejected.
+    ///
+    /// The key was checked when the verifier was created, so every proof gets a verdict.
+    /// # Arguments
+    /// * `proof` - Buffer representing the proof.
+    /// # Returns
+    /// * `VerifyOutcome` - Returns whether the proof is valid.
+    pub fn verify_detailed(&self, proof: &[u8]) -> VerifyOutcome {
+        verify_loaded(&self.acir_composer, proof, self.flavor)
+    }
+
+    /// Loads the key into a composer sized from the circuit size recorded in the key.
+    fn load(verification_key: &[u8]) -> Result<Self, NoirRsError> {
+        let circuit_size = vk_circuit_size(verification_key)?;
+        let acir_composer = AcirComposer::new(&circuit_size)?;
+        acir_composer.load_verification_key(verification_key)?;
+        Ok(Verifier { acir_composer, flavor: ProofFlavor::default() })
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::{Mutex, MutexGuard};
+
+use acvm::blackbox_solver::sha256;
+
+use crate::{errors::NoirRsError, VerifyOptions, VerifyOutcome};
+
+static VERIFY_CACHE: Mutex<VerifyCache> = Mutex::new(VerifyCache::new(0));
+
+/// Least recently used cache of verdicts, keyed by the SHA-256 of the proof, the verification key
+/// and the options they were checked with.
+///
+/// Entries are ordered from the most to the least recently used.
+#[derive(Debug)]
+pub(crate) struct VerifyCache {
+    entries: Vec<([u8; 32], VerifyOutcome)>,
+    capacity: usize,
+}
+
+impl VerifyCache {
+    pub(crate) const fn new(capacity: usize) -> Self {
+        VerifyCache { entries: Vec::new(), capacity }
+    }
+
+    /// Returns the verdict stored for `hash` and marks it as most recently used.
+    pub(crate) fn get(&mut self, hash: &[u8; 32]) -> Option<VerifyOutcome> {
+        let position = self.entries.iter().position(|(key, _)| key == hash)?;
+        let entry = self.entries.remove(position);
+        let outcome = entry.1.clone();
+        self.entries.insert(0, entry);
+        Some(outcome)
+    }
+
+    /// Stores the verdict for `hash`, evicting the least recently used entry when full.
+    pub(crate) fn insert(&mut self, hash: [u8; 32], outcome: VerifyOutcome) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.retain(|(key, _)| *key != hash);
+        if self.entries.len() >= self.capacity {
+            self.entries.pop();
+        }
+        self.entries.insert(0, (hash, outcome));
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.entries.truncate(capacity);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Returns the verdict on a proof, running `verify` only if the same proof was not already
+/// checked against the same key with the same options, using the cache of
+/// [`set_verify_cache_capacity`].
+pub(crate) fn cached_verify(
+    proof: &[u8],
+    verification_key: &[u8],
+    options: &VerifyOptions,
+    verify: impl FnOnce() -> Result<VerifyOutcome, NoirRsError>,
+) -> Result<VerifyOutcome, NoirRsError> {
+    verify_with_cache(&VERIFY_CACHE, proof, verification_key, options, verify)
+}
+
+/// Returns the verdict on a proof stored in `cache`, running `verify` only on a miss.
+///
+/// Errors are not cached, so that a failure to load the SRS is retried. Neither are verdicts with
+/// `skip_srs_check`, computed with an SRS that may not be the transcript later calls verify with.
+/// # Arguments
+/// * `cache` - The verdicts to look the proof up in and store its verdict into.
+/// * `proof` - Buffer representing the proof.
+/// * `verification_key` - Buffer representing the verification key.
+/// * `options` - Options the proof is checked with.
+/// * `verify` - Checks the proof, called on a miss only.
+/// # Returns
+/// * `Result<VerifyOutcome, NoirRsError>` - Returns whether the proof is valid, or the error of
+///   `verify`.
+pub(crate) fn verify_with_cache(
+    cache: &Mutex<VerifyCache>,
+    proof: &[u8],
+    verification_key: &[u8],
+    options: &VerifyOptions,
+    verify: impl FnOnce() -> Result<VerifyOutcome, NoirRsError>,
+) -> Result<VerifyOutcome, NoirRsError> {
+    if options.skip_srs_check || lock(cache).capacity == 0 {
+        return verify();
+    }
+    let hash = verify_cache_key(proof, verification_key, options)?;
+    if let Some(outcome) = lock(cache).get(&hash) {
+        return Ok(outcome);
+    }
+
+    // The lock is not held while the proof is verified, so concurrent calls may verify the same
+    // proof twice, which is harmless.
+    let outcome = verify()?;
+    lock(cache).insert(hash, outcome.clone());
+    Ok(outcome)
+}
+
+/// Hashes the proof and the key, prefixing the proof with its length so that moving bytes from
+/// one to the other changes the hash, along with the options the verdict depends on.
+fn verify_cache_key(
+    proof: &[u8],
+    verification_key: &[u8],
+    options: &VerifyOptions,
+) -> Result<[u8; 32], NoirRsError> {
+    let mut buffer = Vec::with_capacity(10 + proof.len() + verification_key.len());
+    buffer.push(options.flavor as u8);
+    buffer.push(options.backend as u8);
+    buffer.extend_from_slice(&(proof.len() as u64).to_be_bytes());
+    buffer.extend_from_slice(proof);
+    buffer.extend_from_slice(verification_key);
+    Ok(sha256(&buffer)?)
+}
+
+/// Keeps the verdicts on the last `capacity` proofs verified by [`verify`](crate::verify) and the
+/// other verification functions taking the key, so that verifying the same proof again, such as
+/// a retried or replayed request, returns the stored verdict without running the verifier.
+///
+/// The cache is disabled by default, with a capacity of 0. Verification is deterministic, so a
+/// stored verdict is the one the verifier would return, but the cache is a performance
+/// optimization, not a security boundary: a verdict is only as trustworthy as the proof, key and
+/// SRS it was computed with. The SRS is not part of the cache key, so the cache must only be
+/// enabled when every proof is verified with the same transcript, such as the ignition one, and
+/// proofs verified with [`VerifyOptions::skip_srs_check`] always run the verifier. Each entry keeps
+/// a 32-byte hash and the verdict, so the capacity should be sized for the number of proofs
+/// expected to repeat, not the number verified. Lowering the capacity evicts the least recently
+/// used verdicts.
+/// # Arguments
+/// * `capacity` - Number of verdicts kept, 0 disables the cache.
+pub fn set_verify_cache_capacity(capacity: usize) {
+    lock(&VERIFY_CACHE).set_capacity(capacity);
+}
+
+/// Empties the cache of verdicts set up with [`set_verify_cache_capacity`], keeping its capacity.
+pub fn clear_verify_cache() {
+    lock(&VERIFY_CACHE).clear();
+}
+
+fn lock(cache: &Mutex<VerifyCache>) -> MutexGuard<'_, VerifyCache> {
+    // The cache holds no invariant a panic could break, so a poisoned lock is still usable.
+    cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,145 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use acvm::blackbox_solver::blake2s;
+
+use crate::{envelope::VerificationKey, errors::NoirRsError};
+
+/// Number of verification keys kept in memory by [`VkCache::default`].
+pub const DEFAULT_VK_CACHE_CAPACITY: usize = 32;
+
+/// Cache of verification keys, keyed by the blake2s hash of the decompressed ACIR of their
+/// circuit.
+///
+/// Computing a verification key commits to every selector of the circuit, which dominates the
+/// setup of a service handling many circuits, and the key only depends on the circuit. The cache
+/// keeps the most recently used keys in memory and, with [`VkCache::with_dir`], every key in a
+/// directory that outlives the process.
+///
+/// Keys stored on disk record the barretenberg commit they were computed with, and are computed
+/// again after an upgrade. A key also depends on the SRS it was computed with, so a cache must
+/// only be used with a single transcript, such as the ignition one.
+///
+/// A cache is consulted by [`get_verification_key`](crate::get_verification_key) and by proving
+/// with [`ProveOptions::vk_cache`](crate::ProveOptions::vk_cache).
+#[derive(Debug)]
+pub struct VkCache {
+    /// Entries ordered from the most to the least recently used.
+    entries: Mutex<Vec<([u8; 32], Vec<u8>)>>,
+    capacity: usize,
+    dir: Option<PathBuf>,
+}
+
+impl VkCache {
+    /// Creates a cache keeping at most `capacity` keys in memory.
+    /// # Arguments
+    /// * `capacity` - Number of keys kept in memory, the least recently used are evicted first.
+    pub fn new(capacity: usize) -> Self {
+        VkCache { entries: Mutex::new(Vec::new()), capacity, dir: None }
+    }
+
+    /// Also stores the keys in `dir`, one file per circuit, and reads them from there on a miss
+    /// in memory. The directory is created on the first write.
+    ///
+    /// # Arguments
+    /// * `dir` - Directory the keys are stored in.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Returns the key cached for a circuit, or computes and caches it with `compute`.
+    /// # Arguments
+    /// * `acir_buffer_uncompressed` - The decompressed ACIR of the circuit.
+    /// * `compute` - Computes the verification key of the circuit, called on a miss only.
+    /// # Returns
+    /// * `Result<Vec<u8>, NoirRsError>` - Returns the verification key, or a NoirRsError if it
+    ///   cannot be computed or written to the cache directory.
+    pub fn get_or_compute(
+        &self,
+        acir_buffer_uncompressed: &[u8],
+        compute: impl FnOnce() -> Result<Vec<u8>, NoirRsError>,
+    ) -> Result<Vec<u8>, NoirRsError> {
+        let hash = blake2s(acir_buffer_uncompressed)?;
+        if let Some(verification_key) = self.get(&hash) {
+            return Ok(verification_key);
+        }
+        let path = self.dir.as_ref().map(|dir| dir.join(format!("{}.vk", hex::encode(hash))));
+        if let Some(verification_key) = path.as_deref().and_then(read_entry) {
+            self.insert(hash, verification_key.clone());
+            return Ok(verification_key);
+        }
+
+        // The lock is not held while the key is computed, so concurrent calls may compute the
+        // key of the same circuit twice, which is harmless.
+        let verification_key = compute()?;
+        if let Some(path) = path {
+            write_entry(&path, &verification_key)
+                .map_err(|source| NoirRsError::File { path, source })?;
+        }
+        self.insert(hash, verification_key.clone());
+        Ok(verification_key)
+    }
+
+    /// Empties the cache in memory, the keys stored on disk are kept.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns the key stored for `hash` and marks it as most recently used.
+    fn get(&self, hash: &[u8; 32]) -> Option<Vec<u8>> {
+        let mut entries = self.lock();
+        let position = entries.iter().position(|(key, _)| key == hash)?;
+        let entry = entries.remove(position);
+        let verification_key = entry.1.clone();
+        entries.insert(0, entry);
+        Some(verification_key)
+    }
+
+    /// Stores the key for `hash`, evicting the least recently used entry when full.
+    fn insert(&self, hash: [u8; 32], verification_key: Vec<u8>) {
+        let mut entries = self.lock();
+        entries.retain(|(key, _)| *key != hash);
+        if entries.len() >= self.capacity {
+            entries.pop();
+        }
+        if self.capacity > 0 {
+            entries.insert(0, (hash, verification_key));
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<([u8; 32], Vec<u8>)>> {
+        // The cache holds no invariant a panic could break, so a poisoned lock is still usable.
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for VkCache {
+    fn default() -> Self {
+        VkCache::new(DEFAULT_VK_CACHE_CAPACITY)
+    }
+}
+
+/// Reads a key stored by [`write_entry`], ignoring missing files and keys stored by another
+/// format or barretenberg version.
+fn read_entry(path: &Path) -> Option<Vec<u8>> {
+    let bytes = fs::read(path).ok()?;
+    VerificationKey::from_versioned_bytes(&bytes).ok().map(|verification_key| verification_key.0)
+}
+
+/// Stores a key along with the format and barretenberg versions it was computed with.
+///
+/// The key is written to a temporary file renamed into place, so that a concurrent reader never
+/// sees a partial key.
+fn write_entry(path: &Path, verification_key: &[u8]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let bytes = VerificationKey(verification_key.to_vec()).to_versioned_bytes();
+    let temp_path = path.with_extension(format!("vk.{}.tmp", std::process::id()));
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path)
+}
@@ -0,0 +1,177 @@
+use std::{
+    cmp::Ordering,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use acir::native_types::{Witness, WitnessMap};
+use acvm::FieldElement;
+use serde_json::Value;
+
+use crate::errors::NoirRsError;
+
+/// Builds a witness map assigning `inputs` to the first witnesses, `w1` to `wN`, which is how
+/// nargo lays out the parameters of a circuit.
+///
+/// The positions are not checked against the circuit, which [`prove_with_inputs`](crate::prove_with_inputs)
+/// does.
+/// # Arguments
+/// * `inputs` - The values of the witnesses, the first one being `w1`.
+/// # Returns
+/// * `WitnessMap` - Returns the witness map.
+pub fn from_vec(inputs: Vec<FieldElement>) -> WitnessMap {
+    let mut witness_map = WitnessMap::new();
+    for (index, value) in (1..).zip(inputs) {
+        witness_map.insert(Witness(index), value);
+    }
+    witness_map
+}
+
+/// A difference between two witness maps, as listed by [`diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WitnessDiff {
+    /// The witness only has a value in the first map.
+    OnlyInFirst { witness: Witness, value: FieldElement },
+    /// The witness only has a value in the second map.
+    OnlyInSecond { witness: Witness, value: FieldElement },
+    /// The witness has a different value in each map.
+    Changed { witness: Witness, first: FieldElement, second: FieldElement },
+}
+
+impl WitnessDiff {
+    /// Returns the witness the difference is about.
+    pub fn witness(&self) -> Witness {
+        match self {
+            WitnessDiff::OnlyInFirst { witness, .. }
+            | WitnessDiff::OnlyInSecond { witness, .. }
+            | WitnessDiff::Changed { witness, .. } => *witness,
+        }
+    }
+}
+
+/// Lists the witnesses whose values differ between two maps, such as the solved witnesses of two
+/// executions of the same circuit.
+/// # Arguments
+/// * `first` - The first witness map.
+/// * `second` - The second witness map.
+/// # Returns
+/// * `Vec<WitnessDiff>` - Returns the differences, in increasing witness order.
+pub fn diff(first: &WitnessMap, second: &WitnessMap) -> Vec<WitnessDiff> {
+    let mut first = first.clone().into_iter().peekable();
+    let mut second = second.clone().into_iter().peekable();
+    let mut diffs = Vec::new();
+    loop {
+        let order = match (first.peek(), second.peek()) {
+            (Some((first_witness, _)), Some((second_witness, _))) => {
+                first_witness.cmp(second_witness)
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => return diffs,
+        };
+        match order {
+            Ordering::Less => {
+                let (witness, value) = first.next().unwrap();
+                diffs.push(WitnessDiff::OnlyInFirst { witness, value });
+            }
+            Ordering::Greater => {
+                let (witness, value) = second.next().unwrap();
+                diffs.push(WitnessDiff::OnlyInSecond { witness, value });
+            }
+            Ordering::Equal => {
+                let ((witness, first), (_, second)) =
+                    (first.next().unwrap(), second.next().unwrap());
+                if first != second {
+                    diffs.push(WitnessDiff::Changed { witness, first, second });
+                }
+            }
+        }
+    }
+}
+
+/// Writes a witness map as one `index: 0x<value>` line per witness, in increasing witness order
+/// and with values padded to 64 hex digits, so that the dumps of two maps can be compared with
+/// `diff`.
+///
+/// At most `limit` witnesses are written, followed by a line counting the omitted ones.
+/// # Arguments
+/// * `witness_map` - The witness map to write.
+/// * `writer` - The destination of the lines.
+/// * `limit` - The maximum number of witnesses to write.
+/// # Returns
+/// * `io::Result<()>` - Returns the error of the writer, if any.
+pub fn dump(witness_map: &WitnessMap, writer: &mut impl Write, limit: usize) -> io::Result<()> {
+    let mut entries = witness_map.clone().into_iter();
+    for (witness, value) in entries.by_ref().take(limit) {
+        writeln!(writer, "{}: 0x{}", witness.witness_index(), value.to_hex())?;
+    }
+    let omitted = entries.count();
+    if omitted > 0 {
+        writeln!(writer, "... {} more witnesses", omitted)?;
+    }
+    Ok(())
+}
+
+/// Serializes a witness map as a JSON object mapping witness indices to field elements, such as
+/// `{"1":"0x00…00","2":"0x00…01"}`.
+///
+/// Witnesses are written in increasing order and values as `0x` followed by 64 lowercase hex
+/// digits, so that a witness map always has the same serialization.
+/// # Arguments
+/// * `witness_map` - The witness map to serialize.
+/// # Returns
+/// * `String` - The JSON object, read back by [`witness_from_json`].
+pub fn witness_to_json(witness_map: &WitnessMap) -> String {
+    let mut json = String::from("{");
+    for (position, (witness, value)) in witness_map.clone().into_iter().enumerate() {
+        if position > 0 {
+            json.push(',');
+        }
+        write!(json, "\"{}\":\"0x{}\"", witness.witness_index(), value.to_hex()).unwrap();
+    }
+    json.push('}');
+    json
+}
+
+/// Reads a witness map from a JSON object mapping witness indices to field elements, such as
+/// `{"1": "0x00", "2": "1"}`.
+///
+/// Values are hexadecimal strings prefixed with `0x`, decimal strings or non-negative integers, so
+/// the output of [`witness_to_json`] is read back as well as witnesses written by hand.
+/// # Arguments
+/// * `json` - The JSON object to read.
+/// # Returns
+/// * `Result<WitnessMap, NoirRsError>` - Returns the witness map, or a NoirRsError if `json` is not
+///   an object of witness values.
+pub fn witness_from_json(json: &str) -> Result<WitnessMap, NoirRsError> {
+    let json: Value = serde_json::from_str(json)
+        .map_err(|error| NoirRsError::InvalidWitnessJson(error.to_string()))?;
+    let entries = match json {
+        Value::Object(entries) => entries,
+        _ => {
+            return Err(NoirRsError::InvalidWitnessJson(String::from(
+                "expected an object of witness values",
+            )))
+        }
+    };
+
+    let mut witness_map = WitnessMap::new();
+    for (index, value) in entries {
+        let index: u32 = index.parse().map_err(|_| {
+            NoirRsError::InvalidWitnessJson(format!("{:?} is not a witness index", index))
+        })?;
+        let field = match &value {
+            Value::String(value) => FieldElement::try_from_str(value),
+            Value::Number(value) => value.as_u64().map(|value| FieldElement::from(value as u128)),
+            _ => None,
+        };
+        let field = field.ok_or_else(|| {
+            NoirRsError::InvalidWitnessJson(format!(
+                "{} is not a field element for witness {}",
+                value, index
+            ))
+        })?;
+        witness_map.insert(Witness(index), field);
+    }
+    Ok(witness_map)
+}
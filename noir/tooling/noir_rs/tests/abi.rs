@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::PathBuf;
+
+use noir_rs::{prelude::*, witness_from_toml};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/struct_inputs").join(name)
+}
+
+fn struct_inputs_abi() -> Abi {
+    serde_json::from_str(&fs::read_to_string(fixture("abi.json")).unwrap()).unwrap()
+}
+
+#[test]
+fn witness_from_toml_matches_witness_file() {
+    let abi = struct_inputs_abi();
+    let toml_str = fs::read_to_string(fixture("Prover.toml")).unwrap();
+
+    let witness = witness_from_toml(&abi, &toml_str).unwrap();
+    // `delta = "-3"` is an i8, encoded as its two's complement.
+    assert_eq!(witness.get(&Witness(14)), Some(&FieldElement::from(253u128)));
+    let witness_file: Vec<u8> = witness.try_into().unwrap();
+    assert_eq!(witness_file, fs::read(fixture("witness.gz")).unwrap());
+}
+
+#[test]
+fn witness_from_toml_names_failing_key() {
+    let abi = struct_inputs_abi();
+    let toml_str = fs::read_to_string(fixture("Prover.toml")).unwrap();
+
+    let invalid = toml_str.replace("\"0x2a\"", "\"0xzz\"");
+    assert!(matches!(
+        witness_from_toml(&abi, &invalid),
+        Err(NoirRsError::InvalidTomlValue { key, .. }) if key == "owner"
+    ));
+
+    let missing = toml_str.replace("active = false", "");
+    let error = witness_from_toml(&abi, &missing).unwrap_err();
+    assert!(error.to_string().contains("accounts.active"), "{}", error);
+
+    assert!(matches!(witness_from_toml(&abi, "owner = "), Err(NoirRsError::InvalidToml(_))));
+}
@@ -0,0 +1,53 @@
+//! Writes a proof generated with the dev SRS to files in the nargo layout and reads it back.
+
+use std::fs;
+
+use noir_rs::{
+    prelude::*, prove_with_srs, read_proof_file, read_vk_file, verify_with_srs, witness,
+    write_proof_file, write_vk_file,
+};
+use tempfile::TempDir;
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+#[test]
+fn proof_and_vk_files_round_trip() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+    let (proof, vk) =
+        prove_with_srs(&bytecode, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+
+    let dir = TempDir::new().unwrap();
+    let proof_path = dir.path().join("main.proof");
+    let vk_path = dir.path().join("vk");
+    write_proof_file(&proof_path, &proof).unwrap();
+    write_vk_file(&vk_path, &vk).unwrap();
+
+    // nargo writes the proof as bare lowercase hex and the key as raw bytes.
+    let proof_file = fs::read_to_string(&proof_path).unwrap();
+    assert_eq!(proof_file.len(), proof.len() * 2);
+    assert!(proof_file.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f')));
+    assert_eq!(fs::read(&vk_path).unwrap(), vk);
+
+    let read_proof = read_proof_file(&proof_path).unwrap();
+    let read_vk = read_vk_file(&vk_path).unwrap();
+    assert_eq!(read_proof, proof);
+    assert_eq!(read_vk, vk);
+    assert!(verify_with_srs(bytecode, read_proof, read_vk, DevSrs::new(DEV_SRS_POINTS)).unwrap());
+
+    fs::write(&proof_path, format!("0x{}\n", proof_file)).unwrap();
+    assert_eq!(read_proof_file(&proof_path).unwrap(), proof);
+
+    fs::write(&proof_path, "0xzz").unwrap();
+    assert!(matches!(
+        read_proof_file(&proof_path),
+        Err(NoirRsError::InvalidProofFile { path, .. }) if path == proof_path
+    ));
+    let missing = dir.path().join("missing");
+    assert!(matches!(
+        read_vk_file(&missing),
+        Err(NoirRsError::File { path, .. }) if path == missing
+    ));
+}
@@ -0,0 +1,53 @@
+//! A circuit calling an unconstrained function, whose Brillig bytecode is executed by the ACVM
+//! before its result is constrained and proven.
+
+use std::fs;
+
+use noir_rs::{
+    execute, extract_public_inputs, prelude::*, prove_from_witness, prove_with_srs, verify_with_srs,
+};
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    fs::read_to_string(path).unwrap().trim().to_string()
+}
+
+fn initial_witness(numerator: u128, denominator: u128) -> WitnessMap {
+    let mut initial_witness = WitnessMap::new();
+    initial_witness.insert(Witness(1), FieldElement::from(numerator));
+    initial_witness.insert(Witness(2), FieldElement::from(denominator));
+    initial_witness
+}
+
+#[test]
+fn unconstrained_division() {
+    // `w3` is computed as `w1 / w2` by a Brillig opcode, which also prints it through a foreign
+    // call, and returned after constraining `w3 * w2 = w1`.
+    let bytecode = read_fixture("brillig.b64");
+    let initial_witness = initial_witness(12, 4);
+
+    let solved_witness = execute(&bytecode, &initial_witness, &BlackboxSolver::new()).unwrap();
+    assert_eq!(solved_witness.get(&Witness(3)), Some(&FieldElement::from(3u128)));
+
+    let (proof, vk) =
+        prove_with_srs(&bytecode, &initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+    assert_eq!(extract_public_inputs(&proof, &vk).unwrap(), vec![FieldElement::from(3u128)]);
+    let verdict =
+        verify_with_srs(bytecode.clone(), proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+    assert!(verdict);
+
+    // Proving from the witness solved above gives a proof of the same statement.
+    let (proof, vk) =
+        prove_from_witness(&bytecode, &solved_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+    assert!(verify_with_srs(bytecode, proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap());
+}
+
+#[test]
+fn unconstrained_result_is_still_constrained() {
+    // Dividing by zero makes the unconstrained function return 0, which the constraint rejects.
+    let bytecode = read_fixture("brillig.b64");
+    let error = execute(&bytecode, &initial_witness(12, 0), &BlackboxSolver::new()).unwrap_err();
+    assert!(matches!(error, NoirRsError::Execution(_)), "{error:?}");
+}
@@ -0,0 +1,314 @@
+//! Drives the `noir_rs` binary against the circuit in `tests/fixtures`, proving with the dev SRS.
+
+use std::{fs, net::TcpListener, path::Path, process::Command};
+
+use assert_cmd::prelude::*;
+use noir_rs::{prelude::*, ACIR_VERSION};
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+/// Writes the dev SRS as a flat CRS directory.
+fn write_dev_srs(dir: &Path) {
+    let mut dev_srs = DevSrs::new(DEV_SRS_POINTS);
+    dev_srs.load_data(DEV_SRS_POINTS).unwrap();
+    fs::write(dir.join("bn254_g1.dat"), dev_srs.g1_data()).unwrap();
+    fs::write(dir.join("bn254_g2.dat"), dev_srs.g2_data()).unwrap();
+}
+
+/// Proves the fixture circuit and returns the directory holding `proof`, `vk` and the dev SRS in
+/// `srs`.
+fn prove_fixture() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let srs_dir = dir.path().join("srs");
+    fs::create_dir(&srs_dir).unwrap();
+    write_dev_srs(&srs_dir);
+
+    Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("prove")
+        .arg("--bytecode")
+        .arg(Path::new(FIXTURES).join("circuit.b64"))
+        .arg("--witness")
+        .arg(Path::new(FIXTURES).join("witness.json"))
+        .arg("--srs")
+        .arg(&srs_dir)
+        .arg("--out")
+        .arg(dir.path().join("proof"))
+        .arg("--vk-out")
+        .arg(dir.path().join("vk"))
+        .assert()
+        .success();
+    dir
+}
+
+fn verify(dir: &Path) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("verify")
+        .arg("--proof")
+        .arg(dir.join("proof"))
+        .arg("--vk")
+        .arg(dir.join("vk"))
+        .arg("--srs-path")
+        .arg(dir.join("srs"))
+        .assert()
+}
+
+#[test]
+fn prove_then_verify() {
+    let dir = prove_fixture();
+    verify(dir.path()).code(0).stdout(predicate::str::contains("Proof is valid"));
+
+    // Flipping a byte of the public input makes the proof invalid.
+    let proof_path = dir.path().join("proof");
+    let mut proof = fs::read(&proof_path).unwrap();
+    proof[31] ^= 1;
+    fs::write(&proof_path, proof).unwrap();
+    verify(dir.path()).code(1).stdout(predicate::str::contains("Proof is invalid"));
+}
+
+#[test]
+fn info_prints_circuit_sizes() {
+    Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("info")
+        .arg("--bytecode")
+        .arg(Path::new(FIXTURES).join("circuit.b64"))
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("exact: ")
+                .and(predicate::str::contains("subgroup: "))
+                .and(predicate::str::contains(format!("acir version: {}", ACIR_VERSION))),
+        );
+}
+
+#[test]
+fn info_prints_proof_layout() {
+    let dir = prove_fixture();
+    let proof_len = fs::read(dir.path().join("proof")).unwrap().len();
+    let info = || {
+        let mut command = Command::cargo_bin("noir_rs").unwrap();
+        command.arg("info").arg("--vk").arg(dir.path().join("vk"));
+        command
+    };
+    info().assert().success().stdout(
+        predicate::str::contains("num public inputs: 1\n")
+            .and(predicate::str::contains(format!("proof bytes: {}\n", proof_len)))
+            .and(predicate::str::contains("exact: ").not()),
+    );
+
+    let output = info().arg("--json").output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["proof_bytes_without_inputs"], 2144);
+    assert_eq!(json["proof_bytes"], proof_len);
+}
+
+#[test]
+fn gates_prints_counts() {
+    let gates = || {
+        let mut command = Command::cargo_bin("noir_rs").unwrap();
+        command.arg("gates").arg("--bytecode").arg(Path::new(FIXTURES).join("circuit.b64"));
+        command
+    };
+    gates().assert().success().stdout(
+        predicate::str::contains("opcodes: 5\n").and(predicate::str::contains("subgroup: ")),
+    );
+
+    let output = gates().arg("--json").output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["opcodes"], 5);
+    let subgroup_size = json["subgroup_size"].as_u64().unwrap();
+    assert!(subgroup_size >= json["total_gates"].as_u64().unwrap());
+    assert_eq!(1 << json["log2_subgroup_size"].as_u64().unwrap(), subgroup_size);
+}
+
+#[test]
+fn json_output() {
+    let dir = prove_fixture();
+    let parse = |output: &std::process::Output| -> serde_json::Value {
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+    let output = Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("--json")
+        .arg("verify")
+        .arg("--proof")
+        .arg(dir.path().join("proof"))
+        .arg("--vk")
+        .arg(dir.path().join("vk"))
+        .arg("--srs")
+        .arg(dir.path().join("srs"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let json = parse(&output);
+    assert_eq!(json["valid"], true);
+    assert!(json["reason"].is_null());
+    assert!(json["elapsed_ms"].is_u64());
+
+    // Errors are reported on stdout with a code naming their kind.
+    let gates_error = |bytecode: &Path| {
+        Command::cargo_bin("noir_rs")
+            .unwrap()
+            .arg("gates")
+            .arg("--bytecode")
+            .arg(bytecode)
+            .arg("--json")
+            .output()
+            .unwrap()
+    };
+    let output = gates_error(&dir.path().join("missing.b64"));
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(parse(&output)["error"]["code"], "read_file");
+
+    let bytecode_path = dir.path().join("invalid.b64");
+    fs::write(&bytecode_path, "H4sI*").unwrap();
+    let output = gates_error(&bytecode_path);
+    assert_eq!(output.status.code(), Some(2));
+    let json = parse(&output);
+    assert_eq!(json["error"]["code"], "invalid_bytecode_character");
+    assert!(json["error"]["message"].as_str().unwrap().contains("'*'"));
+}
+
+#[test]
+fn errors_exit_with_code_2() {
+    let dir = TempDir::new().unwrap();
+    let witness_path = dir.path().join("witness.json");
+    fs::write(&witness_path, r#"{"1": "zero"}"#).unwrap();
+
+    Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("prove")
+        .arg("--bytecode")
+        .arg(Path::new(FIXTURES).join("circuit.b64"))
+        .arg("--witness")
+        .arg(&witness_path)
+        .arg("--out")
+        .arg(dir.path().join("proof"))
+        .arg("--vk-out")
+        .arg(dir.path().join("vk"))
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Invalid witness file"));
+
+    Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("verify")
+        .arg("--proof")
+        .arg(dir.path().join("missing_proof"))
+        .arg("--vk")
+        .arg(dir.path().join("missing_vk"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Failed to read"));
+}
+
+#[test]
+fn inputs_read_from_stdin() {
+    let dir = prove_fixture();
+    let bytecode = fs::read_to_string(Path::new(FIXTURES).join("circuit.b64")).unwrap();
+    let prove = |bytecode: &Path, witness: &Path| {
+        // Unlike the standard `Command`, this one can write to the standard input of the binary.
+        let mut command = assert_cmd::Command::cargo_bin("noir_rs").unwrap();
+        command
+            .arg("prove")
+            .arg("--bytecode")
+            .arg(bytecode)
+            .arg("--witness")
+            .arg(witness)
+            .arg("--srs")
+            .arg(dir.path().join("srs"))
+            .arg("--out")
+            .arg(dir.path().join("proof"))
+            .arg("--vk-out")
+            .arg(dir.path().join("vk"));
+        command
+    };
+    let witness_path = Path::new(FIXTURES).join("witness.json");
+
+    prove(Path::new("-"), &witness_path).write_stdin(bytecode).assert().success();
+    verify(dir.path()).success();
+    let witness = fs::read(&witness_path).unwrap();
+    prove(&Path::new(FIXTURES).join("circuit.b64"), Path::new("-"))
+        .write_stdin(witness)
+        .assert()
+        .success();
+    verify(dir.path()).success();
+
+    prove(Path::new("-"), &witness_path)
+        .write_stdin(" \n")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("stdin is empty"));
+    prove(Path::new("-"), Path::new("-"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("can only be read once"));
+}
+
+#[test]
+fn witness_from_prover_toml() {
+    let dir = TempDir::new().unwrap();
+    let project = Path::new(FIXTURES).join("struct_inputs");
+    // The ABI as nargo writes it in the artifact, next to the bytecode.
+    let abi: serde_json::Value =
+        serde_json::from_slice(&fs::read(project.join("abi.json")).unwrap()).unwrap();
+    let artifact_path = dir.path().join("struct_inputs.json");
+    fs::write(&artifact_path, serde_json::json!({ "abi": abi, "bytecode": "" }).to_string())
+        .unwrap();
+    let witness = |prover_toml: &Path| {
+        let mut command = Command::cargo_bin("noir_rs").unwrap();
+        command
+            .arg("witness")
+            .arg("--artifact")
+            .arg(&artifact_path)
+            .arg("--prover-toml")
+            .arg(prover_toml)
+            .arg("--out")
+            .arg(dir.path().join("witness.gz"));
+        command
+    };
+
+    witness(&project.join("Prover.toml")).assert().success();
+    assert_eq!(
+        fs::read(dir.path().join("witness.gz")).unwrap(),
+        fs::read(project.join("witness.gz")).unwrap()
+    );
+
+    let toml_str = fs::read_to_string(project.join("Prover.toml")).unwrap();
+    let invalid_path = dir.path().join("Invalid.toml");
+    fs::write(&invalid_path, toml_str.replace("\"0x2a\"", "\"0xzz\"")).unwrap();
+    witness(&invalid_path).assert().code(2).stderr(predicate::str::contains("owner"));
+    fs::write(&invalid_path, toml_str.replace("active = false", "")).unwrap();
+    witness(&invalid_path).assert().code(2).stderr(predicate::str::contains("accounts.active"));
+}
+
+#[test]
+fn srs_url_is_downloaded() {
+    let dir = prove_fixture();
+    // Nothing listens on a port freed right after binding it.
+    let closed_url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/transcript00.dat", listener.local_addr().unwrap())
+    };
+
+    Command::cargo_bin("noir_rs")
+        .unwrap()
+        .arg("verify")
+        .arg("--proof")
+        .arg(dir.path().join("proof"))
+        .arg("--vk")
+        .arg(dir.path().join("vk"))
+        .arg("--srs")
+        .arg(&closed_url)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(closed_url.as_str()));
+}
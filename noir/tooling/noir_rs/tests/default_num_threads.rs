@@ -0,0 +1,43 @@
+//! Proves with the thread count barretenberg picks itself, in a test binary of its own as the
+//! first proof of a process sizes the thread pool for the following ones.
+
+use std::{env, fs};
+
+use noir_rs::{prelude::*, verify_with_srs, witness, NUM_THREADS_VAR};
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+/// Read by barretenberg when sizing its thread pool.
+const HARDWARE_CONCURRENCY_VAR: &str = "HARDWARE_CONCURRENCY";
+
+#[test]
+fn prove_with_default_num_threads() {
+    // Neither variable may leak in from the environment running the tests.
+    env::remove_var(HARDWARE_CONCURRENCY_VAR);
+    env::remove_var(NUM_THREADS_VAR);
+
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let bytecode = bytecode.trim();
+    let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+    for _ in 0..2 {
+        let (proof, vk) = ProveOptions::new()
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .prove(bytecode, &initial_witness)
+            .unwrap();
+        assert!(
+            verify_with_srs(bytecode.to_string(), proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap()
+        );
+        // Without a requested count the backend is left to use every core.
+        assert!(env::var_os(HARDWARE_CONCURRENCY_VAR).is_none());
+    }
+
+    assert!(matches!(
+        ProveOptions::new()
+            .num_threads(1)
+            .srs(DevSrs::new(DEV_SRS_POINTS))
+            .prove(bytecode, &initial_witness),
+        Err(NoirRsError::NumThreadsFixed { configured: None, requested: 1 })
+    ));
+}
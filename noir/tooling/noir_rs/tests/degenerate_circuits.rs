@@ -0,0 +1,58 @@
+//! Circuits at the edges of what the pipeline accepts: one without any input, whose witnesses are
+//! all solved from constants, and one whose inputs are all private.
+
+use std::fs;
+
+use noir_rs::{
+    execute, extract_public_inputs, num_public_inputs, prelude::*, proof_layout, prove_with_srs,
+    split_proof, verify_with_srs,
+};
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+fn read_fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    fs::read_to_string(path).unwrap().trim().to_string()
+}
+
+/// Proves and verifies a circuit, checking that its proof carries no public input.
+fn prove_and_verify_without_public_inputs(bytecode: &str, initial_witness: &WitnessMap) {
+    let (proof, vk) =
+        prove_with_srs(bytecode, initial_witness, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+    assert_eq!(num_public_inputs(&vk).unwrap(), 0);
+    assert_eq!(extract_public_inputs(&proof, &vk).unwrap(), Vec::new());
+    assert_eq!(split_proof(&proof, 0), (Vec::new(), proof.clone()));
+    let layout = proof_layout(&vk).unwrap();
+    assert_eq!((layout.num_public_inputs, layout.total_bytes), (0, 2144));
+    assert_eq!(proof.len(), layout.total_bytes as usize);
+
+    let verdict =
+        verify_with_srs(bytecode.to_string(), proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap();
+    assert!(verdict);
+}
+
+#[test]
+fn constant_circuit() {
+    // A single opcode constraining `w1 = 5`, solved from an empty initial witness.
+    let bytecode = read_fixture("constant.b64");
+    let solved_witness = execute(&bytecode, &WitnessMap::new(), &BlackboxSolver::new()).unwrap();
+    assert_eq!(solved_witness.get(&Witness(1)), Some(&FieldElement::from(5u128)));
+
+    prove_and_verify_without_public_inputs(&bytecode, &WitnessMap::new());
+    let output = ProveOptions::new()
+        .srs(DevSrs::new(DEV_SRS_POINTS))
+        .prove_detailed(&bytecode, &WitnessMap::new())
+        .unwrap();
+    assert!(output.public_inputs.is_empty());
+}
+
+#[test]
+fn circuit_without_public_inputs() {
+    // Constrains `w3 = w1 * w2` with private `w1` and `w2`, and returns nothing.
+    let bytecode = read_fixture("no_public_inputs.b64");
+    let mut initial_witness = WitnessMap::new();
+    initial_witness.insert(Witness(1), FieldElement::from(3u128));
+    initial_witness.insert(Witness(2), FieldElement::from(4u128));
+
+    prove_and_verify_without_public_inputs(&bytecode, &initial_witness);
+}
@@ -0,0 +1,66 @@
+//! A prove request received as JSON by a service, proven and answered as JSON.
+
+use std::fs;
+
+use noir_rs::{dto::SrsDto, prelude::*, verify_with_srs, ProveRequestDto};
+use serde_json::Value;
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+fn prove_request() -> ProveRequestDto {
+    let path = format!("{}/tests/fixtures/prove_request.json", env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn parses_hex_and_decimal_inputs() {
+    let request = prove_request();
+    assert_eq!(request.srs, SrsDto::Default);
+
+    let initial_witness = request.initial_witness().unwrap();
+    assert_eq!(initial_witness.get(&Witness(1)), Some(&FieldElement::zero()));
+    assert_eq!(initial_witness.get(&Witness(2)), Some(&FieldElement::one()));
+}
+
+#[test]
+fn names_invalid_input() {
+    let mut request = prove_request();
+    request.inputs.insert(2, String::from("0xnot_hex"));
+
+    let error = request.initial_witness().unwrap_err();
+    assert!(matches!(
+        &error,
+        NoirRsError::InvalidInputValue { witness: 2, value } if value == "0xnot_hex"
+    ));
+    assert_eq!(error.code(), "invalid_input_value");
+}
+
+#[test]
+fn srs_defaults_when_omitted() {
+    let request: ProveRequestDto =
+        serde_json::from_str(r#"{"bytecode": "", "inputs": {}}"#).unwrap();
+    assert_eq!(request.srs, SrsDto::Default);
+
+    let request: ProveRequestDto = serde_json::from_str(
+        r#"{"bytecode": "", "inputs": {}, "srs": {"kind": "url", "url": "http://localhost/t.dat"}}"#,
+    )
+    .unwrap();
+    assert_eq!(request.srs, SrsDto::Url { url: String::from("http://localhost/t.dat") });
+}
+
+#[test]
+fn prove_request_round_trip() {
+    let request = prove_request();
+    let response = request.prove_with(request.options().srs(DevSrs::new(DEV_SRS_POINTS))).unwrap();
+    assert_eq!(response.public_inputs, vec![format!("0x{}", FieldElement::one().to_hex())]);
+
+    // Services depend on these names, renaming a field is a breaking change.
+    let json: Value = serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+    let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["proof_hex", "public_inputs", "vk_hex"]);
+
+    let proof = hex::decode(&response.proof_hex).unwrap();
+    let vk = hex::decode(&response.vk_hex).unwrap();
+    assert!(verify_with_srs(request.bytecode, proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap());
+}
@@ -0,0 +1,30 @@
+//! Proves with the SRS downloaded from the transcript URL set in the environment, which is shared
+//! by the whole process, so it runs in its own test binary against a local mock of the transcript
+//! host.
+
+use std::{env, fs};
+
+use noir_rs::{prelude::*, prove, verify, witness, SRS_URL_VAR};
+use noir_rs_barretenberg::srs::mock::{MockSrsServer, MockTranscript};
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+#[test]
+fn prove_verify_net_srs() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let bytecode = bytecode.trim();
+    let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+    let mut dev_srs = DevSrs::new(DEV_SRS_POINTS);
+    dev_srs.load_data(DEV_SRS_POINTS).unwrap();
+    let (url, server) =
+        MockSrsServer::serve(MockTranscript::ignition(dev_srs.g1_data(), dev_srs.g2_data()));
+    env::set_var(SRS_URL_VAR, &url);
+
+    let (proof, vk) = prove(bytecode, &initial_witness).unwrap();
+    let verdict = verify(String::from(bytecode), proof, vk).unwrap();
+    assert!(verdict);
+    assert!(!server.requests().is_empty());
+}
@@ -0,0 +1,38 @@
+//! The backend sizes its thread pool once per process, so these checks run in their own test
+//! binary, in a single test, to control which proof comes first. The default thread count is
+//! checked in `default_num_threads.rs`.
+
+use std::fs;
+
+use noir_rs::{prelude::*, verify_with_srs, witness};
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+#[test]
+fn prove_with_num_threads() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let bytecode = bytecode.trim();
+    let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+    let prove = |options: ProveOptions| {
+        options.srs(DevSrs::new(DEV_SRS_POINTS)).prove(bytecode, &initial_witness)
+    };
+
+    let (proof, vk) = prove(ProveOptions::new().num_threads(1)).unwrap();
+    assert!(verify_with_srs(bytecode.to_string(), proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap());
+
+    // Proofs that do not ask for a count keep the one of the first proof.
+    let (proof, vk) = prove(ProveOptions::new()).unwrap();
+    assert!(verify_with_srs(bytecode.to_string(), proof, vk, DevSrs::new(DEV_SRS_POINTS)).unwrap());
+
+    assert!(matches!(
+        prove(ProveOptions::new().num_threads(2)),
+        Err(NoirRsError::NumThreadsFixed { configured: Some(1), requested: 2 })
+    ));
+    assert!(matches!(
+        prove(ProveOptions::new().num_threads(0)),
+        Err(NoirRsError::InvalidNumThreads(_))
+    ));
+}
@@ -0,0 +1,58 @@
+//! Signatures produced in Rust satisfy the `schnorr_verify` black box function of a circuit.
+
+use std::fs;
+
+use noir_rs::{
+    num_points_for_circuit,
+    prelude::*,
+    proof_layout, prove_with_srs,
+    schnorr::{self, SchnorrSignature},
+    verify_with_srs,
+};
+
+const MESSAGE: &[u8; 8] = b"noir_rs!";
+
+/// Witnesses of `fixtures/schnorr.b64`, which verifies a signature of an 8-byte message: the public
+/// key is `w1` and `w2`, the signature `w3` to `w66` and the message `w67` to `w74`, a byte per
+/// witness.
+fn schnorr_witness(
+    public_key: (FieldElement, FieldElement),
+    signature: &SchnorrSignature,
+    message: &[u8; 8],
+) -> WitnessMap {
+    let mut witness = WitnessMap::new();
+    witness.insert(Witness(1), public_key.0);
+    witness.insert(Witness(2), public_key.1);
+    let bytes = signature.to_bytes().into_iter().chain(message.iter().copied());
+    for (index, byte) in (3..).zip(bytes) {
+        witness.insert(Witness(index), FieldElement::from(byte as u128));
+    }
+    witness
+}
+
+#[test]
+fn rust_signature_satisfies_circuit() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/schnorr.b64"))
+            .unwrap();
+    let bytecode = bytecode.trim();
+
+    let private_key = FieldElement::from(0x1234_5678_9abc_def0u128);
+    let public_key = schnorr::derive_public_key(private_key).unwrap();
+    let signature = schnorr::sign(MESSAGE, private_key).unwrap();
+    assert_eq!(SchnorrSignature::from_bytes(&signature.to_bytes()), signature);
+    assert!(schnorr::verify(&public_key.0, &public_key.1, &signature, MESSAGE).unwrap());
+    assert!(!schnorr::verify(&public_key.0, &public_key.1, &signature, b"noir_rs?").unwrap());
+
+    let num_points = num_points_for_circuit(bytecode).unwrap();
+    let initial_witness = schnorr_witness(public_key, &signature, MESSAGE);
+    let (proof, vk) = prove_with_srs(bytecode, &initial_witness, DevSrs::new(num_points)).unwrap();
+    assert_eq!(proof.len(), proof_layout(&vk).unwrap().total_bytes as usize);
+    let verdict =
+        verify_with_srs(bytecode.to_string(), proof, vk, DevSrs::new(num_points)).unwrap();
+    assert!(verdict);
+
+    // The circuit asserts the signature is valid, so executing it with another message fails.
+    let tampered_witness = schnorr_witness(public_key, &signature, b"noir_rs?");
+    assert!(prove_with_srs(bytecode, &tampered_witness, DevSrs::new(num_points)).is_err());
+}
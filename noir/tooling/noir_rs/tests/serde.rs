@@ -0,0 +1,53 @@
+//! The circuit analysis reports, stored as JSON to track the size of circuits across commits.
+
+use std::fs;
+
+use noir_rs::{
+    circuit_stats, estimate_memory, gate_report, CircuitStats, GateReport, MemoryEstimate,
+};
+use serde_json::json;
+
+fn bytecode() -> String {
+    fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn reports_round_trip_through_json() {
+    let stats = circuit_stats(&bytecode()).unwrap();
+    let json = serde_json::to_string(&stats).unwrap();
+    assert_eq!(serde_json::from_str::<CircuitStats>(&json).unwrap(), stats);
+
+    let report = gate_report(&bytecode()).unwrap();
+    let json = serde_json::to_string(&report).unwrap();
+    assert_eq!(serde_json::from_str::<GateReport>(&json).unwrap(), report);
+
+    let estimate = estimate_memory(1 << 10);
+    let json = serde_json::to_string(&estimate).unwrap();
+    assert_eq!(serde_json::from_str::<MemoryEstimate>(&json).unwrap(), estimate);
+}
+
+#[test]
+fn circuit_stats_field_names() {
+    let stats: CircuitStats = serde_json::from_value(json!({
+        "opcodes": {"Arithmetic": 2, "MemoryInit": 1},
+        "black_box": {"range": 3},
+        "total_witnesses": 5,
+        "memory_blocks": {"0": 4},
+    }))
+    .unwrap();
+    assert_eq!(stats.total_opcodes(), 3);
+    assert_eq!(stats.range_constraints(), 3);
+    assert_eq!(stats.largest_memory_block(), Some((0, 4)));
+
+    let error = serde_json::from_value::<CircuitStats>(json!({
+        "opcodes": {"Unknown": 1},
+        "black_box": {},
+        "total_witnesses": 0,
+        "memory_blocks": {},
+    }))
+    .unwrap_err();
+    assert!(error.to_string().contains("unknown variant `Unknown`"), "{}", error);
+}
@@ -0,0 +1,35 @@
+//! The SRS handed to the backend is shared by the whole process, so initializing it with too few
+//! points runs in its own test binary.
+
+use std::{fs, io::Read};
+
+use base64::{engine::general_purpose, Engine};
+use flate2::bufread::GzDecoder;
+use noir_rs::{get_circuit_sizes, prelude::*};
+use noir_rs_acir_composer::{AcirComposer, AcirComposerError};
+use noir_rs_barretenberg::{srs::srs_init, BackendError};
+
+#[test]
+fn too_small_srs_is_an_error() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let acir_buffer = general_purpose::STANDARD.decode(bytecode.trim()).unwrap();
+    let mut acir_buffer_uncompressed = Vec::new();
+    GzDecoder::new(acir_buffer.as_slice()).read_to_end(&mut acir_buffer_uncompressed).unwrap();
+
+    let subgroup_size = get_circuit_sizes(&acir_buffer_uncompressed).unwrap().subgroup;
+    let mut srs = DevSrs::new(4);
+    srs.load_data(4).unwrap();
+    assert!(subgroup_size + 1 > srs.num_points());
+    srs_init(srs.g1_data(), srs.num_points(), srs.g2_data()).unwrap();
+
+    let acir_composer = AcirComposer::new(&subgroup_size).unwrap();
+    let error = acir_composer.init_proving_key(&acir_buffer_uncompressed).unwrap_err();
+    assert!(
+        matches!(&error, AcirComposerError::BackendError(BackendError::SrsTooSmall(_))),
+        "{:?}",
+        error
+    );
+    assert!(error.to_string().contains("SRS too small"), "{}", error);
+}
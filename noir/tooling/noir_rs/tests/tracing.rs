@@ -0,0 +1,94 @@
+//! Checks the spans emitted while proving, with a subscriber recording them.
+
+use std::{
+    fmt, fs,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use noir_rs::{prelude::*, prove_with_srs, witness};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+/// Names of the spans created, and the `u64` fields recorded on them.
+#[derive(Default)]
+struct Recorded {
+    spans: Vec<&'static str>,
+    fields: Vec<(&'static str, &'static str, u64)>,
+}
+
+struct Recorder {
+    recorded: Arc<Mutex<Recorded>>,
+    next_id: AtomicU64,
+}
+
+struct FieldVisitor<'a> {
+    span: &'static str,
+    fields: &'a mut Vec<(&'static str, &'static str, u64)>,
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.push((self.span, field.name(), value));
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.recorded.lock().unwrap().spans.push(span.metadata().name());
+        // Ids start at 1 and index the span names.
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut recorded = self.recorded.lock().unwrap();
+        let name = recorded.spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor { span: name, fields: &mut recorded.fields });
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn prove_emits_spans() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+
+    let recorded = Arc::new(Mutex::new(Recorded::default()));
+    let recorder = Recorder { recorded: recorded.clone(), next_id: AtomicU64::new(0) };
+    tracing::subscriber::with_default(recorder, || {
+        prove_with_srs(bytecode.trim(), &initial_witness, DevSrs::new(1 << 10)).unwrap();
+    });
+
+    let recorded = recorded.lock().unwrap();
+    assert_eq!(recorded.spans, ["prove", "execute", "srs_load", "create_proof"]);
+    let field = |span, name| {
+        recorded.fields.iter().find(|field| field.0 == span && field.1 == name).map(|field| field.2)
+    };
+    let circuit_size = field("prove", "circuit_size").unwrap();
+    assert!(circuit_size.is_power_of_two());
+    assert_eq!(field("create_proof", "circuit_size"), Some(circuit_size));
+    assert_eq!(field("srs_load", "num_points"), field("prove", "num_points"));
+    for span in ["prove", "execute", "srs_load", "create_proof"] {
+        assert!(field(span, "elapsed_ms").is_some(), "{} has no elapsed_ms", span);
+    }
+}
@@ -0,0 +1,42 @@
+//! The cache of verdicts is shared by the whole process, so enabling it runs in its own test
+//! binary, away from tests expecting every verification to load the SRS.
+
+use std::fs;
+
+use noir_rs::{
+    clear_verify_cache, prelude::*, set_verify_cache_capacity, verify_with_options,
+    verify_with_srs, witness, VerifyOptions,
+};
+
+const DEV_SRS_POINTS: u32 = 1 << 10;
+
+#[test]
+fn verify_cache_reuses_verdicts() {
+    let bytecode =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/circuit.b64"))
+            .unwrap();
+    let bytecode = bytecode.trim().to_string();
+    let initial_witness = witness::from_vec(vec![FieldElement::zero(), FieldElement::one()]);
+    let (proof, vk) = ProveOptions::new()
+        .srs(DevSrs::new(DEV_SRS_POINTS))
+        .prove(&bytecode, &initial_witness)
+        .unwrap();
+    // A source failing to load shows whether a verification ran the verifier.
+    let missing_srs = || LocalSrs::new(std::env::temp_dir().join("noir_rs_no_such_srs.dat"));
+    let verify = |options: &VerifyOptions| {
+        verify_with_options(bytecode.clone(), proof.clone(), vk.clone(), missing_srs(), options)
+    };
+
+    set_verify_cache_capacity(16);
+    let verdict =
+        verify_with_srs(bytecode.clone(), proof.clone(), vk.clone(), DevSrs::new(DEV_SRS_POINTS));
+    assert!(verdict.unwrap());
+    assert!(verify(&VerifyOptions::default()).unwrap());
+
+    // A verdict is not reused for a proof checked against an unchecked SRS.
+    let unchecked = VerifyOptions { skip_srs_check: true, ..Default::default() };
+    assert!(matches!(verify(&unchecked), Err(NoirRsError::Srs(_))));
+
+    clear_verify_cache();
+    assert!(matches!(verify(&VerifyOptions::default()), Err(NoirRsError::Srs(_))));
+}